@@ -0,0 +1,9 @@
+-- Format-specific metadata (e.g. Aseprite frames, tags and layers) stored as JSON
+CREATE TABLE IF NOT EXISTS asset_format_metadata (
+    asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    format      TEXT NOT NULL,
+    data        TEXT NOT NULL DEFAULT '{}',
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_format_metadata_format ON asset_format_metadata(format);
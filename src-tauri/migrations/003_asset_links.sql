@@ -0,0 +1,15 @@
+-- Files referenced by an asset (e.g. tileset images used by a Tiled map).
+-- target_asset_id is resolved against the library when the referenced file is imported.
+CREATE TABLE IF NOT EXISTS asset_links (
+    id               TEXT PRIMARY KEY,
+    asset_id         TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    target_asset_id  TEXT REFERENCES assets(id) ON DELETE SET NULL,
+    link_type        TEXT NOT NULL,
+    reference_path   TEXT NOT NULL,
+    reference_hash   TEXT NOT NULL DEFAULT '',
+    created_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_links_asset ON asset_links(asset_id);
+CREATE INDEX IF NOT EXISTS idx_asset_links_target ON asset_links(target_asset_id);
+CREATE INDEX IF NOT EXISTS idx_asset_links_hash ON asset_links(reference_hash);
@@ -0,0 +1,18 @@
+-- Technical metadata for image assets
+CREATE TABLE IF NOT EXISTS image_metadata (
+    asset_id          TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    color_type        TEXT NOT NULL DEFAULT '',
+    bit_depth         INTEGER NOT NULL DEFAULT 8,
+    has_alpha         INTEGER NOT NULL DEFAULT 0,
+    alpha_used        INTEGER NOT NULL DEFAULT 0,
+    frame_count       INTEGER NOT NULL DEFAULT 1,
+    has_icc_profile   INTEGER NOT NULL DEFAULT 0,
+    icc_profile_size  INTEGER,
+    dpi_x             REAL,
+    dpi_y             REAL,
+    text_entries      TEXT NOT NULL DEFAULT '{}',
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_image_metadata_alpha ON image_metadata(alpha_used);
+CREATE INDEX IF NOT EXISTS idx_image_metadata_frames ON image_metadata(frame_count);
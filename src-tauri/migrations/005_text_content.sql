@@ -0,0 +1,62 @@
+-- Indexed content of text, data and shader assets (truncated to the indexing limit)
+CREATE TABLE IF NOT EXISTS asset_contents (
+    asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    language    TEXT NOT NULL DEFAULT 'plaintext',
+    content     TEXT NOT NULL DEFAULT '',
+    byte_size   INTEGER NOT NULL DEFAULT 0,
+    line_count  INTEGER NOT NULL DEFAULT 0,
+    truncated   INTEGER NOT NULL DEFAULT 0,
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_contents_language ON asset_contents(language);
+
+-- Rebuild the FTS index as a standalone table so it can also hold file content
+DROP TRIGGER IF EXISTS assets_ai;
+DROP TRIGGER IF EXISTS assets_ad;
+DROP TRIGGER IF EXISTS assets_au;
+DROP TABLE IF EXISTS assets_fts;
+
+CREATE VIRTUAL TABLE assets_fts USING fts5(
+    file_name,
+    description,
+    ai_description,
+    content
+);
+
+INSERT INTO assets_fts(rowid, file_name, description, ai_description, content)
+SELECT a.rowid, a.file_name, a.description, a.ai_description, COALESCE(c.content, '')
+FROM assets a
+LEFT JOIN asset_contents c ON c.asset_id = a.id;
+
+CREATE TRIGGER IF NOT EXISTS assets_ai AFTER INSERT ON assets BEGIN
+    INSERT INTO assets_fts(rowid, file_name, description, ai_description, content)
+    VALUES (new.rowid, new.file_name, new.description, new.ai_description,
+            COALESCE((SELECT content FROM asset_contents WHERE asset_id = new.id), ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS assets_ad AFTER DELETE ON assets BEGIN
+    DELETE FROM assets_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS assets_au AFTER UPDATE ON assets BEGIN
+    DELETE FROM assets_fts WHERE rowid = old.rowid;
+    INSERT INTO assets_fts(rowid, file_name, description, ai_description, content)
+    VALUES (new.rowid, new.file_name, new.description, new.ai_description,
+            COALESCE((SELECT content FROM asset_contents WHERE asset_id = new.id), ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_ai AFTER INSERT ON asset_contents BEGIN
+    UPDATE assets_fts SET content = new.content
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_au AFTER UPDATE ON asset_contents BEGIN
+    UPDATE assets_fts SET content = new.content
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_ad AFTER DELETE ON asset_contents BEGIN
+    UPDATE assets_fts SET content = ''
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = old.asset_id);
+END;
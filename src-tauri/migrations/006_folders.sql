@@ -0,0 +1,21 @@
+-- Folders with stable IDs. Paths are canonical ('/a/b', no trailing slash) and the root has no row.
+CREATE TABLE IF NOT EXISTS folders (
+    id              TEXT PRIMARY KEY,
+    library_id      TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    parent_id       TEXT REFERENCES folders(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    path            TEXT NOT NULL,
+    color           TEXT,
+    icon            TEXT,
+    description     TEXT NOT NULL DEFAULT '',
+    default_tag_ids TEXT NOT NULL DEFAULT '[]',
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(library_id, path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_folders_parent ON folders(parent_id);
+
+-- Bring asset folder paths moved without a leading slash into the canonical format
+UPDATE assets SET folder_path = '/' || folder_path WHERE folder_path NOT LIKE '/%';
+UPDATE assets SET folder_path = rtrim(folder_path, '/') WHERE length(folder_path) > 1 AND folder_path LIKE '%/';
@@ -0,0 +1,22 @@
+-- Previous files of an asset, kept when its file is replaced.
+-- Files live under .versions/<asset_id>/ in the library root.
+CREATE TABLE IF NOT EXISTS asset_versions (
+    id              TEXT PRIMARY KEY,
+    asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    version_number  INTEGER NOT NULL,
+    relative_path   TEXT NOT NULL,
+    original_name   TEXT NOT NULL,
+    file_type       TEXT NOT NULL,
+    mime_type       TEXT NOT NULL,
+    file_size       INTEGER NOT NULL,
+    file_hash       TEXT NOT NULL,
+    width           INTEGER,
+    height          INTEGER,
+    duration_ms     INTEGER,
+    thumbnail_path  TEXT,
+    note            TEXT NOT NULL DEFAULT '',
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(asset_id, version_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_versions_asset ON asset_versions(asset_id);
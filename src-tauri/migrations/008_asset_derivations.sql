@@ -0,0 +1,27 @@
+-- Lineage of assets produced by processing operations (background removal,
+-- splitting, sprite sheets, compression). The operation and its parameters are
+-- stored so the output can be regenerated when a source changes.
+CREATE TABLE IF NOT EXISTS asset_derivations (
+    id              TEXT PRIMARY KEY,
+    asset_id        TEXT NOT NULL UNIQUE REFERENCES assets(id) ON DELETE CASCADE,
+    operation       TEXT NOT NULL,
+    params          TEXT NOT NULL DEFAULT '{}',
+    -- Outputs created by the same run share a batch, e.g. the parts of a split
+    batch_id        TEXT NOT NULL,
+    output_index    INTEGER NOT NULL DEFAULT 0,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_derivations_batch ON asset_derivations(batch_id);
+
+-- Ordered source assets of a derivation. The source name is kept for display
+-- after the source asset has been deleted.
+CREATE TABLE IF NOT EXISTS asset_derivation_sources (
+    derivation_id   TEXT NOT NULL REFERENCES asset_derivations(id) ON DELETE CASCADE,
+    position        INTEGER NOT NULL,
+    source_asset_id TEXT REFERENCES assets(id) ON DELETE SET NULL,
+    source_name     TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (derivation_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_derivation_sources_asset ON asset_derivation_sources(source_asset_id);
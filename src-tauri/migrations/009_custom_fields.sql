@@ -0,0 +1,104 @@
+-- User-defined typed fields per library
+CREATE TABLE IF NOT EXISTS custom_fields (
+    id          TEXT PRIMARY KEY,
+    library_id  TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    field_type  TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'boolean', 'enum', 'date')),
+    -- Allowed values of enum fields, as a JSON array
+    options     TEXT NOT NULL DEFAULT '[]',
+    position    INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(library_id, name)
+);
+
+-- Field values per asset. Text, enum and date (YYYY-MM-DD) values are stored in
+-- text_value, numbers and booleans (0/1) in number_value.
+CREATE TABLE IF NOT EXISTS asset_field_values (
+    asset_id     TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    field_id     TEXT NOT NULL REFERENCES custom_fields(id) ON DELETE CASCADE,
+    text_value   TEXT,
+    number_value REAL,
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (asset_id, field_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_field_values_field ON asset_field_values(field_id, text_value, number_value);
+
+-- Rebuild the FTS index with a column for text and enum field values
+DROP TRIGGER IF EXISTS assets_ai;
+DROP TRIGGER IF EXISTS assets_ad;
+DROP TRIGGER IF EXISTS assets_au;
+DROP TRIGGER IF EXISTS asset_contents_ai;
+DROP TRIGGER IF EXISTS asset_contents_au;
+DROP TRIGGER IF EXISTS asset_contents_ad;
+DROP TABLE IF EXISTS assets_fts;
+
+CREATE VIRTUAL TABLE assets_fts USING fts5(
+    file_name,
+    description,
+    ai_description,
+    content,
+    fields
+);
+
+CREATE VIEW IF NOT EXISTS asset_field_text AS
+SELECT v.asset_id, group_concat(v.text_value, ' ') AS text
+FROM asset_field_values v
+JOIN custom_fields f ON f.id = v.field_id
+WHERE f.field_type IN ('text', 'enum') AND v.text_value IS NOT NULL
+GROUP BY v.asset_id;
+
+INSERT INTO assets_fts(rowid, file_name, description, ai_description, content, fields)
+SELECT a.rowid, a.file_name, a.description, a.ai_description, COALESCE(c.content, ''), COALESCE(t.text, '')
+FROM assets a
+LEFT JOIN asset_contents c ON c.asset_id = a.id
+LEFT JOIN asset_field_text t ON t.asset_id = a.id;
+
+CREATE TRIGGER IF NOT EXISTS assets_ai AFTER INSERT ON assets BEGIN
+    INSERT INTO assets_fts(rowid, file_name, description, ai_description, content, fields)
+    VALUES (new.rowid, new.file_name, new.description, new.ai_description,
+            COALESCE((SELECT content FROM asset_contents WHERE asset_id = new.id), ''),
+            COALESCE((SELECT text FROM asset_field_text WHERE asset_id = new.id), ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS assets_ad AFTER DELETE ON assets BEGIN
+    DELETE FROM assets_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS assets_au AFTER UPDATE ON assets BEGIN
+    DELETE FROM assets_fts WHERE rowid = old.rowid;
+    INSERT INTO assets_fts(rowid, file_name, description, ai_description, content, fields)
+    VALUES (new.rowid, new.file_name, new.description, new.ai_description,
+            COALESCE((SELECT content FROM asset_contents WHERE asset_id = new.id), ''),
+            COALESCE((SELECT text FROM asset_field_text WHERE asset_id = new.id), ''));
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_ai AFTER INSERT ON asset_contents BEGIN
+    UPDATE assets_fts SET content = new.content
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_au AFTER UPDATE ON asset_contents BEGIN
+    UPDATE assets_fts SET content = new.content
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_contents_ad AFTER DELETE ON asset_contents BEGIN
+    UPDATE assets_fts SET content = ''
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = old.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_field_values_ai AFTER INSERT ON asset_field_values BEGIN
+    UPDATE assets_fts SET fields = COALESCE((SELECT text FROM asset_field_text WHERE asset_id = new.asset_id), '')
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_field_values_au AFTER UPDATE ON asset_field_values BEGIN
+    UPDATE assets_fts SET fields = COALESCE((SELECT text FROM asset_field_text WHERE asset_id = new.asset_id), '')
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = new.asset_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_field_values_ad AFTER DELETE ON asset_field_values BEGIN
+    UPDATE assets_fts SET fields = COALESCE((SELECT text FROM asset_field_text WHERE asset_id = old.asset_id), '')
+    WHERE rowid = (SELECT rowid FROM assets WHERE id = old.asset_id);
+END;
@@ -0,0 +1,7 @@
+-- Quick triage attributes: 0-5 star rating, color label and favorite flag
+ALTER TABLE assets ADD COLUMN rating INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE assets ADD COLUMN color_label TEXT;
+ALTER TABLE assets ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_assets_rating ON assets(library_id, rating);
+CREATE INDEX IF NOT EXISTS idx_assets_favorite ON assets(library_id, is_favorite);
@@ -0,0 +1,25 @@
+-- Collections group assets across folders. Manual collections list their
+-- members in collection_assets. Smart collections store a query (JSON) that
+-- is evaluated whenever the collection is listed.
+CREATE TABLE IF NOT EXISTS collections (
+    id          TEXT PRIMARY KEY,
+    library_id  TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    kind        TEXT NOT NULL CHECK (kind IN ('manual', 'smart')),
+    query       TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(library_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS collection_assets (
+    collection_id TEXT NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    asset_id      TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    position      INTEGER NOT NULL,
+    added_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (collection_id, asset_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_assets_asset ON collection_assets(asset_id);
+CREATE INDEX IF NOT EXISTS idx_collection_assets_position ON collection_assets(collection_id, position);
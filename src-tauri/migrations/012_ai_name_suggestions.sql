@@ -0,0 +1,6 @@
+-- File names suggested by AI tagging, the latest one per asset
+CREATE TABLE IF NOT EXISTS ai_name_suggestions (
+    asset_id        TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    suggested_name  TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
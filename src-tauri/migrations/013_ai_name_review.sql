@@ -0,0 +1,5 @@
+-- Review state of AI name suggestions and the per-library auto-apply setting
+ALTER TABLE ai_name_suggestions ADD COLUMN status TEXT NOT NULL DEFAULT 'pending';
+ALTER TABLE libraries ADD COLUMN auto_apply_ai_names INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_ai_name_suggestions_status ON ai_name_suggestions(status);
@@ -0,0 +1,37 @@
+-- Review comments on assets. Replies point at their parent comment. A comment
+-- can be anchored to a point (x, y) or a rectangle on an image, in pixels, or to
+-- a time in audio and video.
+CREATE TABLE IF NOT EXISTS asset_comments (
+    id              TEXT PRIMARY KEY,
+    asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    parent_id       TEXT REFERENCES asset_comments(id) ON DELETE CASCADE,
+    author          TEXT NOT NULL DEFAULT '',
+    body            TEXT NOT NULL,
+    anchor_x        REAL,
+    anchor_y        REAL,
+    anchor_width    REAL,
+    anchor_height   REAL,
+    anchor_time_ms  INTEGER,
+    resolved        INTEGER NOT NULL DEFAULT 0,
+    resolved_at     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_comments_asset ON asset_comments(asset_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_asset_comments_parent ON asset_comments(parent_id);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS asset_comments_fts USING fts5(author, body);
+
+CREATE TRIGGER IF NOT EXISTS asset_comments_ai AFTER INSERT ON asset_comments BEGIN
+    INSERT INTO asset_comments_fts(rowid, author, body) VALUES (new.rowid, new.author, new.body);
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_comments_ad AFTER DELETE ON asset_comments BEGIN
+    DELETE FROM asset_comments_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS asset_comments_au AFTER UPDATE ON asset_comments BEGIN
+    DELETE FROM asset_comments_fts WHERE rowid = old.rowid;
+    INSERT INTO asset_comments_fts(rowid, author, body) VALUES (new.rowid, new.author, new.body);
+END;
@@ -0,0 +1,14 @@
+-- License and attribution of assets. `requires_attribution` is derived from the
+-- license when it is set, unless given explicitly.
+CREATE TABLE IF NOT EXISTS asset_licenses (
+    asset_id              TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    license               TEXT NOT NULL DEFAULT '',
+    author                TEXT NOT NULL DEFAULT '',
+    source_url            TEXT NOT NULL DEFAULT '',
+    attribution           TEXT NOT NULL DEFAULT '',
+    requires_attribution  INTEGER NOT NULL DEFAULT 0,
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_licenses_license ON asset_licenses(license);
+CREATE INDEX IF NOT EXISTS idx_asset_licenses_attribution ON asset_licenses(requires_attribution);
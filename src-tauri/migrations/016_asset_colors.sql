@@ -0,0 +1,19 @@
+-- Dominant colors of image assets, most common first. Colors are stored in
+-- CIELAB so that searches can use perceptual distance; coverage is the share
+-- of the visible (non-transparent) pixels.
+CREATE TABLE IF NOT EXISTS asset_colors (
+    asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    position    INTEGER NOT NULL,
+    hex         TEXT NOT NULL,
+    l           REAL NOT NULL,
+    a           REAL NOT NULL,
+    b           REAL NOT NULL,
+    coverage    REAL NOT NULL,
+    PRIMARY KEY (asset_id, position)
+);
+
+-- Images without visible pixels get an empty palette and are marked as analyzed here
+CREATE TABLE IF NOT EXISTS asset_palette_status (
+    asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,23 @@
+-- Files of game projects that were matched to library assets by a usage scan.
+-- Rescanning a project replaces its rows.
+CREATE TABLE IF NOT EXISTS asset_usages (
+    id              TEXT PRIMARY KEY,
+    library_id      TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    project_path    TEXT NOT NULL,
+    -- Path of the matched file relative to the project
+    file_path       TEXT NOT NULL,
+    -- "exact" (same content hash) or "perceptual" (similar image)
+    match_kind      TEXT NOT NULL DEFAULT 'exact',
+    scanned_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_usages_asset ON asset_usages(asset_id);
+CREATE INDEX IF NOT EXISTS idx_asset_usages_project ON asset_usages(library_id, project_path);
+
+-- Perceptual hashes of image assets, cached for the file content they were computed from
+CREATE TABLE IF NOT EXISTS asset_perceptual_hashes (
+    asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+    file_hash   TEXT NOT NULL,
+    dhash       INTEGER NOT NULL
+);
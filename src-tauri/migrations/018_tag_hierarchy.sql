@@ -0,0 +1,10 @@
+-- Hierarchical tags. `name` is the last segment, `path` the full name ('character/enemy/boss').
+-- Deleting a tag deletes its subtree.
+ALTER TABLE tags ADD COLUMN parent_id TEXT REFERENCES tags(id) ON DELETE CASCADE;
+ALTER TABLE tags ADD COLUMN path TEXT NOT NULL DEFAULT '';
+
+UPDATE tags SET path = name;
+
+DROP INDEX IF EXISTS idx_tags_name_library;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_path_library ON tags(library_id, path);
+CREATE INDEX IF NOT EXISTS idx_tags_parent ON tags(parent_id);
@@ -0,0 +1,11 @@
+-- Alternative names of tags (plurals, spellings, translations). Looking up a tag by an
+-- alias finds its canonical tag. Latin letters are compared case-insensitively.
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    library_id  TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    alias       TEXT NOT NULL COLLATE NOCASE,
+    tag_id      TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (library_id, alias)
+);
+
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_tag ON tag_aliases(tag_id);
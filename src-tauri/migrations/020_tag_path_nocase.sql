@@ -0,0 +1,2 @@
+-- Tag paths are looked up ignoring the case of Latin letters, so 'Tree' finds 'tree'
+CREATE INDEX IF NOT EXISTS idx_tags_path_nocase ON tags(library_id, path COLLATE NOCASE);
@@ -0,0 +1,2 @@
+-- Perceptual hashes now composite transparency onto a gray background; recompute them
+DELETE FROM asset_perceptual_hashes;
@@ -7,6 +7,7 @@ use crate::ai::{
     provider::{AiProvider, AiProviderManager, OpenAiCompatibleProvider},
     tagging::analyze_image_file,
 };
+use crate::commands::rename::ai_file_name;
 use crate::db::{models::*, queries};
 use crate::error::AppError;
 
@@ -15,6 +16,8 @@ pub struct AiTagResult {
     pub tags: Vec<Tag>,
     pub description: String,
     pub suggested_name: Option<String>,
+    /// New file name if the suggested name was applied
+    pub applied_name: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -32,15 +35,28 @@ pub struct AiConfigInput {
     pub embedding_model: String,
 }
 
+/// Tag and describe an image asset with AI. The suggested name is applied to the asset
+/// if `apply_name` is set (defaulting to the library's `auto_apply_ai_names`), otherwise
+/// it is queued for review.
 #[tauri::command]
 pub async fn ai_tag_asset(
     asset_id: String,
+    apply_name: Option<bool>,
     pool: State<'_, SqlitePool>,
     ai_manager: State<'_, AiProviderManager>,
+) -> Result<AiTagResult, AppError> {
+    tag_asset_with_ai(&pool, &ai_manager, &asset_id, apply_name).await
+}
+
+pub(crate) async fn tag_asset_with_ai(
+    pool: &SqlitePool,
+    ai_manager: &AiProviderManager,
+    asset_id: &str,
+    apply_name: Option<bool>,
 ) -> Result<AiTagResult, AppError> {
     tracing::info!("=== AI Tagging Started for asset: {} ===", asset_id);
 
-    let asset = queries::get_asset(&pool, &asset_id).await?;
+    let asset = queries::get_asset(pool, asset_id).await?;
     tracing::info!("Asset info: file_name={}, file_type={}", asset.file_name, asset.file_type);
 
     if asset.file_type != "image" {
@@ -50,12 +66,12 @@ pub async fn ai_tag_asset(
         ));
     }
 
-    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let library = queries::get_library(pool, &asset.library_id).await?;
     let file_path = std::path::Path::new(&library.root_path).join(&asset.relative_path);
     tracing::info!("Image file path: {:?}", file_path);
 
     tracing::info!("Calling AI vision model to analyze image...");
-    let analysis = analyze_image_file(&file_path, &ai_manager).await?;
+    let analysis = analyze_image_file(&file_path, ai_manager).await?;
     tracing::info!("AI analysis completed:");
     tracing::info!("  Description: {}", analysis.description);
     tracing::info!("  Suggested tags: {:?}", analysis.tags.iter().map(|t| &t.name).collect::<Vec<_>>());
@@ -65,25 +81,42 @@ pub async fn ai_tag_asset(
 
     // Update asset description
     tracing::info!("Updating asset description...");
-    queries::update_asset_description(&pool, &asset_id, &analysis.description).await?;
+    queries::update_asset_description(pool, asset_id, &analysis.description).await?;
+
+    // Keep the suggested name for review and renaming ({ai_name} in rename templates)
+    let mut applied_name = None;
+    if let Some(name) = analysis.suggested_name.as_deref().filter(|n| !n.trim().is_empty()) {
+        queries::save_ai_name_suggestion(pool, asset_id, name.trim()).await?;
+        if apply_name.unwrap_or(library.auto_apply_ai_names) {
+            match ai_file_name(pool, &asset, name.trim()).await {
+                Ok(new_name) => {
+                    tracing::info!("Applying suggested name: {}", new_name);
+                    queries::rename_asset(pool, asset_id, &new_name).await?;
+                    queries::set_ai_name_suggestion_status(pool, asset_id, "accepted").await?;
+                    applied_name = Some(new_name);
+                }
+                Err(e) => tracing::warn!("Suggested name left for review: {}", e),
+            }
+        }
+    }
 
     // Create/assign tags
     let mut assigned_tags = Vec::new();
     tracing::info!("Creating and assigning {} tags...", analysis.tags.len());
     for suggested in &analysis.tags {
-        let tag = queries::get_or_create_tag(&pool, &asset.library_id, &suggested.name, true)
+        let tag = queries::get_or_create_tag(pool, &asset.library_id, &suggested.name, true)
             .await?;
         tracing::info!("  Tag created/found: {} (id: {})", tag.name, tag.id);
-        queries::assign_tags(&pool, &asset_id, &[tag.id.clone()]).await?;
+        queries::assign_tags(pool, asset_id, &[tag.id.clone()]).await?;
         assigned_tags.push(tag);
     }
 
     // Generate and store embedding for semantic search
     tracing::info!("Generating embedding for semantic search...");
     if let Ok(embedding_bytes) =
-        embed_text_to_bytes(&analysis.description, &ai_manager).await
+        embed_text_to_bytes(&analysis.description, ai_manager).await
     {
-        queries::save_embedding(&pool, &asset_id, "default", &embedding_bytes).await?;
+        queries::save_embedding(pool, asset_id, "default", &embedding_bytes).await?;
         tracing::info!("Embedding saved successfully");
     } else {
         tracing::warn!("Failed to generate embedding");
@@ -95,9 +128,71 @@ pub async fn ai_tag_asset(
         tags: assigned_tags,
         description: analysis.description,
         suggested_name: analysis.suggested_name,
+        applied_name,
     })
 }
 
+/// AI name suggestions of a library that are waiting for review
+#[tauri::command]
+pub async fn get_name_suggestions(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<NameSuggestion>, AppError> {
+    let suggestions = queries::get_pending_name_suggestions(&pool, &library_id).await?;
+    Ok(suggestions)
+}
+
+/// Apply the suggested names of the given assets, keeping their extensions. Names taken in
+/// the folder get a numeric suffix. Assets whose suggestion cannot be applied report an
+/// error and stay pending.
+#[tauri::command]
+pub async fn accept_name_suggestions(
+    asset_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<RenamePreview>, AppError> {
+    let mut results = Vec::new();
+    for asset_id in asset_ids {
+        let asset = queries::get_asset(&pool, &asset_id).await?;
+        let Some(suggested) = queries::get_ai_name_suggestion(&pool, &asset_id).await? else {
+            results.push(RenamePreview {
+                asset_id,
+                old_name: asset.file_name.clone(),
+                new_name: asset.file_name,
+                error: Some("Asset has no AI suggested name".to_string()),
+            });
+            continue;
+        };
+
+        let (new_name, error) = match ai_file_name(&pool, &asset, &suggested).await {
+            Ok(new_name) => {
+                queries::rename_asset(&pool, &asset_id, &new_name).await?;
+                queries::set_ai_name_suggestion_status(&pool, &asset_id, "accepted").await?;
+                (new_name, None)
+            }
+            Err(e) => (asset.file_name.clone(), Some(e.to_string())),
+        };
+        results.push(RenamePreview {
+            asset_id,
+            old_name: asset.file_name,
+            new_name,
+            error,
+        });
+    }
+    Ok(results)
+}
+
+/// Dismiss the suggested names of the given assets
+#[tauri::command]
+pub async fn reject_name_suggestions(
+    asset_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    for asset_id in &asset_ids {
+        queries::set_ai_name_suggestion_status(&pool, asset_id, "rejected").await?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn ai_semantic_search(
     library_id: String,
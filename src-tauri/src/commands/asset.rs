@@ -4,17 +4,27 @@ use uuid::Uuid;
 use base64::Engine;
 
 use crate::db::{
-    models::{Asset, AssetDetail, FolderInfo, PaginatedAssets},
+    models::{Asset, AssetCursor, AssetDetail, AssetFilter, FormatMetadata, LicenseUpdate, PaginatedAssets},
     queries,
 };
 use crate::error::AppError;
-use crate::storage::{file_ops, thumbnail};
+use crate::commands::field::validate_field_conditions;
+use crate::commands::license::update_asset_licenses;
+use crate::commands::processing::asset_lineage;
+use crate::processing::aseprite::{self, AsepriteFile};
+use crate::processing::tiled::{self, TiledDocument};
+use crate::storage::{file_ops, image_metadata, palette, text_content, thumbnail};
 
 /// Supported file extensions for import
 const SUPPORTED_EXTENSIONS: &[&str] = &[
     "png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "tiff",
+    "aseprite", "ase",
+    "tmx", "tmj", "tsx", "tsj",
     "mp3", "wav", "ogg", "flac", "aac", "m4a",
     "mp4", "avi", "mov", "webm",
+    "json", "xml", "yaml", "yml", "toml", "txt", "md", "csv", "ini", "cfg",
+    "glsl", "vert", "frag", "comp", "geom", "tesc", "tese",
+    "hlsl", "hlsli", "fx", "fxh", "wgsl", "shader", "cginc",
 ];
 
 /// Check if a file has a supported extension
@@ -45,11 +55,14 @@ fn collect_files(path: &std::path::Path) -> Vec<std::path::PathBuf> {
     files
 }
 
+/// Import files and directories into a folder of the library. With `license`, every
+/// imported asset gets the same license and attribution, e.g. for an asset pack.
 #[tauri::command]
 pub async fn import_assets(
     library_id: String,
     file_paths: Vec<String>,
     folder_path: String,
+    license: Option<LicenseUpdate>,
     pool: State<'_, SqlitePool>,
 ) -> Result<Vec<Asset>, AppError> {
     // Debug logging
@@ -73,6 +86,12 @@ pub async fn import_assets(
 
     tracing::info!("Total files to import: {}", all_files.len());
 
+    // Import Tiled documents last so the tileset images they reference are already in the library
+    all_files.sort_by_key(|path| tiled::is_tiled(path));
+
+    let folder = file_ops::normalize_folder_path(&folder_path)
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", folder_path)))?;
+
     let mut imported = Vec::new();
 
     for source in &all_files {
@@ -87,38 +106,11 @@ pub async fn import_assets(
             .unwrap_or("unknown")
             .to_string();
 
-        let mime_type = file_ops::guess_mime_type(source);
-        let file_type = file_ops::file_type_from_mime(&mime_type).to_string();
-        let file_size = file_ops::get_file_size(source)? as i64;
-        let file_hash = file_ops::compute_file_hash(source)?;
-
-        // Get image dimensions if applicable
-        let (width, height) = if file_type == "image" {
-            file_ops::get_image_dimensions(source)
-                .map(|(w, h)| (Some(w as i32), Some(h as i32)))
-                .unwrap_or((None, None))
-        } else {
-            (None, None)
-        };
+        let file = inspect_file(source, &library_root, &asset_id)?;
 
         // Copy file to library
         let relative_path =
-            file_ops::copy_to_library(source, &library_root, &folder_path, &asset_id)?;
-
-        // Generate thumbnail for images
-        let thumbnail_path = if file_type == "image" {
-            thumbnail::generate_thumbnail(source, &library_root, &asset_id).ok()
-        } else {
-            None
-        };
-
-        let folder = if folder_path.is_empty() {
-            "/".to_string()
-        } else if !folder_path.starts_with('/') {
-            format!("/{}", folder_path)
-        } else {
-            folder_path.clone()
-        };
+            file_ops::copy_to_library(source, &library_root, &folder, &asset_id)?;
 
         let asset = Asset {
             id: asset_id,
@@ -126,49 +118,328 @@ pub async fn import_assets(
             file_name: original_name.clone(),
             original_name,
             relative_path,
-            file_type,
-            mime_type,
-            file_size,
-            file_hash,
-            width,
-            height,
-            duration_ms: None,
+            file_type: file.file_type.clone(),
+            mime_type: file.mime_type.clone(),
+            file_size: file.file_size,
+            file_hash: file.file_hash.clone(),
+            width: file.width,
+            height: file.height,
+            duration_ms: file.duration_ms,
             description: String::new(),
             ai_description: String::new(),
-            thumbnail_path,
-            folder_path: folder,
+            thumbnail_path: file.thumbnail_path.clone(),
+            folder_path: folder.clone(),
+            rating: 0,
+            color_label: None,
+            is_favorite: false,
             created_at: String::new(),
             updated_at: String::new(),
             imported_at: String::new(),
         };
 
         let saved = queries::insert_asset(&pool, &asset).await?;
+        queries::apply_folder_default_tags(&pool, &library_id, &folder, std::slice::from_ref(&saved.id)).await?;
+        record_file_details(&pool, &saved, source, &file).await?;
+
         imported.push(saved);
     }
 
+    if let Some(license) = &license {
+        let ids: Vec<String> = imported.iter().map(|a| a.id.clone()).collect();
+        update_asset_licenses(&pool, &ids, license).await?;
+    }
+
     Ok(imported)
 }
 
+/// What is known about a file before it is stored as (or replaces) an asset
+pub(crate) struct InspectedFile {
+    pub(crate) mime_type: String,
+    pub(crate) file_type: String,
+    pub(crate) file_size: i64,
+    pub(crate) file_hash: String,
+    pub(crate) width: Option<i32>,
+    pub(crate) height: Option<i32>,
+    pub(crate) duration_ms: Option<i64>,
+    pub(crate) thumbnail_path: Option<String>,
+    pub(crate) aseprite: Option<AsepriteFile>,
+    pub(crate) tiled: Option<TiledDocument>,
+    pub(crate) text: Option<text_content::TextContent>,
+}
+
+/// Read a file's type, hash, dimensions and format details, and generate the
+/// thumbnail for `asset_id`
+pub(crate) fn inspect_file(
+    source: &std::path::Path,
+    library_root: &std::path::Path,
+    asset_id: &str,
+) -> Result<InspectedFile, AppError> {
+    let mime_type = file_ops::guess_mime_type(source);
+    let file_type = file_ops::file_type_from_mime(&mime_type).to_string();
+    let file_size = file_ops::get_file_size(source)? as i64;
+    let file_hash = file_ops::compute_file_hash(source)?;
+
+    // Aseprite documents are parsed once for dimensions, thumbnail and metadata
+    let aseprite_file = if aseprite::is_aseprite(source) {
+        match AsepriteFile::open(source) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                tracing::warn!("Failed to parse Aseprite file {:?}: {}", source, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let tiled_doc = if tiled::is_tiled(source) {
+        match TiledDocument::open(source) {
+            Ok(doc) => Some(doc),
+            Err(e) => {
+                tracing::warn!("Failed to parse Tiled file {:?}: {}", source, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let text_content = if file_type == "text" {
+        text_content::read_text_content(source)
+    } else {
+        None
+    };
+
+    // Get image dimensions if applicable
+    let (width, height) = if let Some(ase) = &aseprite_file {
+        (Some(ase.width as i32), Some(ase.height as i32))
+    } else if let Some(doc) = &tiled_doc {
+        doc.pixel_size()
+            .map(|(w, h)| (Some(w as i32), Some(h as i32)))
+            .unwrap_or((None, None))
+    } else if file_type == "image" {
+        file_ops::get_image_dimensions(source)
+            .map(|(w, h)| (Some(w as i32), Some(h as i32)))
+            .unwrap_or((None, None))
+    } else {
+        (None, None)
+    };
+
+    // Animated Aseprite documents report their total playback length
+    let duration_ms = aseprite_file
+        .as_ref()
+        .filter(|ase| ase.frame_count() > 1)
+        .map(|ase| ase.metadata().total_duration_ms);
+
+    // Generate thumbnail for images (first frame for Aseprite documents)
+    let thumbnail_path = if let Some(ase) = &aseprite_file {
+        ase.render_frame(0).ok().and_then(|frame| {
+            thumbnail::generate_thumbnail_from_image(
+                &image::DynamicImage::ImageRgba8(frame),
+                library_root,
+                asset_id,
+            )
+            .ok()
+        })
+    } else if let Some(doc) = &tiled_doc {
+        doc.render_preview().and_then(|preview| {
+            thumbnail::generate_thumbnail_from_image(
+                &image::DynamicImage::ImageRgba8(preview),
+                library_root,
+                asset_id,
+            )
+            .ok()
+        })
+    } else if let Some(text) = &text_content {
+        thumbnail::generate_thumbnail_from_image(
+            &text_content::render_code_preview(&text.content),
+            library_root,
+            asset_id,
+        )
+        .ok()
+    } else if file_type == "image" {
+        thumbnail::generate_thumbnail(source, library_root, asset_id).ok()
+    } else {
+        None
+    };
+
+    Ok(InspectedFile {
+        mime_type,
+        file_type,
+        file_size,
+        file_hash,
+        width,
+        height,
+        duration_ms,
+        thumbnail_path,
+        aseprite: aseprite_file,
+        tiled: tiled_doc,
+        text: text_content,
+    })
+}
+
+/// Store the format-specific details of an inspected file for a saved asset
+pub(crate) async fn record_file_details(
+    pool: &SqlitePool,
+    asset: &Asset,
+    source: &std::path::Path,
+    file: &InspectedFile,
+) -> Result<(), AppError> {
+    if let Some(ase) = &file.aseprite {
+        let metadata = serde_json::to_string(&ase.metadata())?;
+        queries::save_format_metadata(pool, &asset.id, "aseprite", &metadata).await?;
+    }
+
+    if let Some(doc) = &file.tiled {
+        let metadata = serde_json::to_string(&doc.metadata)?;
+        queries::save_format_metadata(pool, &asset.id, "tiled", &metadata).await?;
+        link_tiled_references(pool, &asset.library_id, &asset.id, doc).await?;
+    }
+
+    if asset.file_type == "image" {
+        record_image_metadata(pool, source, &asset.id).await?;
+        record_palette(pool, source, &asset.id).await?;
+    }
+
+    if let Some(text) = &file.text {
+        queries::save_text_content(
+            pool,
+            &asset.id,
+            &text.language,
+            &text.content,
+            text.byte_size,
+            text.line_count,
+            text.truncated,
+        )
+        .await?;
+    }
+
+    // Earlier imports may have been waiting for this file
+    queries::resolve_asset_links(pool, &asset.library_id, &asset.id, &asset.file_hash).await?;
+    Ok(())
+}
+
+/// Extract and store technical metadata for an image asset.
+/// Files the decoder cannot read (e.g. SVG) are logged and skipped.
+pub(crate) async fn record_image_metadata(
+    pool: &SqlitePool,
+    path: &std::path::Path,
+    asset_id: &str,
+) -> Result<(), AppError> {
+    match image_metadata::extract_image_metadata(path, asset_id) {
+        Ok(metadata) => queries::save_image_metadata(pool, &metadata).await?,
+        Err(e) => tracing::warn!("Failed to extract image metadata from {:?}: {}", path, e),
+    }
+    Ok(())
+}
+
+/// Extract and store the dominant colors of an image asset.
+/// Files the decoder cannot read are logged and skipped.
+pub(crate) async fn record_palette(
+    pool: &SqlitePool,
+    path: &std::path::Path,
+    asset_id: &str,
+) -> Result<(), AppError> {
+    match palette::extract_palette(path) {
+        Ok(colors) => queries::save_asset_palette(pool, asset_id, &colors).await?,
+        Err(e) => tracing::warn!("Failed to extract color palette from {:?}: {}", path, e),
+    }
+    Ok(())
+}
+
+/// Record the tilesets and images a Tiled document references, linking each to
+/// the library asset with the same content (or, if the file is missing, the same name)
+async fn link_tiled_references(
+    pool: &SqlitePool,
+    library_id: &str,
+    asset_id: &str,
+    doc: &TiledDocument,
+) -> Result<(), AppError> {
+    for reference in doc.references() {
+        let hash = file_ops::compute_file_hash(&reference.resolved_path).ok();
+        let target = match &hash {
+            Some(hash) => queries::find_asset_by_hash(pool, library_id, hash).await?,
+            None => {
+                let name = reference
+                    .resolved_path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or(&reference.reference_path);
+                queries::find_asset_by_original_name(pool, library_id, name).await?
+            }
+        };
+
+        queries::insert_asset_link(
+            pool,
+            asset_id,
+            target.as_ref().map(|a| a.id.as_str()),
+            &reference.link_type,
+            &reference.reference_path,
+            hash.as_deref().unwrap_or(""),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Normalize the folder path of a filter from the frontend and check its field conditions
+pub(crate) async fn normalize_filter(
+    pool: &SqlitePool,
+    mut filter: AssetFilter,
+) -> Result<AssetFilter, AppError> {
+    if let Some(path) = &filter.folder_path {
+        filter.folder_path = Some(
+            file_ops::normalize_folder_path(path)
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", path)))?,
+        );
+    }
+    validate_field_conditions(pool, &filter.fields).await?;
+    Ok(filter)
+}
+
+/// Decode the `next_cursor` of a previous page
+pub(crate) fn parse_cursor(cursor: Option<&str>) -> Result<Option<AssetCursor>, AppError> {
+    cursor
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| {
+            AssetCursor::decode(c)
+                .ok_or_else(|| AppError::InvalidInput("Invalid pagination cursor".to_string()))
+        })
+        .transpose()
+}
+
+/// List a library's assets page by page. `folder_path` is shorthand for the filter's
+/// folder. Pass the `next_cursor` of a page as `cursor` to continue after it; the
+/// cursor is only valid for the same filter and sort order.
 #[tauri::command]
 pub async fn get_assets(
     library_id: String,
     folder_path: Option<String>,
     file_type: Option<String>,
+    filter: Option<AssetFilter>,
     page: u32,
     page_size: u32,
     sort_by: String,
     sort_order: String,
+    cursor: Option<String>,
     pool: State<'_, SqlitePool>,
 ) -> Result<PaginatedAssets, AppError> {
+    let mut filter = filter.unwrap_or_default();
+    if filter.folder_path.is_none() {
+        filter.folder_path = folder_path;
+    }
+    let filter = normalize_filter(&pool, filter).await?;
+    let cursor = parse_cursor(cursor.as_deref())?;
     let result = queries::get_assets(
         &pool,
         &library_id,
-        folder_path.as_deref(),
         file_type.as_deref(),
+        &filter,
         page,
         page_size,
         &sort_by,
         &sort_order,
+        cursor.as_ref(),
     )
     .await?;
     Ok(result)
@@ -181,7 +452,82 @@ pub async fn get_asset_detail(
 ) -> Result<AssetDetail, AppError> {
     let asset = queries::get_asset(&pool, &id).await?;
     let tags = queries::get_asset_tags(&pool, &id).await?;
-    Ok(AssetDetail { asset, tags })
+    let format_metadata = queries::get_format_metadata(&pool, &id)
+        .await?
+        .map(|(format, data)| -> Result<FormatMetadata, AppError> {
+            Ok(FormatMetadata {
+                format,
+                data: serde_json::from_str(&data)?,
+            })
+        })
+        .transpose()?;
+    let links = queries::get_asset_links(&pool, &id).await?;
+    let image_metadata = queries::get_image_metadata(&pool, &id).await?;
+    let text_content = queries::get_text_content_info(&pool, &id).await?;
+    let versions = queries::get_asset_versions(&pool, &id).await?;
+    let fields = queries::get_asset_field_values(&pool, &id).await?;
+    let collections = queries::get_asset_collections(&pool, &id).await?;
+    let comments = queries::get_asset_comments(&pool, &id).await?;
+    let license = queries::get_asset_license(&pool, &id).await?;
+    let palette = queries::get_asset_palette(&pool, &id).await?;
+    let usages = queries::get_asset_usages(&pool, &id).await?;
+    let derived_from = asset_lineage(&pool, &id).await?;
+    let derivatives = queries::get_asset_derivatives(&pool, &id).await?;
+    Ok(AssetDetail {
+        asset,
+        tags,
+        format_metadata,
+        links,
+        image_metadata,
+        text_content,
+        versions,
+        fields,
+        collections,
+        comments,
+        license,
+        palette,
+        usages,
+        derived_from,
+        derivatives,
+    })
+}
+
+/// Extract technical metadata for image assets imported before it was recorded.
+/// With `force`, metadata is re-extracted for every image. Returns the number of assets processed.
+#[tauri::command]
+pub async fn refresh_image_metadata(
+    library_id: String,
+    force: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<u32, AppError> {
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+
+    let assets = queries::get_image_assets_for_metadata(&pool, &library_id, force).await?;
+    for asset in &assets {
+        record_image_metadata(&pool, &library_root.join(&asset.relative_path), &asset.id).await?;
+    }
+
+    Ok(assets.len() as u32)
+}
+
+/// Extract color palettes for image assets imported before they were recorded.
+/// With `force`, every image is analyzed again. Returns the number of assets processed.
+#[tauri::command]
+pub async fn refresh_color_palettes(
+    library_id: String,
+    force: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<u32, AppError> {
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+
+    let assets = queries::get_image_assets_for_palette(&pool, &library_id, force).await?;
+    for asset in &assets {
+        record_palette(&pool, &library_root.join(&asset.relative_path), &asset.id).await?;
+    }
+
+    Ok(assets.len() as u32)
 }
 
 #[tauri::command]
@@ -204,114 +550,222 @@ pub async fn update_description(
     Ok(())
 }
 
+/// Set the same description on several assets at once
 #[tauri::command]
-pub async fn delete_assets(
-    ids: Vec<String>,
+pub async fn bulk_update_description(
+    asset_ids: Vec<String>,
+    description: String,
     pool: State<'_, SqlitePool>,
 ) -> Result<(), AppError> {
-    // Get asset details before deleting from database
-    for id in &ids {
-        let asset = queries::get_asset(&pool, id).await?;
-        let library = queries::get_library(&pool, &asset.library_id).await?;
-        let library_root = std::path::Path::new(&library.root_path);
+    load_assets(&pool, &asset_ids).await?;
+    queries::set_assets_description(&pool, &asset_ids, &description).await?;
+    Ok(())
+}
 
-        // Delete the actual file
-        let file_path = library_root.join(&asset.relative_path);
-        if file_path.exists() {
-            tracing::info!("Deleting file: {:?}", file_path);
-            std::fs::remove_file(&file_path)?;
-        }
+/// Color labels that can be set on assets
+const COLOR_LABELS: &[&str] = &["red", "orange", "yellow", "green", "blue", "purple", "gray"];
 
-        // Delete the thumbnail if it exists
-        if let Some(thumb_rel) = &asset.thumbnail_path {
-            let thumb_path = library_root.join(thumb_rel);
-            if thumb_path.exists() {
-                tracing::info!("Deleting thumbnail: {:?}", thumb_path);
-                std::fs::remove_file(&thumb_path)?;
-            }
-        }
+/// Set the 0-5 star rating of several assets (0 clears the rating)
+#[tauri::command]
+pub async fn set_asset_rating(
+    asset_ids: Vec<String>,
+    rating: i64,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    if !(0..=5).contains(&rating) {
+        return Err(AppError::InvalidInput("Rating must be between 0 and 5".to_string()));
     }
+    load_assets(&pool, &asset_ids).await?;
+    queries::set_assets_rating(&pool, &asset_ids, rating).await?;
+    Ok(())
+}
 
-    // Delete from database
-    queries::delete_assets(&pool, &ids).await?;
+/// Set (or with `None`, clear) the color label of several assets
+#[tauri::command]
+pub async fn set_asset_color_label(
+    asset_ids: Vec<String>,
+    color_label: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    if let Some(label) = &color_label {
+        if !COLOR_LABELS.contains(&label.as_str()) {
+            return Err(AppError::InvalidInput(format!("Unknown color label: {}", label)));
+        }
+    }
+    load_assets(&pool, &asset_ids).await?;
+    queries::set_assets_color_label(&pool, &asset_ids, color_label.as_deref()).await?;
     Ok(())
 }
 
 #[tauri::command]
-pub async fn move_assets(
-    ids: Vec<String>,
-    target_folder: String,
+pub async fn set_asset_favorite(
+    asset_ids: Vec<String>,
+    favorite: bool,
     pool: State<'_, SqlitePool>,
 ) -> Result<(), AppError> {
-    queries::move_assets(&pool, &ids, &target_folder).await?;
+    load_assets(&pool, &asset_ids).await?;
+    queries::set_assets_favorite(&pool, &asset_ids, favorite).await?;
     Ok(())
 }
 
+/// Load the assets with the given IDs, failing if any of them is unknown
+pub(crate) async fn load_assets(pool: &SqlitePool, ids: &[String]) -> Result<Vec<Asset>, AppError> {
+    let assets = queries::get_assets_by_ids(pool, ids).await?;
+    let found: std::collections::HashSet<&str> = assets.iter().map(|a| a.id.as_str()).collect();
+    if let Some(missing) = ids.iter().find(|id| !found.contains(id.as_str())) {
+        return Err(AppError::NotFound(format!("Asset {}", missing)));
+    }
+    Ok(assets)
+}
+
+/// The library shared by all assets
+pub(crate) fn single_library(assets: &[Asset]) -> Result<Option<&str>, AppError> {
+    let Some(first) = assets.first() else {
+        return Ok(None);
+    };
+    if assets.iter().any(|a| a.library_id != first.library_id) {
+        return Err(AppError::InvalidInput(
+            "Assets from different libraries cannot be changed together".to_string(),
+        ));
+    }
+    Ok(Some(&first.library_id))
+}
+
+/// Delete assets and their files. The database rows go first, in one statement, so a
+/// failure leaves every asset in place; files that cannot be removed afterwards are
+/// only logged.
 #[tauri::command]
-pub async fn get_folders(
-    library_id: String,
+pub async fn delete_assets(
+    ids: Vec<String>,
     pool: State<'_, SqlitePool>,
-) -> Result<Vec<FolderInfo>, AppError> {
-    let library = queries::get_library(&pool, &library_id).await?;
-    let library_root = std::path::PathBuf::from(&library.root_path);
+) -> Result<(), AppError> {
+    delete_asset_ids(&pool, &ids).await
+}
 
-    // Get folders from database (folders with assets)
-    let mut db_folders = queries::get_folders(&pool, &library_id).await?;
+pub(crate) async fn delete_asset_ids(pool: &SqlitePool, ids: &[String]) -> Result<(), AppError> {
+    let assets = load_assets(pool, ids).await?;
+    queries::delete_assets(pool, ids).await?;
 
-    // Recursively scan filesystem for all folders
-    fn scan_folders(
-        base_path: &std::path::Path,
-        current_path: &std::path::Path,
-        folders: &mut Vec<FolderInfo>,
-    ) {
-        if let Ok(entries) = std::fs::read_dir(current_path) {
-            for entry in entries.flatten() {
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_dir() {
-                        if let Some(name) = entry.file_name().to_str() {
-                            // Skip hidden folders (starting with .)
-                            if name.starts_with('.') {
-                                continue;
-                            }
-
-                            let full_path = entry.path();
-                            // Get relative path from library root
-                            let relative_path = full_path
-                                .strip_prefix(base_path)
-                                .ok()
-                                .and_then(|p| p.to_str())
-                                .unwrap_or(name)
-                                .to_string();
-
-                            folders.push(FolderInfo {
-                                path: relative_path.clone(),
-                                name: name.to_string(),
-                                asset_count: 0,
-                            });
-
-                            // Recursively scan subdirectories
-                            scan_folders(base_path, &full_path, folders);
-                        }
-                    }
-                }
-            }
+    let mut library_roots = std::collections::HashMap::new();
+    for asset in &assets {
+        if !library_roots.contains_key(&asset.library_id) {
+            let library = queries::get_library(pool, &asset.library_id).await?;
+            library_roots.insert(asset.library_id.clone(), std::path::PathBuf::from(library.root_path));
+        }
+        let library_root = &library_roots[&asset.library_id];
+        if let Err(e) = delete_asset_files(library_root, asset) {
+            tracing::warn!("Failed to delete files of asset {}: {}", asset.id, e);
+        }
+    }
+    Ok(())
+}
+
+/// Remove an asset's file, thumbnail and previous versions from disk
+fn delete_asset_files(library_root: &std::path::Path, asset: &Asset) -> Result<(), AppError> {
+    let file_path = library_root.join(&asset.relative_path);
+    if file_path.exists() {
+        tracing::info!("Deleting file: {:?}", file_path);
+        std::fs::remove_file(&file_path)?;
+    }
+
+    if let Some(thumb_rel) = &asset.thumbnail_path {
+        let thumb_path = library_root.join(thumb_rel);
+        if thumb_path.exists() {
+            tracing::info!("Deleting thumbnail: {:?}", thumb_path);
+            std::fs::remove_file(&thumb_path)?;
         }
     }
 
-    let mut fs_folders = Vec::new();
-    scan_folders(&library_root, &library_root, &mut fs_folders);
+    let versions_dir = file_ops::versions_dir(library_root, &asset.id);
+    if versions_dir.exists() {
+        std::fs::remove_dir_all(&versions_dir)?;
+    }
+    Ok(())
+}
+
+/// Move asset files into `target_dir`, recording each move in `moved` so it can be undone.
+/// Returns the new relative path for every asset.
+fn move_asset_files(
+    assets: &[Asset],
+    library_root: &std::path::Path,
+    target_dir: &std::path::Path,
+    moved: &mut Vec<(std::path::PathBuf, std::path::PathBuf)>,
+) -> Result<Vec<(String, String)>, AppError> {
+    let mut updates = Vec::new();
+    for asset in assets {
+        let source = library_root.join(&asset.relative_path);
+        let file_name = source.file_name().map(|n| n.to_string_lossy().to_string());
+        let relative_path = match file_name {
+            Some(file_name) if source.exists() && source.parent() != Some(target_dir) => {
+                let dest = file_ops::unique_file_path(target_dir, &file_name);
+                std::fs::rename(&source, &dest)?;
+                let relative_path = file_ops::relative_to_library(&dest, library_root);
+                moved.push((source, dest));
+                relative_path
+            }
+            _ => {
+                if !source.exists() {
+                    tracing::warn!("File for asset {} is missing: {:?}", asset.id, source);
+                }
+                asset.relative_path.clone()
+            }
+        };
+        updates.push((asset.id.clone(), relative_path));
+    }
+    Ok(updates)
+}
 
-    // Merge with db_folders, avoiding duplicates
-    for fs_folder in fs_folders {
-        if !db_folders.iter().any(|f| f.path == fs_folder.path) {
-            db_folders.push(fs_folder);
+/// Move files back to where they were, newest move first
+pub(crate) fn undo_file_moves(moved: &[(std::path::PathBuf, std::path::PathBuf)]) {
+    for (source, dest) in moved.iter().rev() {
+        if let Err(e) = std::fs::rename(dest, source) {
+            tracing::error!("Failed to move {:?} back to {:?}: {}", dest, source, e);
         }
     }
+}
 
-    // Sort by path
-    db_folders.sort_by(|a, b| a.path.cmp(&b.path));
+/// Move assets to another folder, relocating their files on disk to match.
+/// If any file cannot be moved, all files are moved back and nothing changes.
+#[tauri::command]
+pub async fn move_assets(
+    ids: Vec<String>,
+    target_folder: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    move_asset_ids(&pool, &ids, &target_folder).await
+}
+
+pub(crate) async fn move_asset_ids(
+    pool: &SqlitePool,
+    ids: &[String],
+    target_folder: &str,
+) -> Result<(), AppError> {
+    let target_folder = file_ops::normalize_folder_path(target_folder)
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", target_folder)))?;
+    let assets = load_assets(pool, ids).await?;
+    let Some(library_id) = single_library(&assets)?.map(str::to_string) else {
+        return Ok(());
+    };
+    let library = queries::get_library(pool, &library_id).await?;
+    let library_root = std::path::PathBuf::from(&library.root_path);
+    let target_dir = library_root.join(target_folder.trim_start_matches('/'));
+    std::fs::create_dir_all(&target_dir)?;
+
+    let mut moved = Vec::new();
+    let updates = match move_asset_files(&assets, &library_root, &target_dir, &mut moved) {
+        Ok(updates) => updates,
+        Err(e) => {
+            undo_file_moves(&moved);
+            return Err(e);
+        }
+    };
 
-    Ok(db_folders)
+    // Moves the rows and applies the folder's default tags together
+    if let Err(e) = queries::move_assets(pool, &library_id, &updates, &target_folder).await {
+        undo_file_moves(&moved);
+        return Err(e.into());
+    }
+    Ok(())
 }
 
 /// Get the absolute filesystem path for an asset file (for frontend to display)
@@ -342,6 +796,26 @@ pub async fn get_thumbnail_path(
     }
 }
 
+/// Read a thumbnail image as a base64 encoded data URL
+pub(crate) fn thumbnail_data_url(full_path: &std::path::Path) -> Result<String, AppError> {
+    // Read the file
+    let data = std::fs::read(full_path)?;
+
+    // Encode as base64
+    let base64_data = base64::engine::general_purpose::STANDARD.encode(&data);
+
+    // Determine MIME type based on file extension
+    let mime_type = match full_path.extension().and_then(|s| s.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "image/png", // default
+    };
+
+    Ok(format!("data:{};base64,{}", mime_type, base64_data))
+}
+
 /// Get thumbnail as base64 encoded data URL
 #[tauri::command]
 pub async fn get_thumbnail_data(
@@ -353,97 +827,8 @@ pub async fn get_thumbnail_data(
         let library = queries::get_library(&pool, &asset.library_id).await?;
         let full_path = std::path::Path::new(&library.root_path).join(thumb_rel);
 
-        // Read the file
-        let data = std::fs::read(&full_path)?;
-
-        // Encode as base64
-        let base64_data = base64::engine::general_purpose::STANDARD.encode(&data);
-
-        // Determine MIME type based on file extension
-        let mime_type = match full_path.extension().and_then(|s| s.to_str()) {
-            Some("png") => "image/png",
-            Some("jpg") | Some("jpeg") => "image/jpeg",
-            Some("gif") => "image/gif",
-            Some("webp") => "image/webp",
-            _ => "image/png", // default
-        };
-
-        Ok(Some(format!("data:{};base64,{}", mime_type, base64_data)))
+        Ok(Some(thumbnail_data_url(&full_path)?))
     } else {
         Ok(None)
     }
 }
-
-/// Create a new folder
-#[tauri::command]
-pub async fn create_folder(
-    library_id: String,
-    folder_name: String,
-    parent_path: String,
-    pool: State<'_, SqlitePool>,
-) -> Result<(), AppError> {
-    // Validate folder name
-    if folder_name.is_empty() || folder_name.contains('/') || folder_name.contains('\\') {
-        return Err(AppError::InvalidInput("Invalid folder name".to_string()));
-    }
-
-    // Get library to access root path
-    let library = queries::get_library(&pool, &library_id).await?;
-    let library_root = std::path::Path::new(&library.root_path);
-
-    // Build the full folder path
-    let folder_path = if parent_path == "/" {
-        library_root.join(&folder_name)
-    } else {
-        library_root.join(&parent_path).join(&folder_name)
-    };
-
-    // Check if folder already exists
-    if folder_path.exists() {
-        return Err(AppError::InvalidInput("Folder already exists".to_string()));
-    }
-
-    // Create the folder
-    std::fs::create_dir_all(&folder_path)?;
-
-    Ok(())
-}
-
-/// Rename a folder
-#[tauri::command]
-pub async fn rename_folder(
-    library_id: String,
-    old_path: String,
-    new_name: String,
-    pool: State<'_, SqlitePool>,
-) -> Result<(), AppError> {
-    // Validate new name
-    if new_name.is_empty() || new_name.contains('/') || new_name.contains('\\') {
-        return Err(AppError::InvalidInput("Invalid folder name".to_string()));
-    }
-
-    // Get library to access root path
-    let library = queries::get_library(&pool, &library_id).await?;
-    let library_root = std::path::Path::new(&library.root_path);
-
-    let old_folder_path = library_root.join(&old_path);
-    let new_folder_path = library_root.join(&new_name);
-
-    // Check if old folder exists
-    if !old_folder_path.exists() {
-        return Err(AppError::InvalidInput("Folder does not exist".to_string()));
-    }
-
-    // Check if new folder already exists
-    if new_folder_path.exists() {
-        return Err(AppError::InvalidInput("A folder with this name already exists".to_string()));
-    }
-
-    // Rename the folder
-    std::fs::rename(&old_folder_path, &new_folder_path)?;
-
-    // Update all assets in the database that reference this folder
-    queries::update_folder_path(&pool, &library_id, &old_path, &new_name).await?;
-
-    Ok(())
-}
@@ -0,0 +1,117 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::ai::provider::AiProviderManager;
+use crate::commands::ai::tag_asset_with_ai;
+use crate::commands::asset::{delete_asset_ids, move_asset_ids};
+use crate::commands::collection::normalize_query;
+use crate::commands::export::export_selection;
+use crate::commands::processing::compress_asset;
+use crate::commands::tag::validate_bulk_tagging;
+use crate::db::{
+    models::{BulkFailure, BulkOperation, BulkQueryResult, ExportSelection, SmartCollectionQuery},
+    queries,
+};
+use crate::error::AppError;
+
+/// Apply an operation to every asset of a library matching a query (the same query
+/// smart collections use), resolving it here rather than sending each asset ID. With
+/// `dry_run` the assets the operation would apply to are only counted. Tagging, moving
+/// and deleting change all matches or none; AI tagging and compression only apply to
+/// images and report failures per asset.
+#[tauri::command]
+pub async fn bulk_query_operation(
+    library_id: String,
+    query: SmartCollectionQuery,
+    operation: BulkOperation,
+    dry_run: bool,
+    pool: State<'_, SqlitePool>,
+    ai_manager: State<'_, AiProviderManager>,
+) -> Result<BulkQueryResult, AppError> {
+    let mut query = normalize_query(&pool, query).await?;
+    let images_only = matches!(
+        operation,
+        BulkOperation::AiTag { .. } | BulkOperation::Compress { .. }
+    );
+    let asset_ids = if images_only && query.file_type.as_deref().is_some_and(|t| t != "image") {
+        Vec::new()
+    } else {
+        if images_only {
+            query.file_type = Some("image".to_string());
+        }
+        queries::get_query_asset_ids(&pool, &library_id, &query).await?
+    };
+
+    let mut result = BulkQueryResult {
+        matched: asset_ids.len(),
+        applied: !dry_run,
+        failures: Vec::new(),
+        export: None,
+    };
+    if dry_run || asset_ids.is_empty() {
+        return Ok(result);
+    }
+    tracing::info!("Applying bulk operation to {} assets of library {}", asset_ids.len(), library_id);
+
+    match operation {
+        BulkOperation::Tag { tag_ids } => {
+            validate_bulk_tagging(&pool, &asset_ids, &tag_ids).await?;
+            queries::assign_tags_to_assets(&pool, &asset_ids, &tag_ids).await?;
+        }
+        BulkOperation::Untag { tag_ids } => {
+            validate_bulk_tagging(&pool, &asset_ids, &tag_ids).await?;
+            queries::remove_tags_from_assets(&pool, &asset_ids, &tag_ids).await?;
+        }
+        BulkOperation::Move { target_folder } => {
+            move_asset_ids(&pool, &asset_ids, &target_folder).await?;
+        }
+        BulkOperation::Delete => delete_asset_ids(&pool, &asset_ids).await?,
+        BulkOperation::AiTag { apply_name } => {
+            for asset_id in &asset_ids {
+                if let Err(e) = tag_asset_with_ai(&pool, &ai_manager, asset_id, apply_name).await {
+                    tracing::warn!("AI tagging failed for asset {}: {}", asset_id, e);
+                    result.failures.push(BulkFailure {
+                        asset_id: asset_id.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+        BulkOperation::Compress {
+            max_width,
+            max_height,
+            quality,
+            output_format,
+            suffix,
+        } => {
+            for asset_id in &asset_ids {
+                let compressed = compress_asset(
+                    &pool,
+                    asset_id,
+                    max_width,
+                    max_height,
+                    quality,
+                    output_format.clone(),
+                    suffix.clone(),
+                )
+                .await;
+                if let Err(e) = compressed {
+                    tracing::warn!("Compression failed for asset {}: {}", asset_id, e);
+                    result.failures.push(BulkFailure {
+                        asset_id: asset_id.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+        BulkOperation::Export { options } => {
+            // The manifest records the query rather than every matched ID
+            let selection = ExportSelection::Query {
+                library_id: library_id.clone(),
+                query,
+            };
+            result.export = Some(export_selection(&pool, &selection, &options).await?);
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,224 @@
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use tauri::State;
+
+use crate::db::{
+    models::{Collection, CollectionInfo, PaginatedAssets, SmartCollectionQuery},
+    queries,
+};
+use crate::commands::asset::{normalize_filter, parse_cursor};
+use crate::error::AppError;
+use crate::storage::file_ops;
+
+fn validate_collection_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Collection name cannot be empty".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Fail if another collection of the library already uses `name`
+async fn ensure_unique_name(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    collection_id: Option<&str>,
+) -> Result<(), AppError> {
+    let existing = queries::get_collection_by_name(pool, library_id, name).await?;
+    if existing.is_some_and(|c| Some(c.id.as_str()) != collection_id) {
+        return Err(AppError::InvalidInput(format!(
+            "A collection named '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Normalize the folder path and tag list of a smart collection query and check its filter
+pub(crate) async fn normalize_query(
+    pool: &SqlitePool,
+    mut query: SmartCollectionQuery,
+) -> Result<SmartCollectionQuery, AppError> {
+    if let Some(path) = &query.folder_path {
+        query.folder_path = Some(
+            file_ops::normalize_folder_path(path)
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", path)))?,
+        );
+    }
+    let mut seen = HashSet::new();
+    query.tag_ids.retain(|id| seen.insert(id.clone()));
+    query.filter = normalize_filter(pool, query.filter).await?;
+    Ok(query)
+}
+
+async fn get_manual_collection(pool: &SqlitePool, id: &str) -> Result<Collection, AppError> {
+    let collection = queries::get_collection(pool, id).await?;
+    if collection.kind != "manual" {
+        return Err(AppError::InvalidInput(
+            "Assets can only be added to or arranged in manual collections".to_string(),
+        ));
+    }
+    Ok(collection)
+}
+
+async fn collection_info(pool: &SqlitePool, collection: Collection) -> Result<CollectionInfo, AppError> {
+    let asset_count = queries::count_collection_assets(pool, &collection).await?;
+    Ok(CollectionInfo {
+        collection,
+        asset_count,
+    })
+}
+
+#[tauri::command]
+pub async fn get_collections(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<CollectionInfo>, AppError> {
+    let collections = queries::get_collections(&pool, &library_id).await?;
+    Ok(collections)
+}
+
+/// Create a collection. With a `query` it is a smart collection, otherwise a manual one.
+#[tauri::command]
+pub async fn create_collection(
+    library_id: String,
+    name: String,
+    description: Option<String>,
+    query: Option<SmartCollectionQuery>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CollectionInfo, AppError> {
+    let name = validate_collection_name(&name)?;
+    ensure_unique_name(&pool, &library_id, &name, None).await?;
+    let query = match query {
+        Some(query) => Some(normalize_query(&pool, query).await?),
+        None => None,
+    };
+
+    let collection = queries::create_collection(
+        &pool,
+        &library_id,
+        &name,
+        &description.unwrap_or_default(),
+        query.as_ref(),
+    )
+    .await?;
+    collection_info(&pool, collection).await
+}
+
+/// Update a collection's name and description, and the query of a smart collection
+#[tauri::command]
+pub async fn update_collection(
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    query: Option<SmartCollectionQuery>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CollectionInfo, AppError> {
+    let collection = queries::get_collection(&pool, &id).await?;
+    let name = match name {
+        Some(name) => validate_collection_name(&name)?,
+        None => collection.name.clone(),
+    };
+    ensure_unique_name(&pool, &collection.library_id, &name, Some(&id)).await?;
+    if query.is_some() && collection.kind != "smart" {
+        return Err(AppError::InvalidInput(
+            "Only smart collections have a query".to_string(),
+        ));
+    }
+    let query = match query {
+        Some(query) => Some(normalize_query(&pool, query).await?),
+        None => collection.query.map(|q| q.0),
+    };
+
+    let updated = queries::update_collection(
+        &pool,
+        &id,
+        &name,
+        &description.unwrap_or(collection.description),
+        query.as_ref(),
+    )
+    .await?;
+    collection_info(&pool, updated).await
+}
+
+/// Delete a collection. Its assets are not affected.
+#[tauri::command]
+pub async fn delete_collection(
+    id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    queries::delete_collection(&pool, &id).await?;
+    Ok(())
+}
+
+/// Append assets to the end of a manual collection
+#[tauri::command]
+pub async fn add_to_collection(
+    collection_id: String,
+    asset_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CollectionInfo, AppError> {
+    let collection = get_manual_collection(&pool, &collection_id).await?;
+    for asset_id in &asset_ids {
+        let asset = queries::get_asset(&pool, asset_id).await?;
+        if asset.library_id != collection.library_id {
+            return Err(AppError::InvalidInput(
+                "Asset belongs to a different library".to_string(),
+            ));
+        }
+    }
+    queries::add_collection_assets(&pool, &collection_id, &asset_ids).await?;
+    collection_info(&pool, collection).await
+}
+
+#[tauri::command]
+pub async fn remove_from_collection(
+    collection_id: String,
+    asset_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CollectionInfo, AppError> {
+    let collection = get_manual_collection(&pool, &collection_id).await?;
+    queries::remove_collection_assets(&pool, &collection_id, &asset_ids).await?;
+    collection_info(&pool, collection).await
+}
+
+/// Move `asset_ids` to the start of a manual collection, in the given order.
+/// Passing all members sets the complete order.
+#[tauri::command]
+pub async fn reorder_collection(
+    collection_id: String,
+    asset_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    get_manual_collection(&pool, &collection_id).await?;
+    queries::reorder_collection_assets(&pool, &collection_id, &asset_ids).await?;
+    Ok(())
+}
+
+/// List a collection's assets page by page. Smart collections are evaluated now;
+/// manual collections can be sorted by "position" (their own order).
+#[tauri::command]
+pub async fn get_collection_assets(
+    collection_id: String,
+    page: u32,
+    page_size: u32,
+    sort_by: String,
+    sort_order: String,
+    cursor: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<PaginatedAssets, AppError> {
+    let collection = queries::get_collection(&pool, &collection_id).await?;
+    let cursor = parse_cursor(cursor.as_deref())?;
+    let result = queries::get_collection_assets(
+        &pool,
+        &collection,
+        page,
+        page_size,
+        &sort_by,
+        &sort_order,
+        cursor.as_ref(),
+    )
+    .await?;
+    Ok(result)
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use sqlx::SqlitePool;
+use tauri::State;
+use uuid::Uuid;
+
+use crate::db::{
+    models::{Asset, AssetComment, CommentAnchor, CommentSearchResult},
+    queries,
+};
+use crate::error::AppError;
+
+fn validate_body(body: &str) -> Result<String, AppError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(AppError::InvalidInput("Comment cannot be empty".to_string()));
+    }
+    Ok(body.to_string())
+}
+
+/// Check that an anchor fits the asset: a point or rectangle inside an image, or a time
+/// within audio and video
+fn validate_anchor(anchor: &CommentAnchor, asset: &Asset) -> Result<(), AppError> {
+    let invalid = |message: &str| Err(AppError::InvalidInput(format!("Invalid anchor: {}", message)));
+
+    let has_region = anchor.x.is_some() || anchor.y.is_some();
+    if anchor.x.is_some() != anchor.y.is_some() {
+        return invalid("x and y must be set together");
+    }
+    if anchor.width.is_some() != anchor.height.is_some() {
+        return invalid("width and height must be set together");
+    }
+    if anchor.width.is_some() && !has_region {
+        return invalid("a rectangle needs x and y");
+    }
+    if has_region && anchor.time_ms.is_some() {
+        return invalid("an anchor is either a region or a time");
+    }
+
+    if let (Some(x), Some(y)) = (anchor.x, anchor.y) {
+        if !matches!(asset.file_type.as_str(), "image" | "video") {
+            return invalid("regions can only be set on images and videos");
+        }
+        let (width, height) = (anchor.width.unwrap_or(0.0), anchor.height.unwrap_or(0.0));
+        if [x, y, width, height].iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return invalid("coordinates must be non-negative numbers");
+        }
+        if anchor.width.is_some() && (width == 0.0 || height == 0.0) {
+            return invalid("a rectangle cannot be empty");
+        }
+        if let (Some(w), Some(h)) = (asset.width, asset.height) {
+            if x + width > f64::from(w) || y + height > f64::from(h) {
+                return invalid("the region is outside the image");
+            }
+        }
+    }
+
+    if let Some(time_ms) = anchor.time_ms {
+        if !matches!(asset.file_type.as_str(), "audio" | "video") {
+            return invalid("times can only be set on audio and video");
+        }
+        if time_ms < 0 || asset.duration_ms.is_some_and(|d| time_ms > d) {
+            return invalid("the time is outside the media");
+        }
+    }
+    Ok(())
+}
+
+/// Comment on an asset, optionally anchored to a region or time. With `parent_id` the
+/// comment is a reply; replies to replies are added to the same thread.
+#[tauri::command]
+pub async fn add_comment(
+    asset_id: String,
+    body: String,
+    parent_id: Option<String>,
+    anchor: Option<CommentAnchor>,
+    author: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<AssetComment, AppError> {
+    let body = validate_body(&body)?;
+    let asset = queries::get_asset(&pool, &asset_id).await?;
+
+    let parent_id = match parent_id {
+        Some(parent_id) => {
+            let parent = queries::get_comment(&pool, &parent_id).await?;
+            if parent.asset_id != asset_id {
+                return Err(AppError::InvalidInput(
+                    "A reply must be on the same asset as its comment".to_string(),
+                ));
+            }
+            Some(parent.parent_id.unwrap_or(parent.id))
+        }
+        None => None,
+    };
+    let anchor = anchor.unwrap_or_default();
+    validate_anchor(&anchor, &asset)?;
+
+    let comment = AssetComment {
+        id: Uuid::new_v4().to_string(),
+        asset_id,
+        parent_id,
+        author: author.unwrap_or_default().trim().to_string(),
+        body,
+        anchor_x: anchor.x,
+        anchor_y: anchor.y,
+        anchor_width: anchor.width,
+        anchor_height: anchor.height,
+        anchor_time_ms: anchor.time_ms,
+        resolved: false,
+        resolved_at: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    let comment = queries::insert_comment(&pool, &comment).await?;
+    Ok(comment)
+}
+
+#[tauri::command]
+pub async fn get_asset_comments(
+    asset_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<AssetComment>, AppError> {
+    let comments = queries::get_asset_comments(&pool, &asset_id).await?;
+    Ok(comments)
+}
+
+#[tauri::command]
+pub async fn update_comment(
+    id: String,
+    body: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<AssetComment, AppError> {
+    let body = validate_body(&body)?;
+    let comment = queries::update_comment_body(&pool, &id, &body).await?;
+    Ok(comment)
+}
+
+/// Resolve or reopen a comment thread
+#[tauri::command]
+pub async fn set_comment_resolved(
+    id: String,
+    resolved: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<AssetComment, AppError> {
+    let comment = queries::get_comment(&pool, &id).await?;
+    if comment.parent_id.is_some() {
+        return Err(AppError::InvalidInput(
+            "Only the first comment of a thread can be resolved".to_string(),
+        ));
+    }
+    let comment = queries::set_comment_resolved(&pool, &id, resolved).await?;
+    Ok(comment)
+}
+
+/// Delete a comment; deleting the first comment of a thread deletes its replies
+#[tauri::command]
+pub async fn delete_comment(id: String, pool: State<'_, SqlitePool>) -> Result<(), AppError> {
+    queries::delete_comment(&pool, &id).await?;
+    Ok(())
+}
+
+/// Find comments in a library by text or author, grouped by asset with the most recently
+/// commented asset first. Comments in resolved threads are only included on request.
+#[tauri::command]
+pub async fn search_comments(
+    library_id: String,
+    query: String,
+    include_resolved: Option<bool>,
+    limit: Option<u32>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<CommentSearchResult>, AppError> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let comments = queries::search_comments(
+        &pool,
+        &library_id,
+        query.trim(),
+        include_resolved.unwrap_or(false),
+        limit.unwrap_or(200),
+    )
+    .await?;
+
+    let mut results: Vec<CommentSearchResult> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for comment in comments {
+        match positions.get(&comment.asset_id) {
+            Some(&i) => results[i].comments.push(comment),
+            None => {
+                let asset = queries::get_asset(&pool, &comment.asset_id).await?;
+                positions.insert(comment.asset_id.clone(), results.len());
+                results.push(CommentSearchResult {
+                    asset,
+                    comments: vec![comment],
+                });
+            }
+        }
+    }
+    Ok(results)
+}
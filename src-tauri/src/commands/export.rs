@@ -0,0 +1,372 @@
+use image::{DynamicImage, ImageFormat};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::db::{
+    models::{Asset, AssetCursor, AssetFilter, ExportOptions, ExportResult, ExportSelection, ExportedFile},
+    queries,
+};
+use crate::error::AppError;
+use crate::processing::{aseprite, compress};
+use crate::storage::file_ops;
+
+/// Assets are loaded page by page for folder, collection and search exports
+const EXPORT_PAGE_SIZE: u32 = 500;
+
+const CONFLICT_POLICIES: &[&str] = &["skip", "overwrite", "rename"];
+
+/// Image formats that can be written when re-encoding
+const ENCODABLE_EXTS: &[&str] = &["png", "jpg", "jpeg", "webp", "bmp", "gif", "tif", "tiff"];
+
+const MANIFEST_NAME: &str = "export_manifest.json";
+
+fn validate_options(options: &ExportOptions) -> Result<(), AppError> {
+    if options.destination.trim().is_empty() {
+        return Err(AppError::InvalidInput("Export destination is required".to_string()));
+    }
+    if !CONFLICT_POLICIES.contains(&options.on_conflict.as_str()) {
+        return Err(AppError::InvalidInput(format!(
+            "Unknown conflict policy: {}",
+            options.on_conflict
+        )));
+    }
+    if let Some(format) = &options.image_format {
+        if !matches!(format.as_str(), "png" | "jpeg" | "jpg" | "webp") {
+            return Err(AppError::InvalidInput(format!("Unsupported image format: {}", format)));
+        }
+    }
+    if !(1..=100).contains(&options.quality) {
+        return Err(AppError::InvalidInput("Quality must be between 1 and 100".to_string()));
+    }
+    if options.max_width == Some(0) || options.max_height == Some(0) {
+        return Err(AppError::InvalidInput("Maximum size must be positive".to_string()));
+    }
+    Ok(())
+}
+
+/// Load the selected assets. Also returns the folder that exported paths are relative to.
+async fn selected_assets(
+    pool: &SqlitePool,
+    selection: &ExportSelection,
+) -> Result<(Vec<Asset>, String), AppError> {
+    let mut assets = Vec::new();
+    let base_folder = match selection {
+        ExportSelection::Assets { asset_ids } => {
+            let mut seen = HashSet::new();
+            for id in asset_ids.iter().filter(|id| seen.insert(id.as_str())) {
+                assets.push(queries::get_asset(pool, id).await?);
+            }
+            "/".to_string()
+        }
+        ExportSelection::Folder {
+            library_id,
+            folder_path,
+            include_subfolders,
+        } => {
+            let path = file_ops::normalize_folder_path(folder_path)
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", folder_path)))?;
+            let filter = AssetFilter {
+                folder_path: Some(path.clone()),
+                include_subfolders: *include_subfolders,
+                ..Default::default()
+            };
+            let mut cursor = None;
+            loop {
+                let result = queries::get_assets(
+                    pool,
+                    library_id,
+                    None,
+                    &filter,
+                    1,
+                    EXPORT_PAGE_SIZE,
+                    "name",
+                    "asc",
+                    cursor.as_ref(),
+                )
+                .await?;
+                assets.extend(result.assets);
+                match result.next_cursor.as_deref().and_then(AssetCursor::decode) {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            path
+        }
+        ExportSelection::Collection { collection_id } => {
+            let collection = queries::get_collection(pool, collection_id).await?;
+            let mut cursor = None;
+            loop {
+                let result = queries::get_collection_assets(
+                    pool,
+                    &collection,
+                    1,
+                    EXPORT_PAGE_SIZE,
+                    "position",
+                    "asc",
+                    cursor.as_ref(),
+                )
+                .await?;
+                assets.extend(result.assets);
+                match result.next_cursor.as_deref().and_then(AssetCursor::decode) {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            "/".to_string()
+        }
+        ExportSelection::Search { library_id, query } => {
+            let mut cursor = None;
+            loop {
+                let result = queries::search_keyword(
+                    pool,
+                    library_id,
+                    query,
+                    None,
+                    false,
+                    None,
+                    &AssetFilter::default(),
+                    1,
+                    EXPORT_PAGE_SIZE,
+                    "date",
+                    "desc",
+                    cursor.as_ref(),
+                )
+                .await?;
+                assets.extend(result.assets);
+                match result.next_cursor.as_deref().and_then(AssetCursor::decode) {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            "/".to_string()
+        }
+        ExportSelection::Query { library_id, query } => {
+            let asset_ids = queries::get_query_asset_ids(pool, library_id, query).await?;
+            assets = queries::get_assets_by_ids(pool, &asset_ids).await?;
+            "/".to_string()
+        }
+    };
+    Ok((assets, base_folder))
+}
+
+/// Extension of the re-encoded image, or `None` if the file is copied as is
+fn reencode_ext(asset: &Asset, options: &ExportOptions) -> Option<String> {
+    if asset.file_type != "image" {
+        return None;
+    }
+    if let Some(format) = &options.image_format {
+        return Some(if format == "jpeg" { "jpg" } else { format }.to_string());
+    }
+    let resize = options.max_width.is_some() || options.max_height.is_some();
+    if !resize && !options.strip_metadata {
+        return None;
+    }
+    let ext = Path::new(&asset.file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if ENCODABLE_EXTS.contains(&ext.as_str()) {
+        Some(ext)
+    } else if resize {
+        // Sources that cannot be written back, such as Aseprite files, are resized to PNG
+        Some("png".to_string())
+    } else {
+        None
+    }
+}
+
+fn encode_image(img: &DynamicImage, ext: &str, quality: u8) -> Result<Vec<u8>, AppError> {
+    let format = ImageFormat::from_extension(ext)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unsupported image format: {}", ext)))?;
+    match format {
+        ImageFormat::Jpeg => compress::compress_to_jpeg_bytes(img, quality),
+        ImageFormat::Png => compress::compress_to_png_bytes(img),
+        _ => {
+            let mut bytes = Vec::new();
+            DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut Cursor::new(&mut bytes), format)?;
+            Ok(bytes)
+        }
+    }
+}
+
+/// File name of the exported asset: its `file_name` with the extension of the output format
+fn export_file_name(asset: &Asset, ext: Option<&str>) -> String {
+    let name = Path::new(&asset.file_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&asset.id);
+    match ext {
+        Some(ext) => match name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => format!("{}.{}", stem, ext),
+            _ => format!("{}.{}", name, ext),
+        },
+        None => name.to_string(),
+    }
+}
+
+/// Where to write an asset, following the conflict policy. Returns `None` to skip it.
+/// Files written earlier in the same export are never overwritten.
+fn export_target(
+    dir: &Path,
+    file_name: &str,
+    on_conflict: &str,
+    written: &HashSet<PathBuf>,
+) -> Option<PathBuf> {
+    let target = dir.join(file_name);
+    if !target.exists() {
+        return Some(target);
+    }
+    match on_conflict {
+        _ if written.contains(&target) => Some(file_ops::unique_file_path(dir, file_name)),
+        "skip" => None,
+        "overwrite" => Some(target),
+        _ => Some(file_ops::unique_file_path(dir, file_name)),
+    }
+}
+
+/// Copy or re-encode one asset to `target`. Returns the size and, for re-encoded images,
+/// the new dimensions.
+fn write_export_file(
+    source: &Path,
+    target: &Path,
+    reencode: Option<&str>,
+    options: &ExportOptions,
+) -> Result<(u64, Option<(u32, u32)>), AppError> {
+    match reencode {
+        Some(ext) => {
+            let img = if options.max_width.is_some() || options.max_height.is_some() {
+                compress::compress_image(source, options.max_width, options.max_height, options.quality)?
+            } else {
+                aseprite::open_image(source)?
+            };
+            let bytes = encode_image(&img, ext, options.quality)?;
+            std::fs::write(target, &bytes)?;
+            Ok((bytes.len() as u64, Some((img.width(), img.height()))))
+        }
+        None => Ok((std::fs::copy(source, target)?, None)),
+    }
+}
+
+/// Copy the selected assets to a directory under their real names (`file_name`) rather
+/// than the IDs they are stored under, optionally recreating their folders. Images can be
+/// converted, shrunk and stripped of metadata on the way. Failures are reported per file.
+#[tauri::command]
+pub async fn export_assets(
+    selection: ExportSelection,
+    options: ExportOptions,
+    pool: State<'_, SqlitePool>,
+) -> Result<ExportResult, AppError> {
+    export_selection(&pool, &selection, &options).await
+}
+
+pub(crate) async fn export_selection(
+    pool: &SqlitePool,
+    selection: &ExportSelection,
+    options: &ExportOptions,
+) -> Result<ExportResult, AppError> {
+    validate_options(options)?;
+    let destination = PathBuf::from(options.destination.trim());
+    std::fs::create_dir_all(&destination)?;
+
+    let (assets, base_folder) = selected_assets(pool, selection).await?;
+    tracing::info!("Exporting {} assets to {:?}", assets.len(), destination);
+
+    let mut library_roots = std::collections::HashMap::new();
+    let mut written = HashSet::new();
+    let mut files = Vec::new();
+    for asset in &assets {
+        if !library_roots.contains_key(&asset.library_id) {
+            let library = queries::get_library(pool, &asset.library_id).await?;
+            library_roots.insert(asset.library_id.clone(), PathBuf::from(library.root_path));
+        }
+        let source = library_roots[&asset.library_id].join(&asset.relative_path);
+        let tags = queries::get_asset_tags(pool, &asset.id).await?;
+
+        let mut file = ExportedFile {
+            asset_id: asset.id.clone(),
+            file_name: asset.file_name.clone(),
+            folder_path: asset.folder_path.clone(),
+            tags: tags.into_iter().map(|t| t.name).collect(),
+            status: "exported".to_string(),
+            path: None,
+            file_size: None,
+            width: None,
+            height: None,
+            converted: false,
+            error: None,
+        };
+
+        let dir = if options.keep_folders {
+            let relative = asset
+                .folder_path
+                .strip_prefix(base_folder.as_str())
+                .unwrap_or(&asset.folder_path);
+            destination.join(relative.trim_start_matches('/'))
+        } else {
+            destination.clone()
+        };
+        let reencode = reencode_ext(asset, options);
+        let file_name = export_file_name(asset, reencode.as_deref());
+
+        let result = std::fs::create_dir_all(&dir).map_err(AppError::from).and_then(|_| {
+            match export_target(&dir, &file_name, &options.on_conflict, &written) {
+                Some(target) => write_export_file(&source, &target, reencode.as_deref(), options)
+                    .map(|written_file| Some((target, written_file))),
+                None => Ok(None),
+            }
+        });
+        match result {
+            Ok(Some((target, (size, dimensions)))) => {
+                file.path = Some(file_ops::relative_to_library(&target, &destination));
+                file.file_size = Some(size);
+                file.width = dimensions.map(|(w, _)| w).or(asset.width.map(|w| w as u32));
+                file.height = dimensions.map(|(_, h)| h).or(asset.height.map(|h| h as u32));
+                file.converted = reencode.is_some();
+                written.insert(target);
+            }
+            Ok(None) => file.status = "skipped".to_string(),
+            Err(e) => {
+                tracing::warn!("Failed to export asset {}: {}", asset.id, e);
+                file.status = "failed".to_string();
+                file.error = Some(e.to_string());
+            }
+        }
+        files.push(file);
+    }
+
+    let count = |status: &str| files.iter().filter(|f| f.status == status).count();
+    let (exported, skipped, failed) = (count("exported"), count("skipped"), count("failed"));
+
+    let manifest_path = if options.write_manifest {
+        let path = if written.contains(&destination.join(MANIFEST_NAME)) {
+            file_ops::unique_file_path(&destination, MANIFEST_NAME)
+        } else {
+            destination.join(MANIFEST_NAME)
+        };
+        let manifest = serde_json::json!({
+            "selection": selection,
+            "options": options,
+            "exported": exported,
+            "skipped": skipped,
+            "failed": failed,
+            "files": &files,
+        });
+        std::fs::write(&path, serde_json::to_string_pretty(&manifest)?)?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(ExportResult {
+        destination: destination.to_string_lossy().to_string(),
+        files,
+        exported,
+        skipped,
+        failed,
+        manifest_path,
+    })
+}
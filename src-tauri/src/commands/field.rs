@@ -0,0 +1,258 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::db::{
+    models::{AssetFieldValue, CustomField, FieldCondition},
+    queries,
+};
+use crate::commands::asset::{load_assets, single_library};
+use crate::error::AppError;
+
+const FIELD_TYPES: &[&str] = &["text", "number", "boolean", "enum", "date"];
+
+fn validate_field_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Field name cannot be empty".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Fail if another field of the library already uses `name`
+async fn ensure_unique_name(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    field_id: Option<&str>,
+) -> Result<(), AppError> {
+    let fields = queries::get_custom_fields(pool, library_id).await?;
+    if fields
+        .iter()
+        .any(|f| f.name == name && Some(f.id.as_str()) != field_id)
+    {
+        return Err(AppError::InvalidInput(format!(
+            "A field named '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Trimmed, non-empty and unique enum options, in the given order
+fn clean_options(options: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for option in options {
+        let option = option.trim().to_string();
+        if !option.is_empty() && !cleaned.contains(&option) {
+            cleaned.push(option);
+        }
+    }
+    cleaned
+}
+
+/// Whether `value` is a `YYYY-MM-DD` date
+fn is_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return false;
+    }
+    match (year.parse::<u32>(), month.parse::<u32>(), day.parse::<u32>()) {
+        (Ok(_), Ok(month), Ok(day)) => (1..=12).contains(&month) && (1..=31).contains(&day),
+        _ => false,
+    }
+}
+
+/// Convert a JSON value to the stored text or number value for `field`.
+/// `null` clears the value.
+fn field_value(
+    field: &CustomField,
+    value: &serde_json::Value,
+) -> Result<(Option<String>, Option<f64>), AppError> {
+    use serde_json::Value;
+
+    let invalid = || {
+        AppError::InvalidInput(format!(
+            "Invalid value for {} field '{}': {}",
+            field.field_type, field.name, value
+        ))
+    };
+
+    match (field.field_type.as_str(), value) {
+        (_, Value::Null) => Ok((None, None)),
+        ("text", Value::String(s)) => Ok((Some(s.clone()), None)),
+        ("number", Value::Number(n)) => Ok((None, Some(n.as_f64().ok_or_else(invalid)?))),
+        ("boolean", Value::Bool(b)) => Ok((None, Some(if *b { 1.0 } else { 0.0 }))),
+        ("enum", Value::String(s)) if field.options.contains(s) => Ok((Some(s.clone()), None)),
+        ("date", Value::String(s)) if is_date(s) => Ok((Some(s.clone()), None)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Check that each condition's field exists and that its `op` and value suit the
+/// field's type, so that a mistyped filter fails instead of matching every asset
+pub(crate) async fn validate_field_conditions(
+    pool: &SqlitePool,
+    conditions: &[FieldCondition],
+) -> Result<(), AppError> {
+    use serde_json::Value;
+
+    for condition in conditions {
+        let field = queries::get_custom_field(pool, &condition.field_id).await?;
+        let ops: &[&str] = match field.field_type.as_str() {
+            "number" | "date" => &["eq", "ne", "lt", "lte", "gt", "gte", "set", "unset"],
+            "boolean" => &["eq", "ne", "set", "unset"],
+            _ => &["eq", "ne", "contains", "set", "unset"],
+        };
+        if !ops.contains(&condition.op.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported condition '{}' for {} field '{}'",
+                condition.op, field.field_type, field.name
+            )));
+        }
+        if matches!(condition.op.as_str(), "set" | "unset") {
+            continue;
+        }
+        let valid = match (field.field_type.as_str(), &condition.value) {
+            ("number", Value::Number(_)) | ("boolean", Value::Bool(_)) => true,
+            ("text" | "enum", Value::String(_)) => true,
+            ("date", Value::String(s)) => is_date(s),
+            _ => false,
+        };
+        if !valid {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid value for {} field '{}': {}",
+                field.field_type, field.name, condition.value
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// List a library's custom fields in display order
+#[tauri::command]
+pub async fn get_custom_fields(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<CustomField>, AppError> {
+    let fields = queries::get_custom_fields(&pool, &library_id).await?;
+    Ok(fields)
+}
+
+/// Add a custom field to a library. `options` lists the allowed values of enum fields.
+#[tauri::command]
+pub async fn create_custom_field(
+    library_id: String,
+    name: String,
+    field_type: String,
+    options: Option<Vec<String>>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CustomField, AppError> {
+    let name = validate_field_name(&name)?;
+    if !FIELD_TYPES.contains(&field_type.as_str()) {
+        return Err(AppError::InvalidInput(format!("Unknown field type: {}", field_type)));
+    }
+    let options = clean_options(options.unwrap_or_default());
+    if field_type == "enum" && options.is_empty() {
+        return Err(AppError::InvalidInput("Enum fields need at least one option".to_string()));
+    }
+
+    ensure_unique_name(&pool, &library_id, &name, None).await?;
+
+    let field =
+        queries::create_custom_field(&pool, &library_id, &name, &field_type, &options).await?;
+    Ok(field)
+}
+
+/// Rename, reorder or change the options of a custom field. The type cannot change.
+#[tauri::command]
+pub async fn update_custom_field(
+    id: String,
+    name: Option<String>,
+    options: Option<Vec<String>>,
+    position: Option<i64>,
+    pool: State<'_, SqlitePool>,
+) -> Result<CustomField, AppError> {
+    let field = queries::get_custom_field(&pool, &id).await?;
+    let name = match name {
+        Some(name) => validate_field_name(&name)?,
+        None => field.name.clone(),
+    };
+    let options = match options {
+        Some(options) => clean_options(options),
+        None => field.options.0.clone(),
+    };
+    if field.field_type == "enum" && options.is_empty() {
+        return Err(AppError::InvalidInput("Enum fields need at least one option".to_string()));
+    }
+    ensure_unique_name(&pool, &field.library_id, &name, Some(&id)).await?;
+
+    let updated = queries::update_custom_field(
+        &pool,
+        &id,
+        &name,
+        &options,
+        position.unwrap_or(field.position),
+    )
+    .await?;
+    Ok(updated)
+}
+
+/// Delete a custom field together with its values on all assets
+#[tauri::command]
+pub async fn delete_custom_field(
+    id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    queries::delete_custom_field(&pool, &id).await?;
+    Ok(())
+}
+
+/// Set (or with `null`, clear) a custom field value on an asset.
+/// Returns all field values of the asset.
+#[tauri::command]
+pub async fn set_asset_field_value(
+    asset_id: String,
+    field_id: String,
+    value: serde_json::Value,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<AssetFieldValue>, AppError> {
+    let asset = queries::get_asset(&pool, &asset_id).await?;
+    let field = queries::get_custom_field(&pool, &field_id).await?;
+    if field.library_id != asset.library_id {
+        return Err(AppError::InvalidInput(
+            "Field belongs to a different library".to_string(),
+        ));
+    }
+
+    let (text_value, number_value) = field_value(&field, &value)?;
+    queries::set_asset_field_value(&pool, &asset_id, &field_id, text_value.as_deref(), number_value)
+        .await?;
+
+    let values = queries::get_asset_field_values(&pool, &asset_id).await?;
+    Ok(values)
+}
+
+/// Set (or with `null`, clear) a custom field value on several assets in a single statement
+#[tauri::command]
+pub async fn bulk_set_field_value(
+    asset_ids: Vec<String>,
+    field_id: String,
+    value: serde_json::Value,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    let assets = load_assets(&pool, &asset_ids).await?;
+    let field = queries::get_custom_field(&pool, &field_id).await?;
+    if single_library(&assets)?.is_some_and(|library_id| library_id != field.library_id) {
+        return Err(AppError::InvalidInput(
+            "Field belongs to a different library".to_string(),
+        ));
+    }
+
+    let (text_value, number_value) = field_value(&field, &value)?;
+    queries::set_assets_field_value(&pool, &asset_ids, &field_id, text_value.as_deref(), number_value)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::db::{
+    models::{FolderInfo, FolderTreeSummary},
+    queries,
+};
+use crate::error::AppError;
+use crate::storage::file_ops;
+
+/// Validate a single folder name (no separators or relative components)
+fn validate_folder_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty()
+        || name.contains('/')
+        || name.contains('\\')
+        || name == "."
+        || name == ".."
+    {
+        return Err(AppError::InvalidInput("Invalid folder name".to_string()));
+    }
+    Ok(())
+}
+
+fn parse_folder_path(path: &str) -> Result<String, AppError> {
+    file_ops::normalize_folder_path(path)
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid folder path: {}", path)))
+}
+
+/// Directories under the library root, as canonical folder paths
+fn scan_disk_folders(base_path: &std::path::Path, current_path: &std::path::Path, folders: &mut Vec<String>) {
+    if let Ok(entries) = std::fs::read_dir(current_path) {
+        for entry in entries.flatten() {
+            if !entry.metadata().map(|m| m.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            // Skip hidden folders (starting with .), e.g. thumbnails
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            let full_path = entry.path();
+            if let Some(path) = full_path
+                .strip_prefix(base_path)
+                .ok()
+                .and_then(|p| p.to_str())
+                .and_then(file_ops::normalize_folder_path)
+            {
+                folders.push(path);
+            }
+            scan_disk_folders(base_path, &full_path, folders);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_folders(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<FolderInfo>, AppError> {
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = std::path::PathBuf::from(&library.root_path);
+
+    // Register folders that so far only exist as asset paths or directories on disk
+    let mut paths = queries::get_unregistered_folder_paths(&pool, &library_id).await?;
+    scan_disk_folders(&library_root, &library_root, &mut paths);
+    for path in paths {
+        queries::ensure_folder_path(&pool, &library_id, &path).await?;
+    }
+
+    let folders = queries::get_folders(&pool, &library_id).await?;
+    Ok(folders)
+}
+
+/// Create a new folder
+#[tauri::command]
+pub async fn create_folder(
+    library_id: String,
+    folder_name: String,
+    parent_path: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderInfo, AppError> {
+    validate_folder_name(&folder_name)?;
+    let parent_path = parse_folder_path(&parent_path)?;
+    let path = parse_folder_path(&format!("{}/{}", parent_path, folder_name.trim()))?;
+
+    if queries::get_folder_by_path(&pool, &library_id, &path)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::InvalidInput("Folder already exists".to_string()));
+    }
+
+    // Get library to access root path
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+    std::fs::create_dir_all(library_root.join(path.trim_start_matches('/')))?;
+
+    let folder = queries::ensure_folder_path(&pool, &library_id, &path)
+        .await?
+        .ok_or_else(|| AppError::InvalidInput("Invalid folder path".to_string()))?;
+    let info = queries::get_folder_info(&pool, &folder.id).await?;
+    Ok(info)
+}
+
+/// Update a folder's color, icon, description and default tags. Omitted fields are kept;
+/// an empty color or icon clears it.
+#[tauri::command]
+pub async fn update_folder(
+    id: String,
+    color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    default_tag_ids: Option<Vec<String>>,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderInfo, AppError> {
+    queries::update_folder(
+        &pool,
+        &id,
+        color.as_deref().map(str::trim),
+        icon.as_deref().map(str::trim),
+        description.as_deref(),
+        default_tag_ids.as_deref(),
+    )
+    .await?;
+    let info = queries::get_folder_info(&pool, &id).await?;
+    Ok(info)
+}
+
+/// Move a folder (and everything in it) to a new canonical path, on disk and in the database.
+/// Fails if the destination already exists or lies inside the folder itself.
+async fn relocate_folder(
+    pool: &SqlitePool,
+    library_id: &str,
+    old_path: &str,
+    new_path: &str,
+) -> Result<FolderInfo, AppError> {
+    if old_path == "/" {
+        return Err(AppError::InvalidInput("The library root cannot be moved".to_string()));
+    }
+    let folder = queries::get_folder_by_path(pool, library_id, old_path)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Folder {}", old_path)))?;
+    if new_path == old_path {
+        return Ok(queries::get_folder_info(pool, &folder.id).await?);
+    }
+    if new_path.starts_with(&format!("{}/", old_path)) {
+        return Err(AppError::InvalidInput(
+            "A folder cannot be moved into itself".to_string(),
+        ));
+    }
+
+    let library = queries::get_library(pool, library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+    let old_dir = library_root.join(old_path.trim_start_matches('/'));
+    let new_dir = library_root.join(new_path.trim_start_matches('/'));
+
+    // Only a case change of the same folder may find its destination already present
+    let case_only = old_path.to_lowercase() == new_path.to_lowercase();
+    if queries::get_folder_by_path(pool, library_id, new_path).await?.is_some()
+        || (new_dir.exists() && !case_only)
+    {
+        return Err(AppError::InvalidInput(
+            "A folder with this name already exists".to_string(),
+        ));
+    }
+
+    // Move the directory first, then the database; undo the move if the database update fails
+    let moved_on_disk = old_dir.is_dir();
+    if moved_on_disk {
+        if let Some(parent) = new_dir.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&old_dir, &new_dir)?;
+    }
+
+    if let Err(e) = queries::move_folder_tree(pool, library_id, old_path, new_path).await {
+        if moved_on_disk {
+            if let Err(undo) = std::fs::rename(&new_dir, &old_dir) {
+                tracing::error!("Failed to restore folder {:?} after error: {}", old_dir, undo);
+            }
+        }
+        return Err(e.into());
+    }
+
+    let info = queries::get_folder_info(pool, &folder.id).await?;
+    Ok(info)
+}
+
+/// Rename a folder in place, keeping its subfolders and assets
+#[tauri::command]
+pub async fn rename_folder(
+    library_id: String,
+    old_path: String,
+    new_name: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderInfo, AppError> {
+    validate_folder_name(&new_name)?;
+    let old_path = parse_folder_path(&old_path)?;
+    let parent_path = old_path.rsplit_once('/').map(|(parent, _)| parent).unwrap_or("");
+    let new_path = parse_folder_path(&format!("{}/{}", parent_path, new_name.trim()))?;
+
+    relocate_folder(&pool, &library_id, &old_path, &new_path).await
+}
+
+/// Move a folder (with its subfolders and assets) into another folder
+#[tauri::command]
+pub async fn move_folder(
+    library_id: String,
+    path: String,
+    new_parent_path: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderInfo, AppError> {
+    let old_path = parse_folder_path(&path)?;
+    let new_parent_path = parse_folder_path(&new_parent_path)?;
+    let name = old_path.rsplit('/').next().unwrap_or_default();
+    let new_path = parse_folder_path(&format!("{}/{}", new_parent_path, name))?;
+
+    relocate_folder(&pool, &library_id, &old_path, &new_path).await
+}
+
+/// Count the subfolders and assets that deleting a folder would remove
+#[tauri::command]
+pub async fn get_folder_delete_summary(
+    library_id: String,
+    path: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderTreeSummary, AppError> {
+    let path = parse_folder_path(&path)?;
+    let summary = queries::get_folder_tree_summary(&pool, &library_id, &path).await?;
+    Ok(summary)
+}
+
+/// Delete a folder with all its subfolders and assets, including thumbnails and embeddings.
+/// With `use_trash`, files are moved to the system trash instead of being removed.
+#[tauri::command]
+pub async fn delete_folder(
+    library_id: String,
+    path: String,
+    use_trash: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<FolderTreeSummary, AppError> {
+    let path = parse_folder_path(&path)?;
+    if path == "/" {
+        return Err(AppError::InvalidInput("The library root cannot be deleted".to_string()));
+    }
+
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+    let folder_dir = library_root.join(path.trim_start_matches('/'));
+
+    let summary = queries::get_folder_tree_summary(&pool, &library_id, &path).await?;
+    let assets = queries::get_folder_tree_assets(&pool, &library_id, &path).await?;
+
+    // Files stored outside the folder's directory (e.g. processing outputs) go individually
+    let outside_files: Vec<_> = assets
+        .iter()
+        .map(|a| library_root.join(&a.relative_path))
+        .filter(|p| !p.starts_with(&folder_dir) && p.exists())
+        .collect();
+
+    // The files go first so that the library keeps its records if this fails
+    if use_trash {
+        let mut targets = outside_files;
+        if folder_dir.exists() {
+            targets.push(folder_dir.clone());
+        }
+        if !targets.is_empty() {
+            trash::delete_all(&targets)
+                .map_err(|e| AppError::Internal(format!("Failed to move files to trash: {}", e)))?;
+        }
+    } else {
+        for file in &outside_files {
+            std::fs::remove_file(file)?;
+        }
+        if folder_dir.exists() {
+            tracing::info!("Deleting folder: {:?}", folder_dir);
+            std::fs::remove_dir_all(&folder_dir)?;
+        }
+    }
+
+    queries::delete_folder_tree(&pool, &library_id, &path).await?;
+
+    // Thumbnails and previous versions are internal data and always removed outright
+    for asset in &assets {
+        if let Some(thumb_rel) = &asset.thumbnail_path {
+            let thumb_path = library_root.join(thumb_rel);
+            if thumb_path.exists() {
+                if let Err(e) = std::fs::remove_file(&thumb_path) {
+                    tracing::warn!("Failed to remove thumbnail {:?}: {}", thumb_path, e);
+                }
+            }
+        }
+        let versions_dir = file_ops::versions_dir(library_root, &asset.id);
+        if versions_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&versions_dir) {
+                tracing::warn!("Failed to remove versions of {}: {}", asset.id, e);
+            }
+        }
+    }
+
+    Ok(summary)
+}
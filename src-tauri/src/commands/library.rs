@@ -50,6 +50,18 @@ pub async fn get_library(id: String, pool: State<'_, SqlitePool>) -> Result<Libr
     Ok(library)
 }
 
+/// Whether AI suggested names are applied right away or queued for review
+#[tauri::command]
+pub async fn set_library_auto_apply_ai_names(
+    id: String,
+    enabled: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<Library, AppError> {
+    queries::set_library_auto_apply_ai_names(&pool, &id, enabled).await?;
+    let library = queries::get_library(&pool, &id).await?;
+    Ok(library)
+}
+
 #[tauri::command]
 pub async fn delete_library(id: String, pool: State<'_, SqlitePool>) -> Result<(), AppError> {
     queries::delete_library(&pool, &id).await?;
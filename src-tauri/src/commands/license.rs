@@ -0,0 +1,216 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use tauri::State;
+
+use crate::db::{
+    models::{AssetLicense, LicenseUpdate, LicenseUsage},
+    queries,
+};
+use crate::error::AppError;
+
+/// Whether a license obliges users to credit the author: Creative Commons BY variants and
+/// common permissive licenses (MIT, BSD, Apache, OFL). CC0, purchased and in-house assets
+/// do not.
+pub(crate) fn license_requires_attribution(license: &str) -> bool {
+    let license = license.trim().to_uppercase().replace(' ', "-");
+    ["CC-BY", "MIT", "BSD", "APACHE", "OFL"]
+        .iter()
+        .any(|prefix| license.starts_with(prefix))
+}
+
+/// Apply a license update to several assets, keeping the fields it leaves unset
+pub(crate) async fn update_asset_licenses(
+    pool: &SqlitePool,
+    asset_ids: &[String],
+    update: &LicenseUpdate,
+) -> Result<(), AppError> {
+    let trimmed = |value: &Option<String>| value.as_deref().map(|v| v.trim().to_string());
+    let mut licenses = Vec::new();
+    for asset_id in asset_ids {
+        let mut license = match queries::get_asset_license(pool, asset_id).await? {
+            Some(license) => license,
+            None => {
+                // Fail early on unknown assets rather than on the foreign key
+                queries::get_asset(pool, asset_id).await?;
+                AssetLicense {
+                    asset_id: asset_id.clone(),
+                    license: String::new(),
+                    author: String::new(),
+                    source_url: String::new(),
+                    attribution: String::new(),
+                    requires_attribution: false,
+                    updated_at: String::new(),
+                }
+            }
+        };
+        if let Some(value) = trimmed(&update.license) {
+            license.requires_attribution = license_requires_attribution(&value);
+            license.license = value;
+        }
+        if let Some(value) = trimmed(&update.author) {
+            license.author = value;
+        }
+        if let Some(value) = trimmed(&update.source_url) {
+            license.source_url = value;
+        }
+        if let Some(value) = trimmed(&update.attribution) {
+            license.attribution = value;
+        }
+        if let Some(value) = update.requires_attribution {
+            license.requires_attribution = value;
+        }
+        licenses.push(license);
+    }
+    queries::save_asset_licenses(pool, &licenses).await?;
+    Ok(())
+}
+
+/// Set license, author, source URL or attribution text of several assets at once
+#[tauri::command]
+pub async fn set_asset_license(
+    asset_ids: Vec<String>,
+    update: LicenseUpdate,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    update_asset_licenses(&pool, &asset_ids, &update).await
+}
+
+#[tauri::command]
+pub async fn get_license_usage(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<LicenseUsage>, AppError> {
+    let usage = queries::get_license_usage(&pool, &library_id).await?;
+    Ok(usage)
+}
+
+/// Assets credited together: same author, source and attribution text
+#[derive(Serialize)]
+struct CreditEntry {
+    author: String,
+    source_url: String,
+    attribution: String,
+    assets: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct LicenseCredits {
+    license: String,
+    requires_attribution: bool,
+    entries: Vec<CreditEntry>,
+}
+
+impl CreditEntry {
+    /// The attribution text, or a line built from the author and source
+    fn credit_line(&self) -> String {
+        if !self.attribution.is_empty() {
+            return self.attribution.clone();
+        }
+        let mut line = if self.author.is_empty() {
+            "Unknown author".to_string()
+        } else {
+            self.author.clone()
+        };
+        if !self.source_url.is_empty() {
+            line.push_str(&format!(" ({})", self.source_url));
+        }
+        line
+    }
+}
+
+fn render_markdown(credits: &[LicenseCredits]) -> String {
+    let mut out = String::from("# Credits\n");
+    for group in credits {
+        out.push_str(&format!("\n## {}\n\n", group.license));
+        for entry in &group.entries {
+            let line = match (entry.attribution.is_empty(), entry.source_url.is_empty()) {
+                (true, false) => format!(
+                    "[{}]({})",
+                    if entry.author.is_empty() { &entry.source_url } else { &entry.author },
+                    entry.source_url
+                ),
+                _ => entry.credit_line(),
+            };
+            out.push_str(&format!("- {}: {}\n", line, entry.assets.join(", ")));
+        }
+    }
+    out
+}
+
+fn render_text(credits: &[LicenseCredits]) -> String {
+    let mut out = String::from("CREDITS\n");
+    for group in credits {
+        out.push_str(&format!("\n{}\n", group.license));
+        for entry in &group.entries {
+            out.push_str(&format!("  {}\n", entry.credit_line()));
+            out.push_str(&format!("    {}\n", entry.assets.join(", ")));
+        }
+    }
+    out
+}
+
+/// Build a credits file for the given assets in Markdown, plain text or JSON, grouped by
+/// license and then by author and source. Assets without a license are left out, as are
+/// assets that need no attribution when `attribution_only` is set. The file is also
+/// written to `output_path` if given.
+#[tauri::command]
+pub async fn generate_credits(
+    asset_ids: Vec<String>,
+    format: String,
+    attribution_only: bool,
+    output_path: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<String, AppError> {
+    if !matches!(format.as_str(), "markdown" | "text" | "json") {
+        return Err(AppError::InvalidInput(format!("Unknown credits format: {}", format)));
+    }
+
+    // Licenses are grouped case-insensitively, in alphabetical order
+    let mut groups: BTreeMap<String, LicenseCredits> = BTreeMap::new();
+    for asset_id in &asset_ids {
+        let Some(license) = queries::get_asset_license(&pool, asset_id).await? else {
+            continue;
+        };
+        if license.license.is_empty() || (attribution_only && !license.requires_attribution) {
+            continue;
+        }
+        let asset = queries::get_asset(&pool, asset_id).await?;
+
+        let group = groups
+            .entry(license.license.to_lowercase())
+            .or_insert_with(|| LicenseCredits {
+                license: license.license.clone(),
+                requires_attribution: false,
+                entries: Vec::new(),
+            });
+        group.requires_attribution |= license.requires_attribution;
+        match group.entries.iter_mut().find(|e| {
+            e.author == license.author
+                && e.source_url == license.source_url
+                && e.attribution == license.attribution
+        }) {
+            Some(entry) if !entry.assets.contains(&asset.file_name) => {
+                entry.assets.push(asset.file_name)
+            }
+            Some(_) => {}
+            None => group.entries.push(CreditEntry {
+                author: license.author,
+                source_url: license.source_url,
+                attribution: license.attribution,
+                assets: vec![asset.file_name],
+            }),
+        }
+    }
+    let credits: Vec<LicenseCredits> = groups.into_values().collect();
+
+    let output = match format.as_str() {
+        "markdown" => render_markdown(&credits),
+        "text" => render_text(&credits),
+        _ => serde_json::to_string_pretty(&credits)?,
+    };
+    if let Some(path) = output_path.filter(|p| !p.trim().is_empty()) {
+        std::fs::write(path.trim(), &output)?;
+    }
+    Ok(output)
+}
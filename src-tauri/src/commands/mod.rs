@@ -1,7 +1,17 @@
 pub mod ai;
 pub mod asset;
+pub mod bulk;
+pub mod collection;
+pub mod comment;
+pub mod export;
+pub mod field;
+pub mod folder;
 pub mod library;
+pub mod license;
 pub mod menu;
 pub mod processing;
+pub mod rename;
 pub mod search;
 pub mod tag;
+pub mod usage;
+pub mod version;
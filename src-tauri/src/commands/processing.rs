@@ -1,10 +1,170 @@
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use std::path::Path;
 use tauri::State;
 use uuid::Uuid;
 
-use crate::db::{models::Asset, queries};
+use crate::commands::asset::{record_image_metadata, record_palette};
+use crate::commands::version::replace_with_file;
+use crate::db::{
+    models::{Asset, AssetLineage, DerivedAsset},
+    queries,
+};
 use crate::error::AppError;
-use crate::processing::{background, compress, descriptor, spritesheet};
+use crate::processing::{
+    aseprite, background, compress, descriptor,
+    spritesheet::{self, SpritesheetInfo},
+};
+use crate::storage::file_ops;
+
+/// A processing operation with its parameters. Stored with every output so the
+/// operation can be re-run when a source changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum ProcessingOperation {
+    RemoveBackground {
+        target_color: [u8; 3],
+        tolerance: u8,
+    },
+    SplitImage {
+        rows: u32,
+        cols: u32,
+    },
+    MergeSpritesheet {
+        columns: u32,
+        padding: u32,
+        output_name: String,
+        descriptor_format: String,
+    },
+    ExportAsepriteTag {
+        tag_name: Option<String>,
+        columns: u32,
+        padding: u32,
+        output_name: String,
+        descriptor_format: String,
+    },
+    CompressImage {
+        max_width: Option<u32>,
+        max_height: Option<u32>,
+        quality: u8,
+        output_format: String,
+        suffix: String,
+    },
+}
+
+impl ProcessingOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::RemoveBackground { .. } => "remove_background",
+            Self::SplitImage { .. } => "split_image",
+            Self::MergeSpritesheet { .. } => "merge_spritesheet",
+            Self::ExportAsepriteTag { .. } => "export_aseprite_tag",
+            Self::CompressImage { .. } => "compress_image",
+        }
+    }
+
+    /// Extension of the files this operation writes
+    fn output_ext(&self) -> &'static str {
+        match self {
+            Self::CompressImage { output_format, .. } if is_jpeg(output_format) => "jpg",
+            _ => "png",
+        }
+    }
+
+    fn encode(&self, img: &DynamicImage) -> Result<Vec<u8>, AppError> {
+        match self {
+            Self::CompressImage { output_format, quality, .. } if is_jpeg(output_format) => {
+                compress::compress_to_jpeg_bytes(img, *quality)
+            }
+            _ => compress::compress_to_png_bytes(img),
+        }
+    }
+}
+
+fn is_jpeg(output_format: &str) -> bool {
+    output_format == "jpeg" || output_format == "jpg"
+}
+
+/// Images produced by running an operation on its sources
+struct ProcessingOutput {
+    images: Vec<DynamicImage>,
+    sheet: Option<SpritesheetInfo>,
+}
+
+/// Run `operation` on `sources`, in the order they were originally given
+fn run_operation(
+    operation: &ProcessingOperation,
+    sources: &[Asset],
+    library_root: &Path,
+) -> Result<ProcessingOutput, AppError> {
+    let first = sources
+        .first()
+        .ok_or_else(|| AppError::InvalidInput("No source assets".to_string()))?;
+    let source_path = library_root.join(&first.relative_path);
+
+    let output = match operation {
+        ProcessingOperation::RemoveBackground { target_color, tolerance } => ProcessingOutput {
+            images: vec![background::remove_background_color_key(&source_path, *target_color, *tolerance)?],
+            sheet: None,
+        },
+        ProcessingOperation::SplitImage { rows, cols } => ProcessingOutput {
+            images: spritesheet::split_image_grid(&source_path, *rows, *cols)?,
+            sheet: None,
+        },
+        ProcessingOperation::MergeSpritesheet { columns, padding, .. } => {
+            let paths: Vec<_> = sources
+                .iter()
+                .map(|a| (a.file_name.clone(), library_root.join(&a.relative_path)))
+                .collect();
+            let paths_ref: Vec<(String, &Path)> = paths
+                .iter()
+                .map(|(name, path)| (name.clone(), path.as_path()))
+                .collect();
+            let (img, info) = spritesheet::merge_spritesheet(&paths_ref, *columns, *padding)?;
+            ProcessingOutput { images: vec![img], sheet: Some(info) }
+        }
+        ProcessingOperation::ExportAsepriteTag { tag_name, columns, padding, .. } => {
+            let frames = render_aseprite_frames(&source_path, tag_name.as_deref())?;
+            let (img, info) = spritesheet::merge_spritesheet_images(&frames, *columns, *padding)?;
+            ProcessingOutput { images: vec![img], sheet: Some(info) }
+        }
+        ProcessingOperation::CompressImage { max_width, max_height, quality, .. } => ProcessingOutput {
+            images: vec![compress::compress_image(&source_path, *max_width, *max_height, *quality)?],
+            sheet: None,
+        },
+    };
+    Ok(output)
+}
+
+/// Record the sources and parameters of newly created outputs, in output order
+async fn record_derivations(
+    pool: &SqlitePool,
+    operation: &ProcessingOperation,
+    sources: &[Asset],
+    outputs: &[Asset],
+) -> Result<(), AppError> {
+    let params = serde_json::to_value(operation)?;
+    let batch_id = Uuid::new_v4().to_string();
+    let sources: Vec<(String, String)> = sources
+        .iter()
+        .map(|a| (a.id.clone(), a.file_name.clone()))
+        .collect();
+
+    for (i, output) in outputs.iter().enumerate() {
+        queries::insert_asset_derivation(
+            pool,
+            &output.id,
+            operation.name(),
+            &params,
+            &batch_id,
+            i as i64,
+            &sources,
+        )
+        .await?;
+    }
+    Ok(())
+}
 
 #[tauri::command]
 pub async fn remove_background(
@@ -17,6 +177,7 @@ pub async fn remove_background(
     let library = queries::get_library(&pool, &asset.library_id).await?;
     let source_path = std::path::Path::new(&library.root_path).join(&asset.relative_path);
 
+    let operation = ProcessingOperation::RemoveBackground { target_color, tolerance };
     let result_img = background::remove_background_color_key(&source_path, target_color, tolerance)?;
 
     // Save as new asset
@@ -57,12 +218,18 @@ pub async fn remove_background(
         ai_description: String::new(),
         thumbnail_path: thumb_path,
         folder_path: asset.folder_path.clone(),
+        rating: 0,
+        color_label: None,
+        is_favorite: false,
         created_at: String::new(),
         updated_at: String::new(),
         imported_at: String::new(),
     };
 
     let saved = queries::insert_asset(&pool, &new_asset).await?;
+    record_image_metadata(&pool, &output_path, &saved.id).await?;
+    record_palette(&pool, &output_path, &saved.id).await?;
+    record_derivations(&pool, &operation, std::slice::from_ref(&asset), std::slice::from_ref(&saved)).await?;
     Ok(saved)
 }
 
@@ -90,11 +257,13 @@ pub async fn merge_spritesheet(
     let library = queries::get_library(&pool, &first_asset.library_id).await?;
     let library_root = std::path::Path::new(&library.root_path);
 
+    let mut sources = Vec::new();
     let mut image_paths = Vec::new();
     for id in &asset_ids {
         let asset = queries::get_asset(&pool, id).await?;
         let path = library_root.join(&asset.relative_path);
         image_paths.push((asset.file_name.clone(), path));
+        sources.push(asset);
     }
 
     let paths_ref: Vec<(String, &std::path::Path)> = image_paths
@@ -102,8 +271,118 @@ pub async fn merge_spritesheet(
         .map(|(name, path)| (name.clone(), path.as_path()))
         .collect();
 
-    let (sheet_img, info) = spritesheet::merge_spritesheet(&paths_ref, columns, padding)?;
+    let sheet = spritesheet::merge_spritesheet(&paths_ref, columns, padding)?;
+    let description = format!("Sprite sheet with {} frames", sheet.1.frames.len());
+
+    let result = save_spritesheet(
+        &pool,
+        &first_asset,
+        library_root,
+        sheet,
+        &output_name,
+        &descriptor_format,
+        description,
+    )
+    .await?;
+
+    let operation = ProcessingOperation::MergeSpritesheet {
+        columns,
+        padding,
+        output_name,
+        descriptor_format,
+    };
+    record_derivations(&pool, &operation, &sources, std::slice::from_ref(&result.image_asset)).await?;
+    Ok(result)
+}
+
+/// Export the frames of an Aseprite animation tag (or all frames) as a sprite sheet
+#[tauri::command]
+pub async fn export_aseprite_tag(
+    asset_id: String,
+    tag_name: Option<String>,
+    columns: u32,
+    padding: u32,
+    output_name: String,
+    descriptor_format: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<SpritesheetResult, AppError> {
+    let asset = queries::get_asset(&pool, &asset_id).await?;
+    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let library_root = std::path::Path::new(&library.root_path);
+    let source_path = library_root.join(&asset.relative_path);
+
+    if !aseprite::is_aseprite(&source_path) {
+        return Err(AppError::InvalidInput(
+            "Asset is not an Aseprite file".to_string(),
+        ));
+    }
+
+    let images = render_aseprite_frames(&source_path, tag_name.as_deref())?;
 
+    let sheet = spritesheet::merge_spritesheet_images(&images, columns, padding)?;
+    let description = match &tag_name {
+        Some(tag) => format!("Sprite sheet of '{}' from {} ({} frames)", tag, asset.file_name, images.len()),
+        None => format!("Sprite sheet from {} ({} frames)", asset.file_name, images.len()),
+    };
+
+    let result = save_spritesheet(
+        &pool,
+        &asset,
+        library_root,
+        sheet,
+        &output_name,
+        &descriptor_format,
+        description,
+    )
+    .await?;
+
+    let operation = ProcessingOperation::ExportAsepriteTag {
+        tag_name,
+        columns,
+        padding,
+        output_name,
+        descriptor_format,
+    };
+    record_derivations(&pool, &operation, std::slice::from_ref(&asset), std::slice::from_ref(&result.image_asset)).await?;
+    Ok(result)
+}
+
+/// Render the frames of an Aseprite animation tag (or all frames), named for the descriptor
+fn render_aseprite_frames(
+    source_path: &Path,
+    tag_name: Option<&str>,
+) -> Result<Vec<(String, DynamicImage)>, AppError> {
+    let file = aseprite::AsepriteFile::open(source_path)?;
+    let (frame_indices, frame_prefix) = match tag_name {
+        Some(tag) => {
+            let frames = file
+                .tag_frames(tag)
+                .ok_or_else(|| AppError::NotFound(format!("Animation tag '{}'", tag)))?;
+            (frames, tag.to_string())
+        }
+        None => ((0..file.frame_count()).collect(), "frame".to_string()),
+    };
+
+    frame_indices
+        .iter()
+        .enumerate()
+        .map(|(i, frame_index)| {
+            let frame = file.render_frame(*frame_index)?;
+            Ok((format!("{}_{}", frame_prefix, i), DynamicImage::ImageRgba8(frame)))
+        })
+        .collect()
+}
+
+/// Save a merged sprite sheet and its descriptor as a new asset next to `source`
+async fn save_spritesheet(
+    pool: &SqlitePool,
+    source: &Asset,
+    library_root: &std::path::Path,
+    (sheet_img, info): (DynamicImage, SpritesheetInfo),
+    output_name: &str,
+    descriptor_format: &str,
+    description: String,
+) -> Result<SpritesheetResult, AppError> {
     // Save sprite sheet
     let new_id = Uuid::new_v4().to_string();
     let output_dir = library_root.join("assets");
@@ -113,22 +392,8 @@ pub async fn merge_spritesheet(
 
     let relative_path = format!("assets/{}.png", new_id);
 
-    // Generate descriptor
-    let img_filename = format!("{}.png", output_name);
-    let descriptor_content = match descriptor_format.as_str() {
-        "xml_unity" => descriptor::generate_unity_xml_descriptor(&info, &img_filename),
-        "plist_cocos2d" => descriptor::generate_cocos2d_plist_descriptor(&info, &img_filename),
-        _ => descriptor::generate_json_descriptor(&info, &img_filename),
-    };
-
     // Save descriptor file alongside
-    let desc_ext = match descriptor_format.as_str() {
-        "xml_unity" => "xml",
-        "plist_cocos2d" => "plist",
-        _ => "json",
-    };
-    let desc_path = output_dir.join(format!("{}.{}", output_name, desc_ext));
-    std::fs::write(&desc_path, &descriptor_content)?;
+    let descriptor_content = write_descriptor(&output_dir, &info, output_name, descriptor_format)?;
 
     let thumb_path =
         crate::storage::thumbnail::generate_thumbnail(&output_path, library_root, &new_id).ok();
@@ -138,7 +403,7 @@ pub async fn merge_spritesheet(
 
     let new_asset = Asset {
         id: new_id,
-        library_id: first_asset.library_id.clone(),
+        library_id: source.library_id.clone(),
         file_name: format!("{}.png", output_name),
         original_name: format!("{}.png", output_name),
         relative_path,
@@ -149,16 +414,21 @@ pub async fn merge_spritesheet(
         width: Some(info.width as i32),
         height: Some(info.height as i32),
         duration_ms: None,
-        description: format!("Sprite sheet with {} frames", info.frames.len()),
+        description,
         ai_description: String::new(),
         thumbnail_path: thumb_path,
-        folder_path: first_asset.folder_path.clone(),
+        folder_path: source.folder_path.clone(),
+        rating: 0,
+        color_label: None,
+        is_favorite: false,
         created_at: String::new(),
         updated_at: String::new(),
         imported_at: String::new(),
     };
 
-    let saved = queries::insert_asset(&pool, &new_asset).await?;
+    let saved = queries::insert_asset(pool, &new_asset).await?;
+    record_image_metadata(pool, &output_path, &saved.id).await?;
+    record_palette(pool, &output_path, &saved.id).await?;
 
     Ok(SpritesheetResult {
         image_asset: saved,
@@ -166,6 +436,32 @@ pub async fn merge_spritesheet(
     })
 }
 
+/// Generate a sprite sheet descriptor and write it into `dir` as `<output_name>.<ext>`.
+/// Returns the descriptor content.
+fn write_descriptor(
+    dir: &Path,
+    info: &SpritesheetInfo,
+    output_name: &str,
+    descriptor_format: &str,
+) -> Result<String, AppError> {
+    // Generate descriptor
+    let img_filename = format!("{}.png", output_name);
+    let descriptor_content = match descriptor_format {
+        "xml_unity" => descriptor::generate_unity_xml_descriptor(info, &img_filename),
+        "plist_cocos2d" => descriptor::generate_cocos2d_plist_descriptor(info, &img_filename),
+        _ => descriptor::generate_json_descriptor(info, &img_filename),
+    };
+
+    let desc_ext = match descriptor_format {
+        "xml_unity" => "xml",
+        "plist_cocos2d" => "plist",
+        _ => "json",
+    };
+    let desc_path = dir.join(format!("{}.{}", output_name, desc_ext));
+    std::fs::write(&desc_path, &descriptor_content)?;
+    Ok(descriptor_content)
+}
+
 #[tauri::command]
 pub async fn split_image(
     asset_id: String,
@@ -178,6 +474,7 @@ pub async fn split_image(
     let library_root = std::path::Path::new(&library.root_path);
     let source_path = library_root.join(&asset.relative_path);
 
+    let operation = ProcessingOperation::SplitImage { rows, cols };
     let sub_images = spritesheet::split_image_grid(&source_path, rows, cols)?;
 
     let output_dir = library_root.join("assets");
@@ -223,15 +520,21 @@ pub async fn split_image(
             ai_description: String::new(),
             thumbnail_path: thumb_path,
             folder_path: asset.folder_path.clone(),
+            rating: 0,
+            color_label: None,
+            is_favorite: false,
             created_at: String::new(),
             updated_at: String::new(),
             imported_at: String::new(),
         };
 
         let saved = queries::insert_asset(&pool, &new_asset).await?;
+        record_image_metadata(&pool, &output_path, &saved.id).await?;
+        record_palette(&pool, &output_path, &saved.id).await?;
         results.push(saved);
     }
 
+    record_derivations(&pool, &operation, std::slice::from_ref(&asset), &results).await?;
     Ok(results)
 }
 
@@ -252,14 +555,27 @@ pub async fn compress_image(
     output_format: String, // "jpeg" or "png"
     suffix: String,        // localized suffix like "_compressed" or "_压缩"
     pool: State<'_, SqlitePool>,
+) -> Result<CompressResult, AppError> {
+    compress_asset(&pool, &asset_id, max_width, max_height, quality, output_format, suffix).await
+}
+
+/// Save a compressed copy of an image asset as a new asset next to it
+pub(crate) async fn compress_asset(
+    pool: &SqlitePool,
+    asset_id: &str,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    quality: u8,
+    output_format: String,
+    suffix: String,
 ) -> Result<CompressResult, AppError> {
     tracing::info!("=== Compress Image Started ===");
     tracing::info!("asset_id: {}, quality: {}, format: {}, suffix: {}", asset_id, quality, output_format, suffix);
 
-    let asset = queries::get_asset(&pool, &asset_id).await?;
+    let asset = queries::get_asset(pool, asset_id).await?;
     tracing::info!("Asset found: {} ({})", asset.file_name, asset.relative_path);
 
-    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let library = queries::get_library(pool, &asset.library_id).await?;
     let library_root = std::path::Path::new(&library.root_path);
     let source_path = library_root.join(&asset.relative_path);
     tracing::info!("Source path: {:?}", source_path);
@@ -283,7 +599,7 @@ pub async fn compress_image(
     let output_dir = if asset.folder_path.is_empty() || asset.folder_path == "/" {
         library_root.join("assets")
     } else {
-        library_root.join(&asset.folder_path)
+        library_root.join(asset.folder_path.trim_start_matches('/'))
     };
     tracing::info!("Output directory: {:?}", output_dir);
 
@@ -293,13 +609,16 @@ pub async fn compress_image(
     }
     tracing::info!("Output directory created/verified");
 
-    let (output_ext, mime_type, output_bytes) = if output_format == "jpeg" || output_format == "jpg" {
-        let bytes = compress::compress_to_jpeg_bytes(&compressed_img, quality)?;
-        ("jpg", "image/jpeg", bytes)
-    } else {
-        let bytes = compress::compress_to_png_bytes(&compressed_img)?;
-        ("png", "image/png", bytes)
+    let operation = ProcessingOperation::CompressImage {
+        max_width,
+        max_height,
+        quality,
+        output_format,
+        suffix: suffix.clone(),
     };
+    let output_ext = operation.output_ext();
+    let mime_type = if output_ext == "jpg" { "image/jpeg" } else { "image/png" };
+    let output_bytes = operation.encode(&compressed_img)?;
 
     let output_path = output_dir.join(format!("{}.{}", new_id, output_ext));
     tracing::info!("Writing compressed image to: {:?}", output_path);
@@ -310,11 +629,7 @@ pub async fn compress_image(
     }
     tracing::info!("Compressed image written successfully, size: {} bytes", output_bytes.len());
 
-    let relative_path = if asset.folder_path.is_empty() || asset.folder_path == "/" {
-        format!("assets/{}.{}", new_id, output_ext)
-    } else {
-        format!("{}/{}.{}", asset.folder_path, new_id, output_ext)
-    };
+    let relative_path = file_ops::relative_to_library(&output_path, library_root);
     let compressed_size = output_bytes.len() as i64;
     let compression_ratio = if original_size > 0 {
         (1.0 - (compressed_size as f64 / original_size as f64)) * 100.0
@@ -352,20 +667,27 @@ pub async fn compress_image(
         ai_description: asset.ai_description.clone(),
         thumbnail_path: thumb_path,
         folder_path: asset.folder_path.clone(),
+        rating: 0,
+        color_label: None,
+        is_favorite: false,
         created_at: String::new(),
         updated_at: String::new(),
         imported_at: String::new(),
     };
 
-    let saved = queries::insert_asset(&pool, &new_asset).await?;
+    let saved = queries::insert_asset(pool, &new_asset).await?;
+    record_image_metadata(pool, &output_path, &saved.id).await?;
+    record_palette(pool, &output_path, &saved.id).await?;
 
     // Copy tags from original asset
-    let original_tags = queries::get_asset_tags(&pool, &asset_id).await?;
+    let original_tags = queries::get_asset_tags(pool, asset_id).await?;
     if !original_tags.is_empty() {
         let tag_ids: Vec<String> = original_tags.iter().map(|t| t.id.clone()).collect();
-        queries::assign_tags(&pool, &new_id, &tag_ids).await?;
+        queries::assign_tags(pool, &new_id, &tag_ids).await?;
     }
 
+    record_derivations(pool, &operation, std::slice::from_ref(&asset), std::slice::from_ref(&saved)).await?;
+
     Ok(CompressResult {
         asset: saved,
         original_size,
@@ -373,3 +695,103 @@ pub async fn compress_image(
         compression_ratio,
     })
 }
+
+/// The sources and operation an asset was produced from, with the source assets
+/// that still exist filled in
+pub(crate) async fn asset_lineage(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<AssetLineage>, AppError> {
+    let Some(derivation) = queries::get_asset_derivation(pool, asset_id).await? else {
+        return Ok(None);
+    };
+
+    let mut sources = queries::get_derivation_sources(pool, &derivation.id).await?;
+    for source in &mut sources {
+        if let Some(id) = &source.source_asset_id {
+            source.asset = Some(queries::get_asset(pool, id).await?);
+        }
+    }
+    Ok(Some(AssetLineage { derivation, sources }))
+}
+
+/// Get the operation and source assets a processing output was created from
+#[tauri::command]
+pub async fn get_asset_sources(
+    asset_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Option<AssetLineage>, AppError> {
+    asset_lineage(&pool, &asset_id).await
+}
+
+/// List the assets created from an asset by processing operations
+#[tauri::command]
+pub async fn get_asset_derivatives(
+    asset_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<DerivedAsset>, AppError> {
+    let derivatives = queries::get_asset_derivatives(&pool, &asset_id).await?;
+    Ok(derivatives)
+}
+
+/// Re-run the operation that produced an asset with the same parameters, e.g. after
+/// its source was replaced. Every output of the original run is updated in place
+/// (all parts of a split), keeping the previous files as versions.
+#[tauri::command]
+pub async fn rerun_processing(
+    asset_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<Asset>, AppError> {
+    let derivation = queries::get_asset_derivation(&pool, &asset_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::InvalidInput("Asset was not created by a processing operation".to_string())
+        })?;
+    let operation: ProcessingOperation = serde_json::from_value(derivation.params.0.clone())?;
+
+    let mut sources = Vec::new();
+    for source in queries::get_derivation_sources(&pool, &derivation.id).await? {
+        let id = source.source_asset_id.ok_or_else(|| {
+            AppError::InvalidInput(format!("Source asset {} has been deleted", source.source_name))
+        })?;
+        sources.push(queries::get_asset(&pool, &id).await?);
+    }
+
+    let asset = queries::get_asset(&pool, &asset_id).await?;
+    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let library_root = Path::new(&library.root_path);
+    let output = run_operation(&operation, &sources, library_root)?;
+
+    let note = format!("Before re-running {}", derivation.operation);
+    let mut updated = Vec::new();
+    for output_derivation in queries::get_derivation_batch(&pool, &derivation.batch_id).await? {
+        let Some(img) = output.images.get(output_derivation.output_index as usize) else {
+            continue;
+        };
+        let output_asset = queries::get_asset(&pool, &output_derivation.asset_id).await?;
+
+        if let (
+            Some(info),
+            ProcessingOperation::MergeSpritesheet { output_name, descriptor_format, .. }
+            | ProcessingOperation::ExportAsepriteTag { output_name, descriptor_format, .. },
+        ) = (&output.sheet, &operation)
+        {
+            let file_path = library_root.join(&output_asset.relative_path);
+            let dir = file_path.parent().unwrap_or(library_root);
+            write_descriptor(dir, info, output_name, descriptor_format)?;
+        }
+
+        // Write the new image next to the versions; replacing copies it into place
+        let dir = file_ops::versions_dir(library_root, &output_asset.id);
+        std::fs::create_dir_all(&dir)?;
+        let temp_path = dir.join(format!("rerun.{}", operation.output_ext()));
+        std::fs::write(&temp_path, operation.encode(img)?)?;
+
+        let original_name = output_asset.original_name.clone();
+        let result = replace_with_file(&pool, output_asset, &temp_path, original_name, note.clone()).await;
+        std::fs::remove_file(&temp_path).ok();
+        updated.push(result?);
+    }
+
+    Ok(updated)
+}
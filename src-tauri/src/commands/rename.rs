@@ -0,0 +1,559 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use regex::Regex;
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::commands::asset::undo_file_moves;
+use crate::db::{
+    models::{Asset, BatchRenameOptions, BatchRenameResult, RenamePreview, Tag},
+    queries,
+};
+use crate::error::AppError;
+use crate::storage::file_ops;
+
+/// Tokens that can be used in rename templates
+const TOKENS: &[&str] = &[
+    "name", "ext", "original", "index", "folder", "tag", "width", "height", "ai_name", "type",
+];
+
+/// Case transforms, usable as `{token|case}` or for the whole name
+const CASES: &[&str] = &["lower", "upper", "title", "snake", "kebab", "camel", "pascal"];
+
+/// A parsed piece of a rename template
+enum Segment {
+    Literal(String),
+    /// `{name:arg|case|case}`
+    Token {
+        name: String,
+        arg: Option<String>,
+        cases: Vec<String>,
+    },
+}
+
+/// Values available to the template for one asset
+struct RenameContext<'a> {
+    asset: &'a Asset,
+    index: u64,
+    tags: &'a [Tag],
+    ai_name: Option<&'a str>,
+}
+
+fn invalid_template(message: &str) -> AppError {
+    AppError::InvalidInput(format!("Invalid rename template: {}", message))
+}
+
+fn validate_case(case: &str) -> Result<String, AppError> {
+    if !CASES.contains(&case) {
+        return Err(AppError::InvalidInput(format!("Unknown case transform: {}", case)));
+    }
+    Ok(case.to_string())
+}
+
+/// Split a template into literals and tokens. `{{` and `}}` are literal braces.
+fn parse_template(template: &str) -> Result<Vec<Segment>, AppError> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err(invalid_template("unmatched '}'")),
+            '{' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => token.push(c),
+                        None => return Err(invalid_template("unclosed '{'")),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(parse_token(&token)?);
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn parse_token(token: &str) -> Result<Segment, AppError> {
+    let mut parts = token.split('|').map(str::trim);
+    let head = parts.next().unwrap_or_default();
+    let (name, arg) = match head.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg.trim().to_string())),
+        None => (head, None),
+    };
+    if !TOKENS.contains(&name) {
+        return Err(invalid_template(&format!("unknown token {{{}}}", name)));
+    }
+    if let (Some(arg), "index") = (&arg, name) {
+        if arg.parse::<usize>().is_err() {
+            return Err(invalid_template(&format!("invalid index width '{}'", arg)));
+        }
+    }
+    let cases = parts.map(validate_case).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Segment::Token {
+        name: name.to_string(),
+        arg,
+        cases,
+    })
+}
+
+/// Split a file name into stem and extension. Leading dots do not start an extension.
+pub(crate) fn split_name(file_name: &str) -> (&str, Option<&str>) {
+    match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (file_name, None),
+    }
+}
+
+/// Split text into words at separators and lower-to-upper case changes
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut after_lower = false;
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            after_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && after_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+        after_lower = c.is_lowercase() || c.is_numeric();
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+pub(crate) fn apply_case(text: &str, case: &str) -> String {
+    match case {
+        "lower" => text.to_lowercase(),
+        "upper" => text.to_uppercase(),
+        "title" => words(text).iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(" "),
+        "snake" => words(text).join("_").to_lowercase(),
+        "kebab" => words(text).join("-").to_lowercase(),
+        "camel" => words(text)
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize(w) })
+            .collect(),
+        "pascal" => words(text).iter().map(|w| capitalize(w)).collect(),
+        _ => text.to_string(),
+    }
+}
+
+fn render_token(name: &str, arg: Option<&str>, ctx: &RenameContext) -> Result<String, String> {
+    let asset = ctx.asset;
+    let value = match name {
+        "name" => split_name(&asset.file_name).0.to_string(),
+        "ext" => split_name(&asset.file_name).1.unwrap_or_default().to_string(),
+        "original" => split_name(&asset.original_name).0.to_string(),
+        "index" => {
+            let width = arg.and_then(|a| a.parse::<usize>().ok()).unwrap_or(0);
+            format!("{:0width$}", ctx.index, width = width)
+        }
+        "folder" => asset.folder_path.rsplit('/').next().unwrap_or_default().to_string(),
+        "tag" => ctx
+            .tags
+            .iter()
+            .find(|t| arg.is_none_or(|category| t.category.eq_ignore_ascii_case(category)))
+            .map(|t| t.name.clone())
+            .ok_or_else(|| match arg {
+                Some(category) => format!("Asset has no '{}' tag", category),
+                None => "Asset has no tags".to_string(),
+            })?,
+        "width" => asset
+            .width
+            .map(|w| w.to_string())
+            .ok_or_else(|| "Asset has no width".to_string())?,
+        "height" => asset
+            .height
+            .map(|h| h.to_string())
+            .ok_or_else(|| "Asset has no height".to_string())?,
+        "ai_name" => ctx
+            .ai_name
+            .map(|n| split_name(n).0.to_string())
+            .ok_or_else(|| "Asset has no AI suggested name".to_string())?,
+        "type" => asset.file_type.clone(),
+        _ => String::new(),
+    };
+    Ok(value)
+}
+
+fn render_template(segments: &[Segment], ctx: &RenameContext) -> Result<String, String> {
+    let mut output = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => output.push_str(text),
+            Segment::Token { name, arg, cases } => {
+                let value = render_token(name, arg.as_deref(), ctx)?;
+                output.push_str(&cases.iter().fold(value, |value, case| apply_case(&value, case)));
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Check that `name` can be used as a file name on all platforms
+pub(crate) fn validate_file_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name == "." || name == ".." {
+        return Err("Name cannot be empty".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_control() || matches!(c, '/' | '\\' | '<' | '>' | ':' | '"' | '|' | '?' | '*'))
+    {
+        return Err(format!("Name cannot contain '{}'", c.escape_default()));
+    }
+    Ok(())
+}
+
+/// File name for an AI suggested name: the suggestion in snake_case with the asset's
+/// extension, made unique among the other assets of its folder by appending `_2`, `_3`, ...
+pub(crate) async fn ai_file_name(
+    pool: &SqlitePool,
+    asset: &Asset,
+    suggested_name: &str,
+) -> Result<String, AppError> {
+    let ext = split_name(&asset.file_name).1;
+    let stem = apply_case(split_name(suggested_name).0, "snake");
+    validate_file_name(&stem).map_err(AppError::InvalidInput)?;
+
+    let taken: HashSet<String> =
+        queries::get_folder_file_names(pool, &asset.library_id, &asset.folder_path)
+            .await?
+            .into_iter()
+            .filter(|(id, _)| *id != asset.id)
+            .map(|(_, name)| name.to_lowercase())
+            .collect();
+
+    let with_ext = |stem: &str| match ext {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem.to_string(),
+    };
+    let mut name = with_ext(&stem);
+    let mut i = 2;
+    while taken.contains(&name.to_lowercase()) {
+        name = with_ext(&format!("{}_{}", stem, i));
+        i += 1;
+    }
+    Ok(name)
+}
+
+/// Compile the find pattern of the options. Plain text is matched literally.
+fn find_pattern(options: &BatchRenameOptions) -> Result<Option<Regex>, AppError> {
+    let Some(find) = options.find.as_deref().filter(|f| !f.is_empty()) else {
+        return Ok(None);
+    };
+    let pattern = if options.regex {
+        find.to_string()
+    } else {
+        regex::escape(find)
+    };
+    Regex::new(&pattern)
+        .map(Some)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid find pattern: {}", e)))
+}
+
+/// Compute the new name of one asset
+fn render_new_name(
+    segments: &[Segment],
+    find: Option<&Regex>,
+    options: &BatchRenameOptions,
+    ctx: &RenameContext,
+) -> Result<String, String> {
+    let mut stem = render_template(segments, ctx)?;
+    if let Some(find) = find {
+        stem = if options.regex {
+            find.replace_all(&stem, options.replace.as_str()).into_owned()
+        } else {
+            find.replace_all(&stem, regex::NoExpand(&options.replace)).into_owned()
+        };
+    }
+    if let Some(case) = &options.case {
+        stem = apply_case(&stem, case);
+    }
+
+    let stem = stem.trim();
+    let name = match split_name(&ctx.asset.file_name).1 {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem.to_string(),
+    };
+    validate_file_name(stem)?;
+    validate_file_name(&name)?;
+    Ok(name)
+}
+
+/// Mark names that collide within the batch or with other assets of the same folder.
+/// Names are compared case-insensitively.
+async fn check_name_collisions(
+    pool: &SqlitePool,
+    library_id: &str,
+    assets: &[Asset],
+    items: &mut [RenamePreview],
+) -> Result<(), AppError> {
+    let batch: HashSet<&str> = assets.iter().map(|a| a.id.as_str()).collect();
+
+    let mut taken: HashMap<&str, HashSet<String>> = HashMap::new();
+    for asset in assets {
+        if taken.contains_key(asset.folder_path.as_str()) {
+            continue;
+        }
+        let names = queries::get_folder_file_names(pool, library_id, &asset.folder_path)
+            .await?
+            .into_iter()
+            .filter(|(id, _)| !batch.contains(id.as_str()))
+            .map(|(_, name)| name.to_lowercase())
+            .collect();
+        taken.insert(&asset.folder_path, names);
+    }
+
+    let mut counts: HashMap<(&str, String), usize> = HashMap::new();
+    for (asset, item) in assets.iter().zip(items.iter()) {
+        if item.error.is_none() {
+            *counts
+                .entry((&asset.folder_path, item.new_name.to_lowercase()))
+                .or_default() += 1;
+        }
+    }
+
+    for (asset, item) in assets.iter().zip(items.iter_mut()) {
+        if item.error.is_some() {
+            continue;
+        }
+        let key = item.new_name.to_lowercase();
+        if taken[asset.folder_path.as_str()].contains(&key) {
+            item.error = Some(format!(
+                "An asset named '{}' already exists in {}",
+                item.new_name, asset.folder_path
+            ));
+        } else if counts[&(asset.folder_path.as_str(), key)] > 1 {
+            item.error = Some(format!(
+                "Another asset in {} would also be named '{}'",
+                asset.folder_path, item.new_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Work out the file renames on disk, marking targets that collide with existing files
+/// or with each other. Returns `(source, target)` for every file that changes name.
+fn plan_file_renames(
+    library_root: &std::path::Path,
+    assets: &[Asset],
+    items: &mut [RenamePreview],
+) -> Vec<Option<(PathBuf, PathBuf)>> {
+    let path_key = |path: &std::path::Path| path.to_string_lossy().to_lowercase();
+    let sources: HashSet<String> = assets
+        .iter()
+        .map(|a| path_key(&library_root.join(&a.relative_path)))
+        .collect();
+
+    let plans: Vec<Option<(PathBuf, PathBuf)>> = assets
+        .iter()
+        .zip(items.iter())
+        .map(|(asset, item)| {
+            let source = library_root.join(&asset.relative_path);
+            if item.error.is_some() || !source.exists() {
+                if !source.exists() {
+                    tracing::warn!("File for asset {} is missing: {:?}", asset.id, source);
+                }
+                return None;
+            }
+            let target = source.with_file_name(&item.new_name);
+            (target != source).then_some((source, target))
+        })
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (_, target) in plans.iter().flatten() {
+        *counts.entry(path_key(target)).or_default() += 1;
+    }
+
+    for (plan, item) in plans.iter().zip(items.iter_mut()) {
+        let Some((_, target)) = plan else {
+            continue;
+        };
+        let key = path_key(target);
+        if target.exists() && !sources.contains(&key) {
+            item.error = Some(format!("A file named '{}' already exists on disk", item.new_name));
+        } else if counts[&key] > 1 {
+            item.error = Some(format!(
+                "Another file would also be named '{}' on disk",
+                item.new_name
+            ));
+        }
+    }
+    plans
+}
+
+/// Rename files through temporary names first so that names can be swapped within the batch.
+/// All moves are recorded in `moved` so they can be undone.
+fn rename_files(
+    renames: &[(String, PathBuf, PathBuf)],
+    moved: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<(), AppError> {
+    let mut staged = Vec::new();
+    for (asset_id, source, target) in renames {
+        let temp = source.with_file_name(format!(".{}.rename", asset_id));
+        std::fs::rename(source, &temp)?;
+        moved.push((source.clone(), temp.clone()));
+        staged.push((temp, target));
+    }
+    for (temp, target) in staged {
+        std::fs::rename(&temp, target)?;
+        moved.push((temp, target.clone()));
+    }
+    Ok(())
+}
+
+/// Rename many assets using a template such as `{folder}_{index:03}` or
+/// `{tag:character|snake}_{width}x{height}`, optionally followed by a find/replace and a
+/// case transform. With `dry_run` nothing changes and the preview lists old and new names,
+/// with an error for every asset that cannot be renamed (e.g. because of a name collision).
+/// Otherwise the renames are only applied if every asset can be renamed.
+#[tauri::command]
+pub async fn batch_rename(
+    asset_ids: Vec<String>,
+    options: BatchRenameOptions,
+    dry_run: bool,
+    pool: State<'_, SqlitePool>,
+) -> Result<BatchRenameResult, AppError> {
+    let segments = parse_template(&options.template)?;
+    if let Some(case) = &options.case {
+        validate_case(case)?;
+    }
+    let find = find_pattern(&options)?;
+
+    let mut seen = HashSet::new();
+    let mut assets = Vec::new();
+    for id in asset_ids.iter().filter(|id| seen.insert(id.as_str())) {
+        assets.push(queries::get_asset(&pool, id).await?);
+    }
+    let Some(library_id) = assets.first().map(|a| a.library_id.clone()) else {
+        return Ok(BatchRenameResult {
+            items: Vec::new(),
+            applied: false,
+        });
+    };
+    if assets.iter().any(|a| a.library_id != library_id) {
+        return Err(AppError::InvalidInput(
+            "Assets from different libraries cannot be renamed together".to_string(),
+        ));
+    }
+
+    let mut items = Vec::new();
+    for (i, asset) in assets.iter().enumerate() {
+        let tags = queries::get_asset_tags(&pool, &asset.id).await?;
+        let ai_name = queries::get_ai_name_suggestion(&pool, &asset.id).await?;
+        let ctx = RenameContext {
+            asset,
+            index: options.start_index + i as u64,
+            tags: &tags,
+            ai_name: ai_name.as_deref(),
+        };
+        let (new_name, error) = match render_new_name(&segments, find.as_ref(), &options, &ctx) {
+            Ok(name) => (name, None),
+            Err(e) => (asset.file_name.clone(), Some(e)),
+        };
+        items.push(RenamePreview {
+            asset_id: asset.id.clone(),
+            old_name: asset.file_name.clone(),
+            new_name,
+            error,
+        });
+    }
+
+    check_name_collisions(&pool, &library_id, &assets, &mut items).await?;
+
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root = PathBuf::from(&library.root_path);
+    let plans = if options.rename_files {
+        plan_file_renames(&library_root, &assets, &mut items)
+    } else {
+        vec![None; assets.len()]
+    };
+
+    let failed = items.iter().filter(|i| i.error.is_some()).count();
+    if dry_run {
+        return Ok(BatchRenameResult {
+            items,
+            applied: false,
+        });
+    }
+    if failed > 0 {
+        return Err(AppError::InvalidInput(format!(
+            "{} of {} assets cannot be renamed",
+            failed,
+            items.len()
+        )));
+    }
+
+    let file_renames: Vec<(String, PathBuf, PathBuf)> = assets
+        .iter()
+        .zip(&plans)
+        .filter_map(|(asset, plan)| {
+            plan.clone().map(|(source, target)| (asset.id.clone(), source, target))
+        })
+        .collect();
+    let mut moved = Vec::new();
+    if let Err(e) = rename_files(&file_renames, &mut moved) {
+        undo_file_moves(&moved);
+        return Err(e);
+    }
+
+    let updates: Vec<(String, String, String)> = assets
+        .iter()
+        .zip(&items)
+        .zip(&plans)
+        .map(|((asset, item), plan)| {
+            let relative_path = match plan {
+                Some((_, target)) => file_ops::relative_to_library(target, &library_root),
+                None => asset.relative_path.clone(),
+            };
+            (asset.id.clone(), item.new_name.clone(), relative_path)
+        })
+        .collect();
+    if let Err(e) = queries::rename_assets(&pool, &updates).await {
+        undo_file_moves(&moved);
+        return Err(e.into());
+    }
+
+    Ok(BatchRenameResult {
+        items,
+        applied: true,
+    })
+}
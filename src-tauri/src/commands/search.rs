@@ -1,39 +1,124 @@
 use sqlx::SqlitePool;
 use tauri::State;
 
-use crate::db::{models::{Asset, PaginatedAssets}, queries};
+use crate::db::{models::{AssetFilter, ColorMatch, PaginatedAssets}, queries};
+use crate::commands::asset::{normalize_filter, parse_cursor};
 use crate::error::AppError;
+use crate::storage::palette;
 
+/// Full-text search, newest first unless `sort_by` is given. Paginates like `get_assets`.
+/// With `include_tag_descendants` the tag filter also matches tags below `tag_ids`.
 #[tauri::command]
 pub async fn search_keyword(
     library_id: String,
     query: String,
     tag_ids: Option<Vec<String>>,
+    include_tag_descendants: Option<bool>,
     file_type: Option<String>,
+    filter: Option<AssetFilter>,
     page: u32,
     page_size: u32,
+    sort_by: Option<String>,
+    sort_order: Option<String>,
+    cursor: Option<String>,
     pool: State<'_, SqlitePool>,
 ) -> Result<PaginatedAssets, AppError> {
+    let filter = normalize_filter(&pool, filter.unwrap_or_default()).await?;
+    let cursor = parse_cursor(cursor.as_deref())?;
     let result = queries::search_keyword(
         &pool,
         &library_id,
         &query,
         tag_ids.as_deref(),
+        include_tag_descendants.unwrap_or(false),
         file_type.as_deref(),
+        &filter,
         page,
         page_size,
+        sort_by.as_deref().unwrap_or("date"),
+        sort_order.as_deref().unwrap_or("desc"),
+        cursor.as_ref(),
     )
     .await?;
     Ok(result)
 }
 
+/// Assets with any or all of the tags, paginated like `get_assets`. With
+/// `include_descendants` a parent tag also matches assets tagged with any tag below it.
 #[tauri::command]
 pub async fn search_by_tags(
     library_id: String,
     tag_ids: Vec<String>,
     match_all: bool,
+    include_descendants: Option<bool>,
+    filter: Option<AssetFilter>,
+    page: u32,
+    page_size: u32,
+    sort_by: Option<String>,
+    sort_order: Option<String>,
+    cursor: Option<String>,
     pool: State<'_, SqlitePool>,
-) -> Result<Vec<Asset>, AppError> {
-    let results = queries::search_by_tags(&pool, &library_id, &tag_ids, match_all).await?;
+) -> Result<PaginatedAssets, AppError> {
+    let filter = normalize_filter(&pool, filter.unwrap_or_default()).await?;
+    let cursor = parse_cursor(cursor.as_deref())?;
+    let results = queries::search_by_tags(
+        &pool,
+        &library_id,
+        &tag_ids,
+        match_all,
+        include_descendants.unwrap_or(false),
+        &filter,
+        page,
+        page_size,
+        sort_by.as_deref().unwrap_or("date"),
+        sort_order.as_deref().unwrap_or("desc"),
+        cursor.as_ref(),
+    )
+    .await?;
+    Ok(results)
+}
+
+/// Find images with a dominant color close to `color` (`#rrggbb`). `max_distance` is the
+/// CIELAB ΔE allowed (default 20) and `min_coverage` the share of the image that matching
+/// colors must cover (default 0.1).
+#[tauri::command]
+pub async fn search_by_color(
+    library_id: String,
+    color: String,
+    max_distance: Option<f64>,
+    min_coverage: Option<f64>,
+    limit: Option<u32>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<ColorMatch>, AppError> {
+    let rgb = palette::parse_hex_color(&color)
+        .ok_or_else(|| AppError::InvalidInput(format!("Invalid color: {}", color)))?;
+    let max_distance = max_distance.unwrap_or(20.0);
+    let min_coverage = min_coverage.unwrap_or(0.1);
+    if max_distance.is_nan() || max_distance <= 0.0 || !(0.0..=1.0).contains(&min_coverage) {
+        return Err(AppError::InvalidInput(
+            "Distance must be positive and coverage between 0 and 1".to_string(),
+        ));
+    }
+
+    let lab = palette::rgb_to_lab(rgb);
+    let matches = queries::search_by_color(
+        &pool,
+        &library_id,
+        (lab.l, lab.a, lab.b),
+        max_distance,
+        min_coverage,
+        limit.unwrap_or(200),
+    )
+    .await?;
+
+    let mut results = Vec::new();
+    for (asset_id, distance, coverage) in matches {
+        let asset = queries::get_asset(&pool, &asset_id).await?;
+        results.push(ColorMatch {
+            asset,
+            distance,
+            coverage,
+        });
+    }
     Ok(results)
 }
@@ -1,20 +1,88 @@
 use sqlx::SqlitePool;
+use std::collections::HashSet;
 use tauri::State;
 
 use crate::db::{
-    models::{Tag, TagWithCount},
+    models::{Tag, TagAlias, TagWithCount},
     queries,
 };
+use crate::commands::asset::{load_assets, single_library};
 use crate::error::AppError;
 
+fn validate_tag_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Tag name cannot be empty".to_string()));
+    }
+    if name.contains('/') {
+        return Err(AppError::InvalidInput(
+            "Tag names cannot contain '/'; create child tags instead".to_string(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Fail if a tag other than `tag_id` already has `path` (ignoring case), or `path` is an alias
+async fn ensure_unique_path(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+    tag_id: Option<&str>,
+) -> Result<(), AppError> {
+    if let Some(existing) = queries::get_tag_by_path(pool, library_id, path).await? {
+        if Some(existing.id.as_str()) != tag_id {
+            return Err(AppError::InvalidInput(format!("A tag '{}' already exists", path)));
+        }
+    }
+    if let Some(existing) = queries::get_tag_by_alias(pool, library_id, path).await? {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' is an alias of the tag '{}'",
+            path, existing.path
+        )));
+    }
+    Ok(())
+}
+
+/// Load the parent tag for a tag of `library_id`
+async fn get_parent_tag(
+    pool: &SqlitePool,
+    library_id: &str,
+    parent_id: Option<&str>,
+) -> Result<Option<Tag>, AppError> {
+    let Some(parent_id) = parent_id else {
+        return Ok(None);
+    };
+    let parent = queries::get_tag(pool, parent_id).await?;
+    if parent.library_id != library_id {
+        return Err(AppError::InvalidInput(
+            "Parent tag belongs to a different library".to_string(),
+        ));
+    }
+    Ok(Some(parent))
+}
+
+/// Path of a tag named `name` below `parent`
+fn child_path(parent: Option<&Tag>, name: &str) -> String {
+    match parent {
+        Some(parent) => format!("{}/{}", parent.path, name),
+        None => name.to_string(),
+    }
+}
+
+/// Create a tag, optionally as a child of `parent_id`
 #[tauri::command]
 pub async fn create_tag(
     library_id: String,
     name: String,
     color: Option<String>,
     category: Option<String>,
+    parent_id: Option<String>,
     pool: State<'_, SqlitePool>,
 ) -> Result<Tag, AppError> {
+    let name = validate_tag_name(&name)?;
+    let parent = get_parent_tag(&pool, &library_id, parent_id.as_deref()).await?;
+    ensure_unique_path(&pool, &library_id, &child_path(parent.as_ref(), &name), None).await?;
+
     let tag = queries::create_tag(
         &pool,
         &library_id,
@@ -22,11 +90,14 @@ pub async fn create_tag(
         &color.unwrap_or_else(|| "#808080".to_string()),
         &category.unwrap_or_default(),
         false,
+        parent_id.as_deref(),
     )
     .await?;
     Ok(tag)
 }
 
+/// List a library's tags ordered by path, with asset counts that include the tags below
+/// each tag in `total_count`
 #[tauri::command]
 pub async fn list_tags(
     library_id: String,
@@ -43,16 +114,154 @@ pub async fn rename_tag(
     new_name: String,
     pool: State<'_, SqlitePool>,
 ) -> Result<(), AppError> {
+    let new_name = validate_tag_name(&new_name)?;
+    let tag = queries::get_tag(&pool, &id).await?;
+    let parent = get_parent_tag(&pool, &tag.library_id, tag.parent_id.as_deref()).await?;
+    ensure_unique_path(&pool, &tag.library_id, &child_path(parent.as_ref(), &new_name), Some(&id))
+        .await?;
     queries::rename_tag(&pool, &id, &new_name).await?;
     Ok(())
 }
 
+/// Move a tag with all tags below it under `parent_id`, or to the top level with `None`
+#[tauri::command]
+pub async fn move_tag(
+    id: String,
+    parent_id: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Tag, AppError> {
+    let tag = queries::get_tag(&pool, &id).await?;
+    let parent = get_parent_tag(&pool, &tag.library_id, parent_id.as_deref()).await?;
+    if let Some(parent) = &parent {
+        if parent.id == tag.id || parent.path.starts_with(&format!("{}/", tag.path)) {
+            return Err(AppError::InvalidInput(
+                "A tag cannot be moved below itself".to_string(),
+            ));
+        }
+    }
+    ensure_unique_path(&pool, &tag.library_id, &child_path(parent.as_ref(), &tag.name), Some(&id))
+        .await?;
+
+    queries::move_tag(&pool, &tag, parent.as_ref()).await?;
+    let moved = queries::get_tag(&pool, &id).await?;
+    Ok(moved)
+}
+
+/// Delete a tag and all tags below it
 #[tauri::command]
 pub async fn delete_tag(id: String, pool: State<'_, SqlitePool>) -> Result<(), AppError> {
     queries::delete_tag(&pool, &id).await?;
     Ok(())
 }
 
+/// Merge tags into `target_id`: assets tagged with a source get the target instead, tags
+/// below a source move below the target and the sources are deleted. With `keep_aliases`
+/// (the default) the source paths become aliases, so later imports and searches using
+/// the old names land on the target.
+#[tauri::command]
+pub async fn merge_tags(
+    source_ids: Vec<String>,
+    target_id: String,
+    keep_aliases: Option<bool>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Tag, AppError> {
+    let mut seen = HashSet::new();
+    let source_ids: Vec<String> = source_ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+    if source_ids.is_empty() {
+        return Err(AppError::InvalidInput("No tags to merge".to_string()));
+    }
+    if source_ids.contains(&target_id) {
+        return Err(AppError::InvalidInput("A tag cannot be merged into itself".to_string()));
+    }
+
+    let target = queries::get_tag(&pool, &target_id).await?;
+    let sources = queries::get_tags_by_ids(&pool, &source_ids).await?;
+    if let Some(missing) = source_ids.iter().find(|id| !sources.iter().any(|t| &t.id == *id)) {
+        return Err(AppError::NotFound(format!("Tag {}", missing)));
+    }
+    if sources.iter().any(|t| t.library_id != target.library_id) {
+        return Err(AppError::InvalidInput("Tag belongs to a different library".to_string()));
+    }
+    let is_below = |tag: &Tag, ancestor: &Tag| tag.path.starts_with(&format!("{}/", ancestor.path));
+    if sources.iter().any(|s| is_below(&target, s)) {
+        return Err(AppError::InvalidInput(
+            "A tag cannot be merged into a tag below it".to_string(),
+        ));
+    }
+    if sources.iter().any(|a| sources.iter().any(|b| is_below(a, b))) {
+        return Err(AppError::InvalidInput(
+            "Merge a tag or a tag below it, not both".to_string(),
+        ));
+    }
+
+    // Children move below the target, so their new paths must be free
+    let mut moved_paths = HashSet::new();
+    for source in &sources {
+        for child in queries::get_child_tags(&pool, &source.id).await? {
+            let path = child_path(Some(&target), &child.name);
+            if !moved_paths.insert(path.clone()) {
+                return Err(AppError::InvalidInput(format!(
+                    "More than one tag would move to '{}'",
+                    path
+                )));
+            }
+            ensure_unique_path(&pool, &target.library_id, &path, None).await?;
+        }
+    }
+
+    queries::merge_tags(&pool, &target, &sources, keep_aliases.unwrap_or(true)).await?;
+    tracing::info!("Merged {} tags into {}", sources.len(), target.path);
+    Ok(target)
+}
+
+/// Make `alias` resolve to a tag when importing, auto-tagging and searching, e.g. a
+/// synonym or a translation such as "树" for "tree"
+#[tauri::command]
+pub async fn add_tag_alias(
+    tag_id: String,
+    alias: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<TagAlias, AppError> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(AppError::InvalidInput("Alias cannot be empty".to_string()));
+    }
+    let tag = queries::get_tag(&pool, &tag_id).await?;
+    if let Some(existing) = queries::get_tag_by_path(&pool, &tag.library_id, alias).await? {
+        return Err(AppError::InvalidInput(format!(
+            "'{}' is already a tag; merge it instead",
+            existing.path
+        )));
+    }
+
+    queries::save_tag_alias(&pool, &tag.library_id, alias, &tag.id).await?;
+    queries::get_tag_aliases(&pool, &tag.library_id)
+        .await?
+        .into_iter()
+        .find(|a| a.tag_id == tag.id && a.alias.eq_ignore_ascii_case(alias))
+        .ok_or_else(|| AppError::NotFound(format!("Alias {}", alias)))
+}
+
+#[tauri::command]
+pub async fn remove_tag_alias(
+    library_id: String,
+    alias: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    queries::delete_tag_alias(&pool, &library_id, alias.trim()).await?;
+    Ok(())
+}
+
+/// List a library's aliases ordered by the path of their tag
+#[tauri::command]
+pub async fn get_tag_aliases(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<TagAlias>, AppError> {
+    let aliases = queries::get_tag_aliases(&pool, &library_id).await?;
+    Ok(aliases)
+}
+
 #[tauri::command]
 pub async fn assign_tags(
     asset_id: String,
@@ -73,6 +282,49 @@ pub async fn remove_tags(
     Ok(())
 }
 
+/// Check that all assets exist and that the tags belong to their library
+pub(crate) async fn validate_bulk_tagging(
+    pool: &SqlitePool,
+    asset_ids: &[String],
+    tag_ids: &[String],
+) -> Result<(), AppError> {
+    let assets = load_assets(pool, asset_ids).await?;
+    let library_id = single_library(&assets)?;
+    let tags = queries::get_tags_by_ids(pool, tag_ids).await?;
+    if let Some(missing) = tag_ids.iter().find(|id| !tags.iter().any(|t| &t.id == *id)) {
+        return Err(AppError::NotFound(format!("Tag {}", missing)));
+    }
+    if library_id.is_some_and(|library_id| tags.iter().any(|t| t.library_id != library_id)) {
+        return Err(AppError::InvalidInput("Tag belongs to a different library".to_string()));
+    }
+    Ok(())
+}
+
+/// Give all assets all tags in a single statement. Returns the number of new assignments.
+#[tauri::command]
+pub async fn bulk_assign_tags(
+    asset_ids: Vec<String>,
+    tag_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<u64, AppError> {
+    validate_bulk_tagging(&pool, &asset_ids, &tag_ids).await?;
+    let assigned = queries::assign_tags_to_assets(&pool, &asset_ids, &tag_ids).await?;
+    Ok(assigned)
+}
+
+/// Remove the tags from all assets in a single statement. Returns the number of removed
+/// assignments.
+#[tauri::command]
+pub async fn bulk_remove_tags(
+    asset_ids: Vec<String>,
+    tag_ids: Vec<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<u64, AppError> {
+    validate_bulk_tagging(&pool, &asset_ids, &tag_ids).await?;
+    let removed = queries::remove_tags_from_assets(&pool, &asset_ids, &tag_ids).await?;
+    Ok(removed)
+}
+
 #[tauri::command]
 pub async fn get_asset_tags(
     asset_id: String,
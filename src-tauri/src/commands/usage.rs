@@ -0,0 +1,201 @@
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::State;
+use uuid::Uuid;
+
+use crate::db::{
+    models::{AssetUsage, UsageProject, UsageScanResult},
+    queries,
+};
+use crate::error::AppError;
+use crate::storage::{file_ops, image_hash};
+
+/// Directories that never hold shipped assets
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "Temp", "Logs"];
+
+/// Image extensions compared by perceptual hash
+const HASHED_IMAGE_EXTS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp", "tga", "tiff", "aseprite", "ase"];
+
+/// Default number of differing bits for two images to count as the same
+const DEFAULT_MAX_HASH_DISTANCE: u32 = 4;
+
+/// All files below `dir`, skipping hidden and build directories
+fn collect_project_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        match entry.file_type() {
+            Ok(t) if t.is_dir() && !SKIPPED_DIRS.contains(&name.as_str()) => {
+                collect_project_files(&path, files)
+            }
+            Ok(t) if t.is_file() => files.push(path),
+            _ => {}
+        }
+    }
+}
+
+fn is_hashed_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| HASHED_IMAGE_EXTS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Perceptual hashes of the library's image assets, computing the ones not cached yet
+async fn library_image_hashes(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<(String, u64)>, AppError> {
+    let library = queries::get_library(pool, library_id).await?;
+    let library_root = Path::new(&library.root_path);
+    let cached: HashMap<String, (String, i64)> = queries::get_perceptual_hashes(pool, library_id)
+        .await?
+        .into_iter()
+        .map(|(asset_id, file_hash, dhash)| (asset_id, (file_hash, dhash)))
+        .collect();
+
+    let mut hashes = Vec::new();
+    for asset in queries::get_library_image_assets(pool, library_id).await? {
+        match cached.get(&asset.id) {
+            Some((file_hash, dhash)) if *file_hash == asset.file_hash => {
+                hashes.push((asset.id, *dhash as u64));
+            }
+            _ => match image_hash::difference_hash(&library_root.join(&asset.relative_path)) {
+                Ok(dhash) => {
+                    queries::save_perceptual_hash(pool, &asset.id, &asset.file_hash, dhash as i64).await?;
+                    hashes.push((asset.id, dhash));
+                }
+                Err(e) => tracing::warn!("Failed to hash image asset {}: {}", asset.id, e),
+            },
+        }
+    }
+    // Flat images would match every other flat image
+    hashes.retain(|(_, dhash)| *dhash != image_hash::FLAT_HASH);
+    Ok(hashes)
+}
+
+/// Scan a game project directory for files that are library assets, by content hash and
+/// optionally by perceptual hash for images (catching resized or re-encoded copies).
+/// The matches replace earlier results for the same project.
+#[tauri::command]
+pub async fn scan_project_usage(
+    library_id: String,
+    project_path: String,
+    perceptual: Option<bool>,
+    max_hash_distance: Option<u32>,
+    pool: State<'_, SqlitePool>,
+) -> Result<UsageScanResult, AppError> {
+    let project_root = PathBuf::from(project_path.trim());
+    if !project_root.is_dir() {
+        return Err(AppError::NotFound(format!("Project directory {:?}", project_root)));
+    }
+    let project_key = project_root.to_string_lossy().to_string();
+
+    // Compare resolved paths so symlinks and `..` cannot hide the library inside the project
+    let library = queries::get_library(&pool, &library_id).await?;
+    let library_root =
+        std::fs::canonicalize(&library.root_path).unwrap_or_else(|_| PathBuf::from(&library.root_path));
+    let project_root = std::fs::canonicalize(&project_root).unwrap_or(project_root);
+    if project_root.starts_with(&library_root) {
+        return Err(AppError::InvalidInput(
+            "The project directory is inside the asset library".to_string(),
+        ));
+    }
+
+    let mut files = Vec::new();
+    collect_project_files(&project_root, &mut files);
+    // A library kept inside the project would match every asset against itself
+    files.retain(|file| !file.starts_with(&library_root));
+    tracing::info!("Scanning {} files of {:?} for library assets", files.len(), project_root);
+
+    // Only files with the size of some asset can have the same content
+    let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+    let mut sizes = HashSet::new();
+    for (asset_id, file_size, file_hash) in queries::get_asset_hashes(&pool, &library_id).await? {
+        sizes.insert(file_size);
+        by_hash.entry(file_hash).or_default().push(asset_id);
+    }
+
+    let image_hashes = if perceptual.unwrap_or(false) {
+        library_image_hashes(&pool, &library_id).await?
+    } else {
+        Vec::new()
+    };
+    let max_distance = max_hash_distance.unwrap_or(DEFAULT_MAX_HASH_DISTANCE);
+
+    let mut usages = Vec::new();
+    for file in &files {
+        let relative = file_ops::relative_to_library(file, &project_root);
+        let usage = |asset_id: &str, match_kind: &str| AssetUsage {
+            id: Uuid::new_v4().to_string(),
+            library_id: library_id.clone(),
+            asset_id: asset_id.to_string(),
+            project_path: project_key.clone(),
+            file_path: relative.clone(),
+            match_kind: match_kind.to_string(),
+            scanned_at: String::new(),
+        };
+
+        let size = file.metadata().map(|m| m.len() as i64).unwrap_or(-1);
+        if sizes.contains(&size) {
+            match file_ops::compute_file_hash(file) {
+                Ok(hash) => {
+                    if let Some(asset_ids) = by_hash.get(&hash) {
+                        usages.extend(asset_ids.iter().map(|id| usage(id, "exact")));
+                        continue;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to hash {:?}: {}", file, e),
+            }
+        }
+
+        if !image_hashes.is_empty() && is_hashed_image(file) {
+            match image_hash::difference_hash(file) {
+                Ok(dhash) if dhash != image_hash::FLAT_HASH => usages.extend(
+                    image_hashes
+                        .iter()
+                        .filter(|(_, asset_hash)| image_hash::hamming_distance(dhash, *asset_hash) <= max_distance)
+                        .map(|(id, _)| usage(id, "perceptual")),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    queries::replace_project_usages(&pool, &library_id, &project_key, &usages).await?;
+    let usages = queries::get_project_usages(&pool, &library_id, &project_key).await?;
+    let used_assets = usages.iter().map(|u| u.asset_id.as_str()).collect::<HashSet<_>>().len();
+
+    Ok(UsageScanResult {
+        project_path: project_key,
+        files_scanned: files.len(),
+        used_assets,
+        usages,
+    })
+}
+
+#[tauri::command]
+pub async fn get_usage_projects(
+    library_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<UsageProject>, AppError> {
+    let projects = queries::get_usage_projects(&pool, &library_id).await?;
+    Ok(projects)
+}
+
+/// Forget the scan results of a project
+#[tauri::command]
+pub async fn clear_project_usage(
+    library_id: String,
+    project_path: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    queries::replace_project_usages(&pool, &library_id, project_path.trim(), &[]).await?;
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use sqlx::SqlitePool;
+use std::path::Path;
+use tauri::State;
+use uuid::Uuid;
+
+use crate::commands::asset::{inspect_file, record_file_details, thumbnail_data_url};
+use crate::db::{
+    models::{Asset, AssetVersion},
+    queries,
+};
+use crate::error::AppError;
+use crate::storage::file_ops;
+
+/// Copy a file into an asset's versions directory as `<version_id>.<ext>`.
+/// Returns the relative path from the library root.
+fn keep_version_file(
+    library_root: &Path,
+    asset_id: &str,
+    version_id: &str,
+    file: &Path,
+) -> Result<String, AppError> {
+    let dir = file_ops::versions_dir(library_root, asset_id);
+    std::fs::create_dir_all(&dir)?;
+    let name = match file.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}.{}", version_id, ext),
+        None => version_id.to_string(),
+    };
+    let target = dir.join(name);
+    std::fs::copy(file, &target)?;
+    Ok(file_ops::relative_to_library(&target, library_root))
+}
+
+/// Replace an asset's file with `source`, keeping the current file as a new version.
+/// The asset keeps its ID, tags and description.
+pub(crate) async fn replace_with_file(
+    pool: &SqlitePool,
+    asset: Asset,
+    source: &Path,
+    original_name: String,
+    note: String,
+) -> Result<Asset, AppError> {
+    let library = queries::get_library(pool, &asset.library_id).await?;
+    let library_root = Path::new(&library.root_path);
+    let current_file = library_root.join(&asset.relative_path);
+    if !current_file.exists() {
+        return Err(AppError::NotFound(format!("File for asset {}", asset.id)));
+    }
+
+    // Keep the current file and thumbnail before anything is overwritten
+    let version_id = Uuid::new_v4().to_string();
+    let version_path = keep_version_file(library_root, &asset.id, &version_id, &current_file)?;
+    let version_thumbnail = match &asset.thumbnail_path {
+        Some(thumb_rel) if library_root.join(thumb_rel).exists() => Some(keep_version_file(
+            library_root,
+            &asset.id,
+            &format!("{}_thumb", version_id),
+            &library_root.join(thumb_rel),
+        )?),
+        _ => None,
+    };
+
+    let previous = AssetVersion {
+        id: version_id,
+        asset_id: asset.id.clone(),
+        version_number: 0,
+        relative_path: version_path,
+        original_name: asset.original_name.clone(),
+        file_type: asset.file_type.clone(),
+        mime_type: asset.mime_type.clone(),
+        file_size: asset.file_size,
+        file_hash: asset.file_hash.clone(),
+        width: asset.width,
+        height: asset.height,
+        duration_ms: asset.duration_ms,
+        thumbnail_path: version_thumbnail,
+        note,
+        created_at: String::new(),
+    };
+
+    // The new file goes next to the current one, named after the asset ID
+    let new_ext = source.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let new_name = if new_ext.is_empty() {
+        asset.id.clone()
+    } else {
+        format!("{}.{}", asset.id, new_ext)
+    };
+    let new_file = current_file
+        .parent()
+        .unwrap_or(library_root)
+        .join(new_name);
+
+    let old_thumbnail = asset.thumbnail_path.clone();
+    let file = inspect_file(source, library_root, &asset.id)?;
+    std::fs::copy(source, &new_file)?;
+
+    let file_name = if new_ext.is_empty() {
+        asset.file_name.clone()
+    } else {
+        Path::new(&asset.file_name)
+            .with_extension(new_ext)
+            .to_string_lossy()
+            .to_string()
+    };
+    let replacement = Asset {
+        file_name,
+        original_name,
+        relative_path: file_ops::relative_to_library(&new_file, library_root),
+        file_type: file.file_type.clone(),
+        mime_type: file.mime_type.clone(),
+        file_size: file.file_size,
+        file_hash: file.file_hash.clone(),
+        width: file.width,
+        height: file.height,
+        duration_ms: file.duration_ms,
+        thumbnail_path: file.thumbnail_path.clone(),
+        ..asset
+    };
+
+    let updated = match queries::replace_asset_file(pool, &previous, &replacement).await {
+        Ok(updated) => updated,
+        Err(e) => {
+            // Put the previous file back if the new one landed elsewhere or overwrote it
+            if new_file != current_file {
+                std::fs::remove_file(&new_file).ok();
+            } else {
+                std::fs::copy(library_root.join(&previous.relative_path), &current_file).ok();
+            }
+            if let (Some(kept), Some(thumb_rel)) = (&previous.thumbnail_path, &old_thumbnail) {
+                std::fs::copy(library_root.join(kept), library_root.join(thumb_rel)).ok();
+            }
+            return Err(e.into());
+        }
+    };
+
+    if new_file != current_file {
+        std::fs::remove_file(&current_file)?;
+    }
+
+    record_file_details(pool, &updated, &new_file, &file).await?;
+    Ok(updated)
+}
+
+/// Replace an asset's file (e.g. with an updated sprite), keeping the previous file as a version
+#[tauri::command]
+pub async fn replace_asset_file(
+    id: String,
+    file_path: String,
+    note: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Asset, AppError> {
+    let source = Path::new(&file_path);
+    if !source.is_file() {
+        return Err(AppError::InvalidInput(format!("Not a file: {}", file_path)));
+    }
+    let asset = queries::get_asset(&pool, &id).await?;
+    let original_name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    replace_with_file(&pool, asset, source, original_name, note.unwrap_or_default()).await
+}
+
+/// List the previous files of an asset, newest first
+#[tauri::command]
+pub async fn list_asset_versions(
+    asset_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<AssetVersion>, AppError> {
+    let versions = queries::get_asset_versions(&pool, &asset_id).await?;
+    Ok(versions)
+}
+
+/// Get a version's thumbnail as base64 encoded data URL
+#[tauri::command]
+pub async fn get_version_thumbnail_data(
+    version_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Option<String>, AppError> {
+    let version = queries::get_asset_version(&pool, &version_id).await?;
+    let asset = queries::get_asset(&pool, &version.asset_id).await?;
+    let library = queries::get_library(&pool, &asset.library_id).await?;
+    match &version.thumbnail_path {
+        Some(thumb_rel) => {
+            let full_path = Path::new(&library.root_path).join(thumb_rel);
+            Ok(Some(thumbnail_data_url(&full_path)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Get the absolute filesystem path of a version's file (for previewing it)
+#[tauri::command]
+pub async fn get_version_file_path(
+    version_id: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<String, AppError> {
+    let version = queries::get_asset_version(&pool, &version_id).await?;
+    let asset = queries::get_asset(&pool, &version.asset_id).await?;
+    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let full_path = Path::new(&library.root_path).join(&version.relative_path);
+    Ok(full_path.to_string_lossy().to_string())
+}
+
+/// Restore a previous version. The current file is kept as a new version, so reverting can be undone.
+#[tauri::command]
+pub async fn revert_asset_version(
+    version_id: String,
+    note: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Asset, AppError> {
+    let version = queries::get_asset_version(&pool, &version_id).await?;
+    let asset = queries::get_asset(&pool, &version.asset_id).await?;
+    let library = queries::get_library(&pool, &asset.library_id).await?;
+    let source = Path::new(&library.root_path).join(&version.relative_path);
+    if !source.exists() {
+        return Err(AppError::NotFound(format!("File for version {}", version_id)));
+    }
+
+    let note = note.unwrap_or_else(|| format!("Before reverting to version {}", version.version_number));
+    replace_with_file(&pool, asset, &source, version.original_name, note).await
+}
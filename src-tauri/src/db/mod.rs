@@ -30,17 +30,95 @@ pub async fn init_db(app_data_dir: &Path) -> Result<SqlitePool, sqlx::Error> {
     Ok(pool)
 }
 
+/// Migrations are applied once each, in order, and recorded in `schema_migrations`
+const MIGRATIONS: &[(&str, &str)] = &[
+    ("001_initial_schema", include_str!("../../migrations/001_initial_schema.sql")),
+    ("002_asset_format_metadata", include_str!("../../migrations/002_asset_format_metadata.sql")),
+    ("003_asset_links", include_str!("../../migrations/003_asset_links.sql")),
+    ("004_image_metadata", include_str!("../../migrations/004_image_metadata.sql")),
+    ("005_text_content", include_str!("../../migrations/005_text_content.sql")),
+    ("006_folders", include_str!("../../migrations/006_folders.sql")),
+    ("007_asset_versions", include_str!("../../migrations/007_asset_versions.sql")),
+    ("008_asset_derivations", include_str!("../../migrations/008_asset_derivations.sql")),
+    ("009_custom_fields", include_str!("../../migrations/009_custom_fields.sql")),
+    ("010_asset_triage", include_str!("../../migrations/010_asset_triage.sql")),
+    ("011_collections", include_str!("../../migrations/011_collections.sql")),
+    ("012_ai_name_suggestions", include_str!("../../migrations/012_ai_name_suggestions.sql")),
+    ("013_ai_name_review", include_str!("../../migrations/013_ai_name_review.sql")),
+    ("014_asset_comments", include_str!("../../migrations/014_asset_comments.sql")),
+    ("015_asset_licenses", include_str!("../../migrations/015_asset_licenses.sql")),
+    ("016_asset_colors", include_str!("../../migrations/016_asset_colors.sql")),
+    ("017_asset_usages", include_str!("../../migrations/017_asset_usages.sql")),
+    ("018_tag_hierarchy", include_str!("../../migrations/018_tag_hierarchy.sql")),
+    ("019_tag_aliases", include_str!("../../migrations/019_tag_aliases.sql")),
+    ("020_tag_path_nocase", include_str!("../../migrations/020_tag_path_nocase.sql")),
+    ("021_perceptual_hash_alpha", include_str!("../../migrations/021_perceptual_hash_alpha.sql")),
+];
+
 async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    let migration_sql = include_str!("../../migrations/001_initial_schema.sql");
-
-    // Split by semicolons and execute each statement
-    for statement in migration_sql.split(';') {
-        let stmt = statement.trim();
-        if !stmt.is_empty() {
-            // Skip PRAGMA statements in batch (they need to run individually)
-            sqlx::query(stmt).execute(pool).await.ok();
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            name TEXT PRIMARY KEY,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    for (name, migration_sql) in MIGRATIONS {
+        let applied: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM schema_migrations WHERE name = ?")
+                .bind(name)
+                .fetch_optional(pool)
+                .await?;
+        if applied.is_some() {
+            continue;
+        }
+
+        // A migration and its record are applied together, so a failed migration is
+        // rolled back and retried on the next start
+        let mut tx = pool.begin().await?;
+        for stmt in split_statements(migration_sql) {
+            if let Err(e) = sqlx::query(&stmt).execute(&mut *tx).await {
+                tracing::error!("Migration {} failed: {}", name, e);
+                return Err(e);
+            }
         }
+        sqlx::query("INSERT INTO schema_migrations (name) VALUES (?)")
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
     }
 
     Ok(())
 }
+
+/// Split a migration script into statements, dropping `--` comment lines and
+/// keeping trigger bodies (`BEGIN ... END;`) together. PRAGMAs are skipped: they are
+/// set on the connection options and cannot change inside a transaction.
+fn split_statements(sql: &str) -> Vec<String> {
+    let sql: String = sql
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut statements = Vec::new();
+    let mut current = String::new();
+
+    for part in sql.split(';') {
+        current.push_str(part);
+        let trimmed = current.trim();
+        let upper = trimmed.to_uppercase();
+        if upper.contains("CREATE TRIGGER") && !upper.ends_with("END") {
+            current.push(';');
+            continue;
+        }
+        if !trimmed.is_empty() && !upper.starts_with("PRAGMA") {
+            statements.push(trimmed.to_string());
+        }
+        current.clear();
+    }
+
+    statements
+}
@@ -7,6 +7,8 @@ pub struct Library {
     pub root_path: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Apply AI suggested names right away instead of queueing them for review
+    pub auto_apply_ai_names: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -27,6 +29,10 @@ pub struct Asset {
     pub ai_description: String,
     pub thumbnail_path: Option<String>,
     pub folder_path: String,
+    /// 0 (unrated) to 5 stars
+    pub rating: i64,
+    pub color_label: Option<String>,
+    pub is_favorite: bool,
     pub created_at: String,
     pub updated_at: String,
     pub imported_at: String,
@@ -41,9 +47,12 @@ pub struct Tag {
     pub category: String,
     pub is_ai: bool,
     pub created_at: String,
+    pub parent_id: Option<String>,
+    /// Names from the root tag down to this one, e.g. "character/enemy/boss"
+    pub path: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct TagWithCount {
     pub id: String,
     pub library_id: String,
@@ -52,7 +61,23 @@ pub struct TagWithCount {
     pub category: String,
     pub is_ai: bool,
     pub created_at: String,
+    pub parent_id: Option<String>,
+    pub path: String,
+    /// Assets with exactly this tag
     pub asset_count: i64,
+    /// Assets with this tag or any tag below it
+    pub total_count: i64,
+}
+
+/// Another name that resolves to a tag, e.g. "trees" or "树" for "tree"
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TagAlias {
+    pub library_id: String,
+    pub alias: String,
+    pub tag_id: String,
+    pub created_at: String,
+    /// Path of the tag the alias resolves to
+    pub tag_path: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -102,17 +127,662 @@ pub struct PaginatedAssets {
     pub total: i64,
     pub page: u32,
     pub page_size: u32,
+    /// Pass as `cursor` to fetch the page after this one; unset on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Keyset pagination position: the sort value and ID of the last asset of a page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetCursor {
+    pub value: serde_json::Value,
+    pub id: String,
+}
+
+impl AssetCursor {
+    /// Opaque token handed to the frontend
+    pub fn encode(&self) -> String {
+        use base64::Engine;
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        use base64::Engine;
+        let json = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+/// Format-specific metadata parsed at import (e.g. Aseprite frames and tags)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormatMetadata {
+    pub format: String,
+    pub data: serde_json::Value,
+}
+
+/// A file referenced by an asset, linked to the library asset it resolved to
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetLink {
+    pub id: String,
+    pub asset_id: String,
+    pub target_asset_id: Option<String>,
+    pub link_type: String,
+    pub reference_path: String,
+    pub reference_hash: String,
+    pub created_at: String,
+}
+
+/// Technical metadata extracted from image files
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ImageMetadata {
+    pub asset_id: String,
+    pub color_type: String,
+    pub bit_depth: i32,
+    pub has_alpha: bool,
+    /// Whether any pixel is actually not fully opaque
+    pub alpha_used: bool,
+    pub frame_count: i64,
+    pub has_icc_profile: bool,
+    pub icc_profile_size: Option<i64>,
+    pub dpi_x: Option<f64>,
+    pub dpi_y: Option<f64>,
+    /// EXIF fields and PNG text chunks
+    pub text_entries: sqlx::types::Json<std::collections::BTreeMap<String, String>>,
+    pub updated_at: String,
+}
+
+/// Summary of the indexed content of a text, data or shader asset
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TextContentInfo {
+    pub asset_id: String,
+    pub language: String,
+    pub byte_size: i64,
+    pub line_count: i64,
+    /// Whether only the first part of the file was indexed
+    pub truncated: bool,
+    pub updated_at: String,
+}
+
+/// A previous file of an asset, kept when the file was replaced
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetVersion {
+    pub id: String,
+    pub asset_id: String,
+    pub version_number: i64,
+    pub relative_path: String,
+    pub original_name: String,
+    pub file_type: String,
+    pub mime_type: String,
+    pub file_size: i64,
+    pub file_hash: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub thumbnail_path: Option<String>,
+    pub note: String,
+    pub created_at: String,
+}
+
+/// How an asset was produced by a processing operation
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetDerivation {
+    pub id: String,
+    pub asset_id: String,
+    pub operation: String,
+    /// The operation's parameters, as used when re-running it
+    pub params: sqlx::types::Json<serde_json::Value>,
+    pub batch_id: String,
+    /// Position of the asset among the outputs of its batch
+    pub output_index: i64,
+    pub created_at: String,
+}
+
+/// A source of a derived asset. `asset` is `None` once the source has been deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DerivationSource {
+    pub position: i64,
+    pub source_asset_id: Option<String>,
+    pub source_name: String,
+    #[sqlx(skip)]
+    pub asset: Option<Asset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetLineage {
+    pub derivation: AssetDerivation,
+    pub sources: Vec<DerivationSource>,
+}
+
+/// An asset produced from another asset, with the operation that produced it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DerivedAsset {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub asset: Asset,
+    pub operation: String,
+    pub params: sqlx::types::Json<serde_json::Value>,
+}
+
+/// A user-defined field of a library
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CustomField {
+    pub id: String,
+    pub library_id: String,
+    pub name: String,
+    /// "text", "number", "boolean", "enum" or "date"
+    pub field_type: String,
+    /// Allowed values of enum fields
+    pub options: sqlx::types::Json<Vec<String>>,
+    pub position: i64,
+    pub created_at: String,
+}
+
+/// The value of a custom field on an asset. Dates are `YYYY-MM-DD` strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetFieldValue {
+    pub field_id: String,
+    pub name: String,
+    pub field_type: String,
+    pub value: serde_json::Value,
+}
+
+/// A condition on a custom field value.
+/// `op` is one of "eq", "ne", "lt", "lte", "gt", "gte", "contains", "set" or "unset".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldCondition {
+    pub field_id: String,
+    pub op: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+/// Optional filters shared by asset listing and search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetFilter {
+    pub has_alpha: Option<bool>,
+    /// Only assets with (or without) real transparency
+    pub alpha_used: Option<bool>,
+    pub animated: Option<bool>,
+    pub has_icc_profile: Option<bool>,
+    pub color_type: Option<String>,
+    /// Language of text assets, e.g. "glsl" or "json"
+    pub language: Option<String>,
+    /// Conditions on custom field values, all of which must match
+    pub fields: Vec<FieldCondition>,
+    pub min_rating: Option<i64>,
+    pub max_rating: Option<i64>,
+    pub color_label: Option<String>,
+    /// Only favorites (or only non-favorites)
+    pub favorite: Option<bool>,
+    /// License identifier, e.g. "CC-BY-4.0" (case-insensitive)
+    pub license: Option<String>,
+    /// Only assets with (or without) a license set
+    pub has_license: Option<bool>,
+    /// Only assets whose license requires (or does not require) attribution
+    pub requires_attribution: Option<bool>,
+    /// Only assets found (or not found) in a scanned game project
+    pub used: Option<bool>,
+    pub folder_path: Option<String>,
+    /// Also match assets in subfolders of `folder_path`
+    pub include_subfolders: bool,
+    /// Any of these MIME types, e.g. "image/png"
+    pub mime_types: Vec<String>,
+    /// File size in bytes
+    pub min_size: Option<i64>,
+    pub max_size: Option<i64>,
+    pub min_width: Option<i64>,
+    pub max_width: Option<i64>,
+    pub min_height: Option<i64>,
+    pub max_height: Option<i64>,
+    /// Width divided by height
+    pub min_aspect: Option<f64>,
+    pub max_aspect: Option<f64>,
+    /// Date ranges as `YYYY-MM-DD` or `YYYY-MM-DD HH:MM:SS` (UTC); "after" is inclusive,
+    /// "before" exclusive
+    pub imported_after: Option<String>,
+    pub imported_before: Option<String>,
+    pub updated_after: Option<String>,
+    pub updated_before: Option<String>,
+    /// Only assets with (or without) tags
+    pub tagged: Option<bool>,
+    pub has_ai_description: Option<bool>,
+    /// Only assets with (or without) an embedding for semantic search
+    pub has_embedding: Option<bool>,
+}
+
+/// Stored definition of a smart collection, evaluated whenever the collection is listed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartCollectionQuery {
+    /// Keyword query, matched like `search_keyword`
+    pub text: Option<String>,
+    pub tag_ids: Vec<String>,
+    /// Require all tags instead of any
+    pub match_all_tags: bool,
+    pub file_type: Option<String>,
+    pub folder_path: Option<String>,
+    pub include_subfolders: bool,
+    pub min_width: Option<i64>,
+    pub max_width: Option<i64>,
+    pub min_height: Option<i64>,
+    pub max_height: Option<i64>,
+    pub filter: AssetFilter,
+}
+
+/// A manual (hand-picked, ordered) or smart (query based) collection of assets
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Collection {
+    pub id: String,
+    pub library_id: String,
+    pub name: String,
+    pub description: String,
+    /// "manual" or "smart"
+    pub kind: String,
+    /// Query of smart collections
+    pub query: Option<sqlx::types::Json<SmartCollectionQuery>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionInfo {
+    #[serde(flatten)]
+    pub collection: Collection,
+    pub asset_count: i64,
+}
+
+/// Options of a batch rename. The template is rendered per asset, then the find/replace
+/// and case transform are applied; the original extension is kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchRenameOptions {
+    /// e.g. `{folder}_{index:03}` or `{tag:character|snake}_{width}x{height}`
+    pub template: String,
+    /// Value of `{index}` for the first asset
+    pub start_index: u64,
+    /// Case transform of the whole name: lower, upper, title, snake, kebab, camel or pascal
+    pub case: Option<String>,
+    pub find: Option<String>,
+    pub replace: String,
+    /// Treat `find` as a regular expression (`replace` may use `$1`)
+    pub regex: bool,
+    /// Also rename the files on disk
+    pub rename_files: bool,
+}
+
+impl Default for BatchRenameOptions {
+    fn default() -> Self {
+        Self {
+            template: "{name}".to_string(),
+            start_index: 1,
+            case: None,
+            find: None,
+            replace: String::new(),
+            regex: false,
+            rename_files: false,
+        }
+    }
+}
+
+/// Old and new name of one asset in a batch rename. `error` is set if the asset cannot be renamed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamePreview {
+    pub asset_id: String,
+    pub old_name: String,
+    pub new_name: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRenameResult {
+    pub items: Vec<RenamePreview>,
+    /// Whether the renames were applied (false for dry runs)
+    pub applied: bool,
+}
+
+/// A file of a game project that matches a library asset
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetUsage {
+    pub id: String,
+    pub library_id: String,
+    pub asset_id: String,
+    pub project_path: String,
+    /// Path of the matched file relative to the project
+    pub file_path: String,
+    /// "exact" (same content) or "perceptual" (similar image)
+    pub match_kind: String,
+    pub scanned_at: String,
+}
+
+/// A scanned game project with the number of library assets it uses
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UsageProject {
+    pub project_path: String,
+    pub asset_count: i64,
+    pub file_count: i64,
+    pub scanned_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageScanResult {
+    pub project_path: String,
+    pub files_scanned: usize,
+    pub used_assets: usize,
+    pub usages: Vec<AssetUsage>,
+}
+
+/// A dominant color of an image, in CIELAB with its sRGB hex value
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PaletteColor {
+    pub hex: String,
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+    /// Share of the visible pixels, 0-1
+    pub coverage: f64,
+}
+
+/// An asset matching a color search. `distance` is the CIELAB ΔE of its closest palette
+/// color and `coverage` the share of the image covered by matching colors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorMatch {
+    pub asset: Asset,
+    pub distance: f64,
+    pub coverage: f64,
+}
+
+/// License and attribution of an asset
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetLicense {
+    pub asset_id: String,
+    /// License identifier, e.g. "CC-BY-4.0", "CC0-1.0", "purchased" or "in-house"
+    pub license: String,
+    pub author: String,
+    pub source_url: String,
+    /// Credit line to use instead of one built from the author and license
+    pub attribution: String,
+    pub requires_attribution: bool,
+    pub updated_at: String,
+}
+
+/// Changes to the license of assets. Unset fields are kept; empty strings clear them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LicenseUpdate {
+    pub license: Option<String>,
+    pub author: Option<String>,
+    pub source_url: Option<String>,
+    pub attribution: Option<String>,
+    /// Overrides whether attribution is required, which is otherwise derived from the license
+    pub requires_attribution: Option<bool>,
+}
+
+/// A license used in a library, with the number of assets under it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LicenseUsage {
+    pub license: String,
+    pub requires_attribution: bool,
+    pub asset_count: i64,
+}
+
+/// Assets to export: an explicit list, a folder, a collection, a keyword search or an
+/// asset query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportSelection {
+    Assets {
+        asset_ids: Vec<String>,
+    },
+    Folder {
+        library_id: String,
+        folder_path: String,
+        #[serde(default)]
+        include_subfolders: bool,
+    },
+    Collection {
+        collection_id: String,
+    },
+    Search {
+        library_id: String,
+        query: String,
+    },
+    Query {
+        library_id: String,
+        query: SmartCollectionQuery,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Directory the files are copied to
+    pub destination: String,
+    /// Recreate the folder structure of the library below the destination
+    pub keep_folders: bool,
+    /// What to do when a file already exists: skip, overwrite or rename
+    pub on_conflict: String,
+    /// Convert images to png, jpeg or webp
+    pub image_format: Option<String>,
+    /// Shrink images to fit, keeping the aspect ratio
+    pub max_width: Option<u32>,
+    pub max_height: Option<u32>,
+    /// JPEG quality, 1-100
+    pub quality: u8,
+    /// Re-encode images so that embedded metadata (EXIF, text chunks) is dropped
+    pub strip_metadata: bool,
+    /// Write `export_manifest.json` describing the export into the destination
+    pub write_manifest: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            destination: String::new(),
+            keep_folders: false,
+            on_conflict: "rename".to_string(),
+            image_format: None,
+            max_width: None,
+            max_height: None,
+            quality: 90,
+            strip_metadata: false,
+            write_manifest: true,
+        }
+    }
+}
+
+/// One asset of an export. `path` is relative to the destination and unset if the
+/// asset was skipped or failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedFile {
+    pub asset_id: String,
+    pub file_name: String,
+    pub folder_path: String,
+    pub tags: Vec<String>,
+    /// exported, skipped or failed
+    pub status: String,
+    pub path: Option<String>,
+    pub file_size: Option<u64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Whether the image was re-encoded (converted, resized or stripped)
+    pub converted: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub destination: String,
+    pub files: Vec<ExportedFile>,
+    pub exported: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub manifest_path: Option<String>,
+}
+
+/// Operation applied to every asset matching a query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BulkOperation {
+    Tag {
+        tag_ids: Vec<String>,
+    },
+    Untag {
+        tag_ids: Vec<String>,
+    },
+    Move {
+        target_folder: String,
+    },
+    Delete,
+    /// AI-tag the matching images
+    AiTag {
+        #[serde(default)]
+        apply_name: Option<bool>,
+    },
+    /// Save compressed copies of the matching images
+    Compress {
+        max_width: Option<u32>,
+        max_height: Option<u32>,
+        quality: u8,
+        /// "jpeg" or "png"
+        output_format: String,
+        #[serde(default)]
+        suffix: String,
+    },
+    Export {
+        options: ExportOptions,
+    },
+}
+
+/// An asset a bulk operation failed for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkFailure {
+    pub asset_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkQueryResult {
+    /// Number of assets the query matched (and the operation applies to)
+    pub matched: usize,
+    /// Whether the operation ran (false for dry runs)
+    pub applied: bool,
+    /// Assets that AI tagging or compression failed for; the others are still processed
+    pub failures: Vec<BulkFailure>,
+    /// Result of export operations
+    pub export: Option<ExportResult>,
+}
+
+/// A review comment on an asset. Replies have a `parent_id`. The anchor is a point
+/// (`anchor_x`, `anchor_y`) or rectangle on an image in pixels, or a time in audio and video.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetComment {
+    pub id: String,
+    pub asset_id: String,
+    pub parent_id: Option<String>,
+    pub author: String,
+    pub body: String,
+    pub anchor_x: Option<f64>,
+    pub anchor_y: Option<f64>,
+    pub anchor_width: Option<f64>,
+    pub anchor_height: Option<f64>,
+    pub anchor_time_ms: Option<i64>,
+    pub resolved: bool,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Where a comment points on its asset. Width and height make the point a rectangle.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommentAnchor {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub time_ms: Option<i64>,
+}
+
+/// An asset with its comments that match a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentSearchResult {
+    pub asset: Asset,
+    pub comments: Vec<AssetComment>,
+}
+
+/// A pending AI name suggestion, with the asset's current name
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct NameSuggestion {
+    pub asset_id: String,
+    pub file_name: String,
+    pub folder_path: String,
+    pub suggested_name: String,
+    pub created_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetDetail {
     pub asset: Asset,
     pub tags: Vec<Tag>,
+    pub format_metadata: Option<FormatMetadata>,
+    pub links: Vec<AssetLink>,
+    pub image_metadata: Option<ImageMetadata>,
+    pub text_content: Option<TextContentInfo>,
+    /// Previous files, newest first
+    pub versions: Vec<AssetVersion>,
+    /// Values of the library's custom fields that are set on this asset
+    pub fields: Vec<AssetFieldValue>,
+    /// Manual collections containing this asset
+    pub collections: Vec<Collection>,
+    /// Review comments and replies, oldest first
+    pub comments: Vec<AssetComment>,
+    pub license: Option<AssetLicense>,
+    /// Dominant colors of images, most common first
+    pub palette: Vec<PaletteColor>,
+    /// Where the asset is used in scanned game projects
+    pub usages: Vec<AssetUsage>,
+    /// How the asset was produced, if it is a processing output
+    pub derived_from: Option<AssetLineage>,
+    pub derivatives: Vec<DerivedAsset>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A library folder. Paths are canonical: `/` for the root, otherwise `/a/b` without a trailing slash.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Folder {
+    pub id: String,
+    pub library_id: String,
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub path: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub description: String,
+    /// Tags assigned to assets imported or moved into this folder
+    pub default_tag_ids: sqlx::types::Json<Vec<String>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct FolderInfo {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub folder: Folder,
+    /// Assets directly in this folder
+    pub asset_count: i64,
+    /// Assets in this folder and all subfolders
+    pub total_asset_count: i64,
+}
+
+/// What deleting a folder would remove, shown to the user for confirmation
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FolderTreeSummary {
     pub path: String,
-    pub name: String,
+    /// The folder itself and all subfolders
+    pub folder_count: i64,
     pub asset_count: i64,
+    pub total_size: i64,
 }
@@ -1,8 +1,253 @@
-use sqlx::SqlitePool;
+use sqlx::{FromRow, Row, SqlitePool};
 use uuid::Uuid;
 
 use super::models::*;
 
+/// Bind value for queries with a dynamically built WHERE clause
+#[derive(Clone)]
+enum SqlValue {
+    Text(String),
+    Int(i64),
+    Real(f64),
+    Null,
+}
+
+/// Bind a list of `SqlValue`s, in order, to any sqlx query type
+macro_rules! bind_values {
+    ($query:expr, $values:expr) => {{
+        let mut query = $query;
+        for value in $values {
+            query = match value {
+                SqlValue::Text(v) => query.bind(v.clone()),
+                SqlValue::Int(v) => query.bind(*v),
+                SqlValue::Real(v) => query.bind(*v),
+                SqlValue::Null => query.bind(None::<String>),
+            };
+        }
+        query
+    }};
+}
+
+/// Append the conditions of an `AssetFilter`, matching assets by `id_column`
+fn push_asset_filter(
+    filter: &AssetFilter,
+    id_column: &str,
+    conditions: &mut Vec<String>,
+    binds: &mut Vec<SqlValue>,
+) {
+    let mut image_conditions = Vec::new();
+    if let Some(v) = filter.has_alpha {
+        image_conditions.push("has_alpha = ?");
+        binds.push(SqlValue::Int(v as i64));
+    }
+    if let Some(v) = filter.alpha_used {
+        image_conditions.push("alpha_used = ?");
+        binds.push(SqlValue::Int(v as i64));
+    }
+    if let Some(v) = filter.animated {
+        image_conditions.push(if v { "frame_count > 1" } else { "frame_count <= 1" });
+    }
+    if let Some(v) = filter.has_icc_profile {
+        image_conditions.push("has_icc_profile = ?");
+        binds.push(SqlValue::Int(v as i64));
+    }
+    if let Some(ct) = &filter.color_type {
+        image_conditions.push("color_type = ?");
+        binds.push(SqlValue::Text(ct.clone()));
+    }
+    if !image_conditions.is_empty() {
+        conditions.push(format!(
+            "{} IN (SELECT asset_id FROM image_metadata WHERE {})",
+            id_column,
+            image_conditions.join(" AND ")
+        ));
+    }
+    if let Some(language) = &filter.language {
+        conditions.push(format!(
+            "{} IN (SELECT asset_id FROM asset_contents WHERE language = ?)",
+            id_column
+        ));
+        binds.push(SqlValue::Text(language.clone()));
+    }
+    for condition in &filter.fields {
+        push_field_condition(condition, id_column, conditions, binds);
+    }
+
+    if !filter.mime_types.is_empty() {
+        conditions.push(format!(
+            "{} IN (SELECT id FROM assets WHERE mime_type IN ({}))",
+            id_column,
+            vec!["?"; filter.mime_types.len()].join(",")
+        ));
+        binds.extend(filter.mime_types.iter().map(|m| SqlValue::Text(m.clone())));
+    }
+
+    let mut asset_conditions = Vec::new();
+    if let Some(folder) = &filter.folder_path {
+        if !filter.include_subfolders {
+            asset_conditions.push("folder_path = ?");
+            binds.push(SqlValue::Text(folder.clone()));
+        } else if folder != "/" {
+            asset_conditions.push("(folder_path = ? OR substr(folder_path, 1, length(?) + 1) = ? || '/')");
+            for _ in 0..3 {
+                binds.push(SqlValue::Text(folder.clone()));
+            }
+        }
+    }
+    for (condition, value) in [
+        ("file_size >= ?", filter.min_size),
+        ("file_size <= ?", filter.max_size),
+        ("width >= ?", filter.min_width),
+        ("width <= ?", filter.max_width),
+        ("height >= ?", filter.min_height),
+        ("height <= ?", filter.max_height),
+    ] {
+        if let Some(v) = value {
+            asset_conditions.push(condition);
+            binds.push(SqlValue::Int(v));
+        }
+    }
+    for (condition, value) in [
+        ("height > 0 AND width * 1.0 / height >= ?", filter.min_aspect),
+        ("height > 0 AND width * 1.0 / height <= ?", filter.max_aspect),
+    ] {
+        if let Some(v) = value {
+            asset_conditions.push(condition);
+            binds.push(SqlValue::Real(v));
+        }
+    }
+    for (condition, value) in [
+        ("imported_at >= ?", &filter.imported_after),
+        ("imported_at < ?", &filter.imported_before),
+        ("updated_at >= ?", &filter.updated_after),
+        ("updated_at < ?", &filter.updated_before),
+    ] {
+        if let Some(v) = value {
+            asset_conditions.push(condition);
+            binds.push(SqlValue::Text(v.clone()));
+        }
+    }
+    if let Some(v) = filter.has_ai_description {
+        asset_conditions.push(if v {
+            "COALESCE(ai_description, '') != ''"
+        } else {
+            "COALESCE(ai_description, '') = ''"
+        });
+    }
+    if let Some(v) = filter.min_rating {
+        asset_conditions.push("rating >= ?");
+        binds.push(SqlValue::Int(v));
+    }
+    if let Some(v) = filter.max_rating {
+        asset_conditions.push("rating <= ?");
+        binds.push(SqlValue::Int(v));
+    }
+    if let Some(label) = &filter.color_label {
+        asset_conditions.push("color_label = ?");
+        binds.push(SqlValue::Text(label.clone()));
+    }
+    if let Some(v) = filter.favorite {
+        asset_conditions.push("is_favorite = ?");
+        binds.push(SqlValue::Int(v as i64));
+    }
+    if !asset_conditions.is_empty() {
+        conditions.push(format!(
+            "{} IN (SELECT id FROM assets WHERE {})",
+            id_column,
+            asset_conditions.join(" AND ")
+        ));
+    }
+    if let Some(v) = filter.tagged {
+        conditions.push(format!(
+            "{} {} (SELECT asset_id FROM asset_tags)",
+            id_column,
+            if v { "IN" } else { "NOT IN" }
+        ));
+    }
+    if let Some(v) = filter.has_embedding {
+        conditions.push(format!(
+            "{} {} (SELECT asset_id FROM embeddings)",
+            id_column,
+            if v { "IN" } else { "NOT IN" }
+        ));
+    }
+
+    let mut license_conditions = Vec::new();
+    if let Some(license) = &filter.license {
+        license_conditions.push("license = ? COLLATE NOCASE");
+        binds.push(SqlValue::Text(license.clone()));
+    }
+    if let Some(v) = filter.requires_attribution {
+        license_conditions.push("requires_attribution = ?");
+        binds.push(SqlValue::Int(v as i64));
+    }
+    if !license_conditions.is_empty() {
+        conditions.push(format!(
+            "{} IN (SELECT asset_id FROM asset_licenses WHERE {})",
+            id_column,
+            license_conditions.join(" AND ")
+        ));
+    }
+    if let Some(v) = filter.has_license {
+        conditions.push(format!(
+            "{} {} (SELECT asset_id FROM asset_licenses WHERE license != '')",
+            id_column,
+            if v { "IN" } else { "NOT IN" }
+        ));
+    }
+    if let Some(v) = filter.used {
+        conditions.push(format!(
+            "{} {} (SELECT asset_id FROM asset_usages)",
+            id_column,
+            if v { "IN" } else { "NOT IN" }
+        ));
+    }
+}
+
+/// Append a condition on a custom field value. Numbers and booleans are compared
+/// with `number_value`, strings with `text_value`. Unknown operators are ignored.
+fn push_field_condition(
+    condition: &FieldCondition,
+    id_column: &str,
+    conditions: &mut Vec<String>,
+    binds: &mut Vec<SqlValue>,
+) {
+    let (column, value) = match &condition.value {
+        serde_json::Value::Bool(v) => ("number_value", SqlValue::Int(*v as i64)),
+        serde_json::Value::Number(v) => ("number_value", SqlValue::Real(v.as_f64().unwrap_or_default())),
+        serde_json::Value::String(v) => ("text_value", SqlValue::Text(v.clone())),
+        _ => ("text_value", SqlValue::Text(String::new())),
+    };
+
+    let (negate, predicate) = match condition.op.as_str() {
+        "set" => (false, None),
+        "unset" => (true, None),
+        "eq" => (false, Some(format!("{} = ?", column))),
+        "ne" => (true, Some(format!("{} = ?", column))),
+        "lt" => (false, Some(format!("{} < ?", column))),
+        "lte" => (false, Some(format!("{} <= ?", column))),
+        "gt" => (false, Some(format!("{} > ?", column))),
+        "gte" => (false, Some(format!("{} >= ?", column))),
+        "contains" => (false, Some("text_value LIKE '%' || ? || '%'".to_string())),
+        // Commands reject unknown ops; a stored one matches nothing rather than everything
+        _ => {
+            conditions.push("0".to_string());
+            return;
+        }
+    };
+
+    conditions.push(format!(
+        "{} {} (SELECT asset_id FROM asset_field_values WHERE field_id = ?{})",
+        id_column,
+        if negate { "NOT IN" } else { "IN" },
+        predicate.as_ref().map(|p| format!(" AND {}", p)).unwrap_or_default()
+    ));
+    binds.push(SqlValue::Text(condition.field_id.clone()));
+    if predicate.is_some() {
+        binds.push(value);
+    }
+}
+
 // --- Library queries ---
 
 pub async fn create_library(
@@ -34,6 +279,21 @@ pub async fn get_library(pool: &SqlitePool, id: &str) -> Result<Library, sqlx::E
         .await
 }
 
+pub async fn set_library_auto_apply_ai_names(
+    pool: &SqlitePool,
+    id: &str,
+    enabled: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE libraries SET auto_apply_ai_names = ?, updated_at = datetime('now') WHERE id = ?",
+    )
+    .bind(enabled)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 pub async fn delete_library(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
     sqlx::query("DELETE FROM libraries WHERE id = ?")
         .bind(id)
@@ -45,6 +305,7 @@ pub async fn delete_library(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Err
 // --- Asset queries ---
 
 pub async fn insert_asset(pool: &SqlitePool, asset: &Asset) -> Result<Asset, sqlx::Error> {
+    ensure_folder_path(pool, &asset.library_id, &asset.folder_path).await?;
     sqlx::query_as::<_, Asset>(
         "INSERT INTO assets (id, library_id, file_name, original_name, relative_path, file_type, mime_type, file_size, file_hash, width, height, duration_ms, description, ai_description, thumbnail_path, folder_path)
          VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
@@ -70,72 +331,163 @@ pub async fn insert_asset(pool: &SqlitePool, asset: &Asset) -> Result<Asset, sql
     .await
 }
 
-pub async fn get_assets(
-    pool: &SqlitePool,
-    library_id: &str,
-    folder_path: Option<&str>,
-    file_type: Option<&str>,
-    page: u32,
-    page_size: u32,
-    sort_by: &str,
-    sort_order: &str,
-) -> Result<PaginatedAssets, sqlx::Error> {
-    let offset = (page.saturating_sub(1)) * page_size;
-
-    let order_column = match sort_by {
+/// Column or expression to order assets by. Custom fields sort as "field:<field_id>".
+fn asset_order_column(sort_by: &str, order_binds: &mut Vec<SqlValue>) -> &'static str {
+    match sort_by {
         "name" => "file_name",
         "size" => "file_size",
         "date" => "imported_at",
-        _ => "imported_at",
-    };
-    let order_dir = if sort_order == "asc" { "ASC" } else { "DESC" };
+        "rating" => "rating",
+        _ => match sort_by.strip_prefix("field:") {
+            Some(field_id) => {
+                order_binds.push(SqlValue::Text(field_id.to_string()));
+                "(SELECT COALESCE(number_value, text_value) FROM asset_field_values WHERE asset_id = assets.id AND field_id = ?)"
+            }
+            None => "imported_at",
+        },
+    }
+}
 
-    // Build dynamic query
-    let mut conditions = vec!["library_id = ?".to_string()];
-    if folder_path.is_some() {
-        conditions.push("folder_path = ?".to_string());
+/// Sort value of an asset row as stored in a cursor
+fn cursor_value(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
+    if let Ok(Some(v)) = row.try_get::<Option<i64>, _>("sort_key") {
+        v.into()
+    } else if let Ok(Some(v)) = row.try_get::<Option<f64>, _>("sort_key") {
+        v.into()
+    } else if let Ok(Some(v)) = row.try_get::<Option<String>, _>("sort_key") {
+        v.into()
+    } else {
+        serde_json::Value::Null
     }
-    if file_type.is_some() {
-        conditions.push("file_type = ?".to_string());
+}
+
+fn cursor_bind(value: &serde_json::Value) -> SqlValue {
+    match value {
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(v) => SqlValue::Int(v),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(v) => SqlValue::Text(v.clone()),
+        serde_json::Value::Bool(v) => SqlValue::Int(*v as i64),
+        _ => SqlValue::Null,
     }
+}
 
+/// Count and fetch one page of the assets matching `conditions`, ordered by
+/// `order_column` with assets without a value last and the asset ID as tie-breaker.
+/// With a cursor the page starts after the cursor's asset (keyset pagination) and `page`
+/// is ignored; otherwise `page` selects the page by offset.
+async fn fetch_asset_page(
+    pool: &SqlitePool,
+    conditions: &[String],
+    binds: &[SqlValue],
+    order_column: &str,
+    order_binds: &[SqlValue],
+    sort_order: &str,
+    page: u32,
+    page_size: u32,
+    cursor: Option<&AssetCursor>,
+) -> Result<PaginatedAssets, sqlx::Error> {
     let where_clause = conditions.join(" AND ");
+    let (order_dir, op) = if sort_order == "asc" { ("ASC", ">") } else { ("DESC", "<") };
+
+    let mut page_binds: Vec<SqlValue> = order_binds.iter().chain(binds).cloned().collect();
+    let keyset_clause = match cursor {
+        Some(cursor) if cursor.value.is_null() => {
+            page_binds.push(SqlValue::Text(cursor.id.clone()));
+            format!("WHERE sort_key IS NULL AND id {} ?", op)
+        }
+        Some(cursor) => {
+            let value = cursor_bind(&cursor.value);
+            page_binds.extend([value.clone(), value, SqlValue::Text(cursor.id.clone())]);
+            format!(
+                "WHERE sort_key {0} ? OR (sort_key = ? AND id {0} ?) OR sort_key IS NULL",
+                op
+            )
+        }
+        None => String::new(),
+    };
+    let offset = if cursor.is_some() { 0 } else { page.saturating_sub(1) * page_size };
 
     let count_sql = format!("SELECT COUNT(*) as count FROM assets WHERE {}", where_clause);
     let query_sql = format!(
-        "SELECT * FROM assets WHERE {} ORDER BY {} {} LIMIT ? OFFSET ?",
-        where_clause, order_column, order_dir
+        "SELECT * FROM (SELECT *, {} AS sort_key FROM assets WHERE {}) {}
+         ORDER BY sort_key {dir} NULLS LAST, id {dir} LIMIT ? OFFSET ?",
+        order_column,
+        where_clause,
+        keyset_clause,
+        dir = order_dir
     );
 
     // Count query
-    let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql).bind(library_id);
-    if let Some(fp) = folder_path {
-        count_query = count_query.bind(fp);
-    }
-    if let Some(ft) = file_type {
-        count_query = count_query.bind(ft);
-    }
-    let total = count_query.fetch_one(pool).await?;
+    let total = bind_values!(sqlx::query_scalar::<_, i64>(&count_sql), binds)
+        .fetch_one(pool)
+        .await?;
 
-    // Data query
-    let mut data_query = sqlx::query_as::<_, Asset>(&query_sql).bind(library_id);
-    if let Some(fp) = folder_path {
-        data_query = data_query.bind(fp);
-    }
-    if let Some(ft) = file_type {
-        data_query = data_query.bind(ft);
-    }
-    data_query = data_query.bind(page_size).bind(offset);
-    let assets = data_query.fetch_all(pool).await?;
+    // Data query, fetching one extra row to tell whether another page follows
+    let mut rows = bind_values!(sqlx::query(&query_sql), &page_binds)
+        .bind(page_size + 1)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+    let has_more = rows.len() > page_size as usize;
+    rows.truncate(page_size as usize);
+
+    let next_cursor = rows.last().filter(|_| has_more).map(|row| {
+        AssetCursor {
+            value: cursor_value(row),
+            id: row.get("id"),
+        }
+        .encode()
+    });
+    let assets = rows.iter().map(Asset::from_row).collect::<Result<Vec<_>, _>>()?;
 
     Ok(PaginatedAssets {
         assets,
         total,
         page,
         page_size,
+        next_cursor,
     })
 }
 
+pub async fn get_assets(
+    pool: &SqlitePool,
+    library_id: &str,
+    file_type: Option<&str>,
+    filter: &AssetFilter,
+    page: u32,
+    page_size: u32,
+    sort_by: &str,
+    sort_order: &str,
+    cursor: Option<&AssetCursor>,
+) -> Result<PaginatedAssets, sqlx::Error> {
+    let mut order_binds = Vec::new();
+    let order_column = asset_order_column(sort_by, &mut order_binds);
+
+    // Build dynamic query
+    let mut conditions = vec!["library_id = ?".to_string()];
+    let mut binds = vec![SqlValue::Text(library_id.to_string())];
+    if let Some(ft) = file_type {
+        conditions.push("file_type = ?".to_string());
+        binds.push(SqlValue::Text(ft.to_string()));
+    }
+    push_asset_filter(filter, "assets.id", &mut conditions, &mut binds);
+
+    fetch_asset_page(
+        pool,
+        &conditions,
+        &binds,
+        order_column,
+        &order_binds,
+        sort_order,
+        page,
+        page_size,
+        cursor,
+    )
+    .await
+}
+
 pub async fn get_asset(pool: &SqlitePool, id: &str) -> Result<Asset, sqlx::Error> {
     sqlx::query_as::<_, Asset>("SELECT * FROM assets WHERE id = ?")
         .bind(id)
@@ -169,382 +521,2307 @@ pub async fn update_asset_description(
     Ok(())
 }
 
-pub async fn delete_assets(pool: &SqlitePool, ids: &[String]) -> Result<(), sqlx::Error> {
-    for id in ids {
-        sqlx::query("DELETE FROM assets WHERE id = ?")
-            .bind(id)
-            .execute(pool)
-            .await?;
-    }
-    Ok(())
-}
-
-pub async fn move_assets(
+/// Rename several assets in one transaction: `(id, file_name, relative_path)`
+pub async fn rename_assets(
     pool: &SqlitePool,
-    ids: &[String],
-    target_folder: &str,
+    renames: &[(String, String, String)],
 ) -> Result<(), sqlx::Error> {
-    for id in ids {
+    let mut tx = pool.begin().await?;
+    for (id, file_name, relative_path) in renames {
         sqlx::query(
-            "UPDATE assets SET folder_path = ?, updated_at = datetime('now') WHERE id = ?",
+            "UPDATE assets SET file_name = ?, relative_path = ?, updated_at = datetime('now') WHERE id = ?",
         )
-        .bind(target_folder)
+        .bind(file_name)
+        .bind(relative_path)
         .bind(id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
     }
+    tx.commit().await?;
     Ok(())
 }
 
-pub async fn get_folders(
+/// `(id, file_name)` of all assets directly in a folder
+pub async fn get_folder_file_names(
     pool: &SqlitePool,
     library_id: &str,
-) -> Result<Vec<FolderInfo>, sqlx::Error> {
-    let rows = sqlx::query_as::<_, (String, i64)>(
-        "SELECT folder_path, COUNT(*) as asset_count FROM assets WHERE library_id = ? AND folder_path != '/' GROUP BY folder_path ORDER BY folder_path",
+    folder_path: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String)>(
+        "SELECT id, file_name FROM assets WHERE library_id = ? AND folder_path = ?",
     )
     .bind(library_id)
+    .bind(folder_path)
     .fetch_all(pool)
-    .await?;
-
-    Ok(rows
-        .into_iter()
-        .map(|(path, count)| {
-            let name = path
-                .rsplit('/')
-                .find(|s| !s.is_empty())
-                .unwrap_or("/")
-                .to_string();
-            FolderInfo {
-                path: path.clone(),
-                name,
-                asset_count: count,
-            }
-        })
-        .collect())
+    .await
 }
 
-pub async fn create_folder(
+/// Set the star rating of several assets
+pub async fn set_assets_rating(
     pool: &SqlitePool,
-    library_id: &str,
-    path: &str,
+    ids: &[String],
+    rating: i64,
 ) -> Result<(), sqlx::Error> {
-    // Folders are virtual — they exist implicitly via folder_path on assets.
-    // We insert a sentinel record or just validate the path format.
-    // For now, folders are created implicitly when assets are moved into them.
-    let _ = (pool, library_id, path);
-    Ok(())
+    update_assets(pool, ids, "rating = ?", SqlValue::Int(rating)).await
 }
 
-// --- Tag queries ---
-
-pub async fn create_tag(
+/// Set (or with `None`, clear) the color label of several assets
+pub async fn set_assets_color_label(
     pool: &SqlitePool,
-    library_id: &str,
-    name: &str,
-    color: &str,
-    category: &str,
-    is_ai: bool,
-) -> Result<Tag, sqlx::Error> {
-    let id = Uuid::new_v4().to_string();
-    sqlx::query_as::<_, Tag>(
-        "INSERT INTO tags (id, library_id, name, color, category, is_ai) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
-    )
-    .bind(&id)
-    .bind(library_id)
-    .bind(name)
-    .bind(color)
-    .bind(category)
-    .bind(is_ai)
-    .fetch_one(pool)
-    .await
+    ids: &[String],
+    color_label: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let value = match color_label {
+        Some(label) => SqlValue::Text(label.to_string()),
+        None => SqlValue::Null,
+    };
+    update_assets(pool, ids, "color_label = ?", value).await
 }
 
-pub async fn get_or_create_tag(
+pub async fn set_assets_favorite(
     pool: &SqlitePool,
-    library_id: &str,
-    name: &str,
-    is_ai: bool,
-) -> Result<Tag, sqlx::Error> {
-    // Try to find existing
-    let existing = sqlx::query_as::<_, Tag>(
-        "SELECT * FROM tags WHERE library_id = ? AND name = ?",
-    )
-    .bind(library_id)
-    .bind(name)
-    .fetch_optional(pool)
-    .await?;
-
-    if let Some(tag) = existing {
-        return Ok(tag);
-    }
-
-    create_tag(pool, library_id, name, "#808080", "", is_ai).await
+    ids: &[String],
+    favorite: bool,
+) -> Result<(), sqlx::Error> {
+    update_assets(pool, ids, "is_favorite = ?", SqlValue::Int(favorite as i64)).await
 }
 
-pub async fn list_tags(
+/// Set the description of several assets
+pub async fn set_assets_description(
     pool: &SqlitePool,
-    library_id: &str,
-    category: Option<&str>,
-) -> Result<Vec<TagWithCount>, sqlx::Error> {
-    let sql = if category.is_some() {
-        "SELECT t.*, COALESCE(c.cnt, 0) as asset_count
-         FROM tags t
-         LEFT JOIN (SELECT tag_id, COUNT(*) as cnt FROM asset_tags GROUP BY tag_id) c ON c.tag_id = t.id
-         WHERE t.library_id = ? AND t.category = ?
-         ORDER BY t.name"
-    } else {
-        "SELECT t.*, COALESCE(c.cnt, 0) as asset_count
-         FROM tags t
-         LEFT JOIN (SELECT tag_id, COUNT(*) as cnt FROM asset_tags GROUP BY tag_id) c ON c.tag_id = t.id
-         WHERE t.library_id = ?
-         ORDER BY t.name"
-    };
-
-    // Manual mapping since TagWithCount doesn't derive FromRow easily with the join
-    let rows = if let Some(cat) = category {
-        sqlx::query_as::<_, (String, String, String, String, String, bool, String, i64)>(sql)
-            .bind(library_id)
-            .bind(cat)
-            .fetch_all(pool)
-            .await?
-    } else {
-        sqlx::query_as::<_, (String, String, String, String, String, bool, String, i64)>(sql)
-            .bind(library_id)
-            .fetch_all(pool)
-            .await?
-    };
-
-    Ok(rows
-        .into_iter()
-        .map(
-            |(id, library_id, name, color, category, is_ai, created_at, asset_count)| {
-                TagWithCount {
-                    id,
-                    library_id,
-                    name,
-                    color,
-                    category,
-                    is_ai,
-                    created_at,
-                    asset_count,
-                }
-            },
-        )
-        .collect())
+    ids: &[String],
+    description: &str,
+) -> Result<(), sqlx::Error> {
+    update_assets(pool, ids, "description = ?", SqlValue::Text(description.to_string())).await
 }
 
-pub async fn rename_tag(pool: &SqlitePool, id: &str, new_name: &str) -> Result<(), sqlx::Error> {
-    sqlx::query("UPDATE tags SET name = ? WHERE id = ?")
-        .bind(new_name)
-        .bind(id)
+/// Apply `assignment` (e.g. `rating = ?`, bound to `value`) to all `ids` in one statement.
+/// IDs are bound as a single JSON array, so selections of any size fit one query.
+async fn update_assets(
+    pool: &SqlitePool,
+    ids: &[String],
+    assignment: &str,
+    value: SqlValue,
+) -> Result<(), sqlx::Error> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let sql = format!(
+        "UPDATE assets SET {}, updated_at = datetime('now') WHERE id IN (SELECT value FROM json_each(?))",
+        assignment
+    );
+    bind_values!(sqlx::query(&sql), std::iter::once(&value))
+        .bind(sqlx::types::Json(ids))
         .execute(pool)
         .await?;
     Ok(())
 }
 
-pub async fn delete_tag(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
-    sqlx::query("DELETE FROM tags WHERE id = ?")
-        .bind(id)
-        .execute(pool)
-        .await?;
-    Ok(())
+/// The assets with the given IDs, in no particular order; unknown IDs are left out
+pub async fn get_assets_by_ids(pool: &SqlitePool, ids: &[String]) -> Result<Vec<Asset>, sqlx::Error> {
+    sqlx::query_as::<_, Asset>("SELECT * FROM assets WHERE id IN (SELECT value FROM json_each(?))")
+        .bind(sqlx::types::Json(ids))
+        .fetch_all(pool)
+        .await
 }
 
-pub async fn assign_tags(
-    pool: &SqlitePool,
-    asset_id: &str,
-    tag_ids: &[String],
-) -> Result<(), sqlx::Error> {
-    for tag_id in tag_ids {
-        sqlx::query(
-            "INSERT OR IGNORE INTO asset_tags (asset_id, tag_id) VALUES (?, ?)",
-        )
-        .bind(asset_id)
-        .bind(tag_id)
+/// Delete assets in one statement. Tags, embeddings and other per-asset rows are
+/// removed by cascade.
+pub async fn delete_assets(pool: &SqlitePool, ids: &[String]) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM assets WHERE id IN (SELECT value FROM json_each(?))")
+        .bind(sqlx::types::Json(ids))
         .execute(pool)
         .await?;
-    }
     Ok(())
 }
 
-pub async fn remove_tags(
+/// Move assets into `target_folder` and give them the folder's default tags, in one
+/// transaction. `moves` pairs each asset ID with its new relative file path.
+pub async fn move_assets(
     pool: &SqlitePool,
-    asset_id: &str,
-    tag_ids: &[String],
+    library_id: &str,
+    moves: &[(String, String)],
+    target_folder: &str,
 ) -> Result<(), sqlx::Error> {
-    for tag_id in tag_ids {
-        sqlx::query("DELETE FROM asset_tags WHERE asset_id = ? AND tag_id = ?")
-            .bind(asset_id)
-            .bind(tag_id)
-            .execute(pool)
-            .await?;
-    }
-    Ok(())
-}
+    ensure_folder_path(pool, library_id, target_folder).await?;
 
-pub async fn get_asset_tags(pool: &SqlitePool, asset_id: &str) -> Result<Vec<Tag>, sqlx::Error> {
-    sqlx::query_as::<_, Tag>(
-        "SELECT t.* FROM tags t INNER JOIN asset_tags at ON t.id = at.tag_id WHERE at.asset_id = ? ORDER BY t.name",
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE assets SET folder_path = ?, relative_path = json_extract(m.value, '$[1]'), updated_at = datetime('now')
+         FROM json_each(?) m
+         WHERE assets.id = json_extract(m.value, '$[0]') AND assets.library_id = ?",
     )
-    .bind(asset_id)
-    .fetch_all(pool)
-    .await
-}
+    .bind(target_folder)
+    .bind(sqlx::types::Json(moves))
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+
+    // Default tags that have since been deleted are skipped
+    sqlx::query(
+        "INSERT OR IGNORE INTO asset_tags (asset_id, tag_id)
+         SELECT json_extract(m.value, '$[0]'), t.id
+         FROM json_each(?) m, folders f, json_each(f.default_tag_ids) d
+         INNER JOIN tags t ON t.id = d.value
+         WHERE f.library_id = ? AND f.path = ?",
+    )
+    .bind(sqlx::types::Json(moves))
+    .bind(library_id)
+    .bind(target_folder)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// --- Folder queries ---
+
+const FOLDER_INFO_SELECT: &str =
+    "SELECT f.*,
+        (SELECT COUNT(*) FROM assets a WHERE a.library_id = f.library_id AND a.folder_path = f.path) AS asset_count,
+        (SELECT COUNT(*) FROM assets a WHERE a.library_id = f.library_id
+            AND (a.folder_path = f.path OR substr(a.folder_path, 1, length(f.path) + 1) = f.path || '/')) AS total_asset_count
+     FROM folders f";
+
+pub async fn get_folders(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<FolderInfo>, sqlx::Error> {
+    sqlx::query_as::<_, FolderInfo>(&format!(
+        "{} WHERE f.library_id = ? ORDER BY f.path",
+        FOLDER_INFO_SELECT
+    ))
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_folder_info(pool: &SqlitePool, id: &str) -> Result<FolderInfo, sqlx::Error> {
+    sqlx::query_as::<_, FolderInfo>(&format!("{} WHERE f.id = ?", FOLDER_INFO_SELECT))
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn get_folder_by_path(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<Option<Folder>, sqlx::Error> {
+    sqlx::query_as::<_, Folder>("SELECT * FROM folders WHERE library_id = ? AND path = ?")
+        .bind(library_id)
+        .bind(path)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Create the folder at a canonical `path` along with any missing ancestors.
+/// Returns the deepest folder, or `None` for the library root.
+pub async fn ensure_folder_path(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<Option<Folder>, sqlx::Error> {
+    let mut folder: Option<Folder> = None;
+    let mut current = String::new();
+
+    for name in path.split('/').filter(|s| !s.is_empty()) {
+        current = format!("{}/{}", current, name);
+        folder = match get_folder_by_path(pool, library_id, &current).await? {
+            Some(existing) => Some(existing),
+            None => Some(
+                sqlx::query_as::<_, Folder>(
+                    "INSERT INTO folders (id, library_id, parent_id, name, path)
+                     VALUES (?, ?, ?, ?, ?) RETURNING *",
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(library_id)
+                .bind(folder.as_ref().map(|f| f.id.clone()))
+                .bind(name)
+                .bind(&current)
+                .fetch_one(pool)
+                .await?,
+            ),
+        };
+    }
+
+    Ok(folder)
+}
+
+/// Asset folder paths that have no folder record yet (e.g. from before folders were stored)
+pub async fn get_unregistered_folder_paths(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String,)>(
+        "SELECT DISTINCT folder_path FROM assets
+         WHERE library_id = ? AND folder_path != '/'
+         AND folder_path NOT IN (SELECT path FROM folders WHERE library_id = ?)",
+    )
+    .bind(library_id)
+    .bind(library_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|(path,)| path).collect())
+}
+
+pub async fn update_folder(
+    pool: &SqlitePool,
+    id: &str,
+    color: Option<&str>,
+    icon: Option<&str>,
+    description: Option<&str>,
+    default_tag_ids: Option<&[String]>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE folders SET
+            color = NULLIF(COALESCE(?, color), ''),
+            icon = NULLIF(COALESCE(?, icon), ''),
+            description = COALESCE(?, description),
+            default_tag_ids = COALESCE(?, default_tag_ids),
+            updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(color)
+    .bind(icon)
+    .bind(description)
+    .bind(default_tag_ids.map(sqlx::types::Json))
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Move a folder and everything below it from `old_path` to `new_path` (both canonical)
+/// in a single transaction: descendant folders, asset folder paths and the relative
+/// paths of files stored under the folder's directory.
+pub async fn move_folder_tree(
+    pool: &SqlitePool,
+    library_id: &str,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), sqlx::Error> {
+    let (parent_path, name) = new_path.rsplit_once('/').unwrap_or(("", new_path));
+    let parent = ensure_folder_path(pool, library_id, parent_path).await?;
+    let old_dir = format!("{}/", old_path.trim_start_matches('/'));
+    let new_dir = format!("{}/", new_path.trim_start_matches('/'));
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "UPDATE folders SET path = ?1 || substr(path, length(?2) + 1), updated_at = datetime('now')
+         WHERE library_id = ?3 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')",
+    )
+    .bind(new_path)
+    .bind(old_path)
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE folders SET name = ?, parent_id = ? WHERE library_id = ? AND path = ?")
+        .bind(name)
+        .bind(parent.map(|f| f.id))
+        .bind(library_id)
+        .bind(new_path)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "UPDATE assets SET folder_path = ?1 || substr(folder_path, length(?2) + 1), updated_at = datetime('now')
+         WHERE library_id = ?3 AND (folder_path = ?2 OR substr(folder_path, 1, length(?2) + 1) = ?2 || '/')",
+    )
+    .bind(new_path)
+    .bind(old_path)
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE assets SET relative_path = ?1 || substr(relative_path, length(?2) + 1)
+         WHERE library_id = ?3 AND substr(relative_path, 1, length(?2)) = ?2",
+    )
+    .bind(&new_dir)
+    .bind(&old_dir)
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Number of folders (including the folder itself) and assets, with their total size,
+/// in the tree rooted at `path`
+pub async fn get_folder_tree_summary(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<FolderTreeSummary, sqlx::Error> {
+    sqlx::query_as::<_, FolderTreeSummary>(
+        "SELECT ?1 AS path,
+            (SELECT COUNT(*) FROM folders WHERE library_id = ?2
+                AND (path = ?1 OR substr(path, 1, length(?1) + 1) = ?1 || '/')) AS folder_count,
+            COUNT(*) AS asset_count,
+            COALESCE(SUM(file_size), 0) AS total_size
+         FROM assets WHERE library_id = ?2
+            AND (folder_path = ?1 OR substr(folder_path, 1, length(?1) + 1) = ?1 || '/')",
+    )
+    .bind(path)
+    .bind(library_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// All assets in the folder at `path` and its subfolders
+pub async fn get_folder_tree_assets(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<Vec<Asset>, sqlx::Error> {
+    sqlx::query_as::<_, Asset>(
+        "SELECT * FROM assets WHERE library_id = ?2
+         AND (folder_path = ?1 OR substr(folder_path, 1, length(?1) + 1) = ?1 || '/')",
+    )
+    .bind(path)
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete the folder at `path`, its subfolders and all their assets in a single transaction.
+/// Tags, embeddings and other per-asset rows are removed by cascade.
+pub async fn delete_folder_tree(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "DELETE FROM assets WHERE library_id = ?2
+         AND (folder_path = ?1 OR substr(folder_path, 1, length(?1) + 1) = ?1 || '/')",
+    )
+    .bind(path)
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "DELETE FROM folders WHERE library_id = ?2
+         AND (path = ?1 OR substr(path, 1, length(?1) + 1) = ?1 || '/')",
+    )
+    .bind(path)
+    .bind(library_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Assign a folder's default tags to assets placed in it
+pub async fn apply_folder_default_tags(
+    pool: &SqlitePool,
+    library_id: &str,
+    folder_path: &str,
+    asset_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    if let Some(folder) = get_folder_by_path(pool, library_id, folder_path).await? {
+        if !folder.default_tag_ids.is_empty() {
+            assign_tags_to_assets(pool, asset_ids, &folder.default_tag_ids.0).await?;
+        }
+    }
+    Ok(())
+}
+
+// --- Format metadata queries ---
+
+pub async fn save_format_metadata(
+    pool: &SqlitePool,
+    asset_id: &str,
+    format: &str,
+    data: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO asset_format_metadata (asset_id, format, data, updated_at) VALUES (?, ?, ?, datetime('now'))",
+    )
+    .bind(asset_id)
+    .bind(format)
+    .bind(data)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_format_metadata(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<(String, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String)>(
+        "SELECT format, data FROM asset_format_metadata WHERE asset_id = ?",
+    )
+    .bind(asset_id)
+    .fetch_optional(pool)
+    .await
+}
+
+// --- Image metadata queries ---
+
+pub async fn save_image_metadata(
+    pool: &SqlitePool,
+    metadata: &ImageMetadata,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO image_metadata (asset_id, color_type, bit_depth, has_alpha, alpha_used, frame_count, has_icc_profile, icc_profile_size, dpi_x, dpi_y, text_entries, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))",
+    )
+    .bind(&metadata.asset_id)
+    .bind(&metadata.color_type)
+    .bind(metadata.bit_depth)
+    .bind(metadata.has_alpha)
+    .bind(metadata.alpha_used)
+    .bind(metadata.frame_count)
+    .bind(metadata.has_icc_profile)
+    .bind(metadata.icc_profile_size)
+    .bind(metadata.dpi_x)
+    .bind(metadata.dpi_y)
+    .bind(&metadata.text_entries)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_image_metadata(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<ImageMetadata>, sqlx::Error> {
+    sqlx::query_as::<_, ImageMetadata>("SELECT * FROM image_metadata WHERE asset_id = ?")
+        .bind(asset_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Image assets that have no technical metadata yet (or all image assets when `include_existing`)
+pub async fn get_image_assets_for_metadata(
+    pool: &SqlitePool,
+    library_id: &str,
+    include_existing: bool,
+) -> Result<Vec<Asset>, sqlx::Error> {
+    let sql = if include_existing {
+        "SELECT * FROM assets WHERE library_id = ? AND file_type = 'image'"
+    } else {
+        "SELECT * FROM assets WHERE library_id = ? AND file_type = 'image'
+         AND id NOT IN (SELECT asset_id FROM image_metadata)"
+    };
+    sqlx::query_as::<_, Asset>(sql)
+        .bind(library_id)
+        .fetch_all(pool)
+        .await
+}
+
+// --- Usage queries ---
+
+/// `(id, file_size, file_hash)` of all assets of a library
+pub async fn get_asset_hashes(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<(String, i64, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, i64, String)>(
+        "SELECT id, file_size, file_hash FROM assets WHERE library_id = ?",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_library_image_assets(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<Asset>, sqlx::Error> {
+    sqlx::query_as::<_, Asset>("SELECT * FROM assets WHERE library_id = ? AND file_type = 'image'")
+        .bind(library_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Cached perceptual hashes of the image assets of a library, with the content hash
+/// they were computed for
+pub async fn get_perceptual_hashes(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<(String, String, i64)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String, i64)>(
+        "SELECT p.asset_id, p.file_hash, p.dhash FROM asset_perceptual_hashes p
+         JOIN assets a ON a.id = p.asset_id
+         WHERE a.library_id = ?",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn save_perceptual_hash(
+    pool: &SqlitePool,
+    asset_id: &str,
+    file_hash: &str,
+    dhash: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR REPLACE INTO asset_perceptual_hashes (asset_id, file_hash, dhash) VALUES (?, ?, ?)")
+        .bind(asset_id)
+        .bind(file_hash)
+        .bind(dhash)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Replace the recorded usages of a project in one transaction
+pub async fn replace_project_usages(
+    pool: &SqlitePool,
+    library_id: &str,
+    project_path: &str,
+    usages: &[AssetUsage],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM asset_usages WHERE library_id = ? AND project_path = ?")
+        .bind(library_id)
+        .bind(project_path)
+        .execute(&mut *tx)
+        .await?;
+    for usage in usages {
+        sqlx::query(
+            "INSERT INTO asset_usages (id, library_id, asset_id, project_path, file_path, match_kind)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&usage.id)
+        .bind(&usage.library_id)
+        .bind(&usage.asset_id)
+        .bind(&usage.project_path)
+        .bind(&usage.file_path)
+        .bind(&usage.match_kind)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn get_project_usages(
+    pool: &SqlitePool,
+    library_id: &str,
+    project_path: &str,
+) -> Result<Vec<AssetUsage>, sqlx::Error> {
+    sqlx::query_as::<_, AssetUsage>(
+        "SELECT * FROM asset_usages WHERE library_id = ? AND project_path = ? ORDER BY file_path",
+    )
+    .bind(library_id)
+    .bind(project_path)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_asset_usages(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<AssetUsage>, sqlx::Error> {
+    sqlx::query_as::<_, AssetUsage>(
+        "SELECT * FROM asset_usages WHERE asset_id = ? ORDER BY project_path, file_path",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Scanned projects of a library, most recently scanned first
+pub async fn get_usage_projects(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<UsageProject>, sqlx::Error> {
+    sqlx::query_as::<_, UsageProject>(
+        "SELECT project_path, COUNT(DISTINCT asset_id) AS asset_count, COUNT(*) AS file_count,
+            MAX(scanned_at) AS scanned_at
+         FROM asset_usages WHERE library_id = ?
+         GROUP BY project_path
+         ORDER BY scanned_at DESC",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+// --- Color palette queries ---
+
+/// Replace the palette of an asset
+pub async fn save_asset_palette(
+    pool: &SqlitePool,
+    asset_id: &str,
+    colors: &[PaletteColor],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM asset_colors WHERE asset_id = ?")
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+    for (position, color) in colors.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO asset_colors (asset_id, position, hex, l, a, b, coverage) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(asset_id)
+        .bind(position as i64)
+        .bind(&color.hex)
+        .bind(color.l)
+        .bind(color.a)
+        .bind(color.b)
+        .bind(color.coverage)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query("INSERT OR REPLACE INTO asset_palette_status (asset_id, updated_at) VALUES (?, datetime('now'))")
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn get_asset_palette(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<PaletteColor>, sqlx::Error> {
+    sqlx::query_as::<_, PaletteColor>(
+        "SELECT hex, l, a, b, coverage FROM asset_colors WHERE asset_id = ? ORDER BY position",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Image assets of a library without a palette (or all images with `include_existing`)
+pub async fn get_image_assets_for_palette(
+    pool: &SqlitePool,
+    library_id: &str,
+    include_existing: bool,
+) -> Result<Vec<Asset>, sqlx::Error> {
+    let sql = if include_existing {
+        "SELECT * FROM assets WHERE library_id = ? AND file_type = 'image'"
+    } else {
+        "SELECT * FROM assets WHERE library_id = ? AND file_type = 'image'
+         AND id NOT IN (SELECT asset_id FROM asset_palette_status)"
+    };
+    sqlx::query_as::<_, Asset>(sql)
+        .bind(library_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Assets with palette colors within `max_distance` (CIELAB ΔE) of `lab` that together
+/// cover at least `min_coverage` of the image. Returns `(asset_id, distance, coverage)`,
+/// closest first.
+pub async fn search_by_color(
+    pool: &SqlitePool,
+    library_id: &str,
+    lab: (f64, f64, f64),
+    max_distance: f64,
+    min_coverage: f64,
+    limit: u32,
+) -> Result<Vec<(String, f64, f64)>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, f64, f64)>(
+        "SELECT c.asset_id, MIN(c.d2) AS d2, SUM(c.coverage) AS coverage
+         FROM (
+            SELECT asset_id, coverage,
+                (l - ?1) * (l - ?1) + (a - ?2) * (a - ?2) + (b - ?3) * (b - ?3) AS d2
+            FROM asset_colors
+         ) c
+         JOIN assets ON assets.id = c.asset_id
+         WHERE assets.library_id = ?4 AND c.d2 <= ?5
+         GROUP BY c.asset_id
+         HAVING SUM(c.coverage) >= ?6
+         ORDER BY d2, coverage DESC
+         LIMIT ?7",
+    )
+    .bind(lab.0)
+    .bind(lab.1)
+    .bind(lab.2)
+    .bind(library_id)
+    .bind(max_distance * max_distance)
+    .bind(min_coverage)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(asset_id, d2, coverage)| (asset_id, d2.sqrt(), coverage))
+        .collect())
+}
+
+// --- Text content queries ---
+
+/// Store the indexed content of a text asset; triggers keep the FTS index in sync
+pub async fn save_text_content(
+    pool: &SqlitePool,
+    asset_id: &str,
+    language: &str,
+    content: &str,
+    byte_size: i64,
+    line_count: i64,
+    truncated: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO asset_contents (asset_id, language, content, byte_size, line_count, truncated)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT(asset_id) DO UPDATE SET
+            language = excluded.language, content = excluded.content,
+            byte_size = excluded.byte_size, line_count = excluded.line_count,
+            truncated = excluded.truncated, updated_at = datetime('now')",
+    )
+    .bind(asset_id)
+    .bind(language)
+    .bind(content)
+    .bind(byte_size)
+    .bind(line_count)
+    .bind(truncated)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_text_content_info(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<TextContentInfo>, sqlx::Error> {
+    sqlx::query_as::<_, TextContentInfo>(
+        "SELECT asset_id, language, byte_size, line_count, truncated, updated_at
+         FROM asset_contents WHERE asset_id = ?",
+    )
+    .bind(asset_id)
+    .fetch_optional(pool)
+    .await
+}
+
+// --- Asset version queries ---
+
+/// Record `previous` as the next version of its asset and switch the asset to the
+/// file described by `asset`, in a single transaction. Details derived from the old
+/// file (format and image metadata, indexed text, links, embeddings) are cleared.
+pub async fn replace_asset_file(
+    pool: &SqlitePool,
+    previous: &AssetVersion,
+    asset: &Asset,
+) -> Result<Asset, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT INTO asset_versions (id, asset_id, version_number, relative_path, original_name, file_type, mime_type, file_size, file_hash, width, height, duration_ms, thumbnail_path, note)
+         VALUES (?, ?, (SELECT COALESCE(MAX(version_number), 0) + 1 FROM asset_versions WHERE asset_id = ?), ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&previous.id)
+    .bind(&previous.asset_id)
+    .bind(&previous.asset_id)
+    .bind(&previous.relative_path)
+    .bind(&previous.original_name)
+    .bind(&previous.file_type)
+    .bind(&previous.mime_type)
+    .bind(previous.file_size)
+    .bind(&previous.file_hash)
+    .bind(previous.width)
+    .bind(previous.height)
+    .bind(previous.duration_ms)
+    .bind(&previous.thumbnail_path)
+    .bind(&previous.note)
+    .execute(&mut *tx)
+    .await?;
+
+    for table in ["asset_format_metadata", "image_metadata", "asset_contents", "asset_links", "asset_colors", "embeddings"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE asset_id = ?", table))
+            .bind(&asset.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let updated = sqlx::query_as::<_, Asset>(
+        "UPDATE assets SET file_name = ?, original_name = ?, relative_path = ?, file_type = ?, mime_type = ?,
+            file_size = ?, file_hash = ?, width = ?, height = ?, duration_ms = ?, thumbnail_path = ?,
+            updated_at = datetime('now')
+         WHERE id = ? RETURNING *",
+    )
+    .bind(&asset.file_name)
+    .bind(&asset.original_name)
+    .bind(&asset.relative_path)
+    .bind(&asset.file_type)
+    .bind(&asset.mime_type)
+    .bind(asset.file_size)
+    .bind(&asset.file_hash)
+    .bind(asset.width)
+    .bind(asset.height)
+    .bind(asset.duration_ms)
+    .bind(&asset.thumbnail_path)
+    .bind(&asset.id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+pub async fn get_asset_versions(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<AssetVersion>, sqlx::Error> {
+    sqlx::query_as::<_, AssetVersion>(
+        "SELECT * FROM asset_versions WHERE asset_id = ? ORDER BY version_number DESC",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_asset_version(pool: &SqlitePool, id: &str) -> Result<AssetVersion, sqlx::Error> {
+    sqlx::query_as::<_, AssetVersion>("SELECT * FROM asset_versions WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+// --- Asset derivation queries ---
+
+/// Record that `asset_id` was produced by `operation` from `sources` (ID and file name, in order)
+pub async fn insert_asset_derivation(
+    pool: &SqlitePool,
+    asset_id: &str,
+    operation: &str,
+    params: &serde_json::Value,
+    batch_id: &str,
+    output_index: i64,
+    sources: &[(String, String)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let id = Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO asset_derivations (id, asset_id, operation, params, batch_id, output_index) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(asset_id)
+    .bind(operation)
+    .bind(sqlx::types::Json(params))
+    .bind(batch_id)
+    .bind(output_index)
+    .execute(&mut *tx)
+    .await?;
+
+    for (position, (source_id, source_name)) in sources.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO asset_derivation_sources (derivation_id, position, source_asset_id, source_name) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(position as i64)
+        .bind(source_id)
+        .bind(source_name)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn get_asset_derivation(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<AssetDerivation>, sqlx::Error> {
+    sqlx::query_as::<_, AssetDerivation>("SELECT * FROM asset_derivations WHERE asset_id = ?")
+        .bind(asset_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn get_derivation_sources(
+    pool: &SqlitePool,
+    derivation_id: &str,
+) -> Result<Vec<DerivationSource>, sqlx::Error> {
+    sqlx::query_as::<_, DerivationSource>(
+        "SELECT position, source_asset_id, source_name FROM asset_derivation_sources
+         WHERE derivation_id = ? ORDER BY position",
+    )
+    .bind(derivation_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// All outputs created by the same processing run, in output order
+pub async fn get_derivation_batch(
+    pool: &SqlitePool,
+    batch_id: &str,
+) -> Result<Vec<AssetDerivation>, sqlx::Error> {
+    sqlx::query_as::<_, AssetDerivation>(
+        "SELECT * FROM asset_derivations WHERE batch_id = ? ORDER BY output_index",
+    )
+    .bind(batch_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Assets produced directly from `asset_id`, oldest first
+pub async fn get_asset_derivatives(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<DerivedAsset>, sqlx::Error> {
+    sqlx::query_as::<_, DerivedAsset>(
+        "SELECT DISTINCT a.*, d.operation, d.params, d.created_at AS derived_at, d.output_index
+         FROM asset_derivation_sources s
+         JOIN asset_derivations d ON d.id = s.derivation_id
+         JOIN assets a ON a.id = d.asset_id
+         WHERE s.source_asset_id = ?
+         ORDER BY derived_at, d.output_index",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+// --- Custom field queries ---
+
+pub async fn get_custom_fields(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<CustomField>, sqlx::Error> {
+    sqlx::query_as::<_, CustomField>(
+        "SELECT * FROM custom_fields WHERE library_id = ? ORDER BY position, name",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_custom_field(pool: &SqlitePool, id: &str) -> Result<CustomField, sqlx::Error> {
+    sqlx::query_as::<_, CustomField>("SELECT * FROM custom_fields WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Create a field at the end of the library's field list
+pub async fn create_custom_field(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    field_type: &str,
+    options: &[String],
+) -> Result<CustomField, sqlx::Error> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query_as::<_, CustomField>(
+        "INSERT INTO custom_fields (id, library_id, name, field_type, options, position)
+         VALUES (?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position), -1) + 1 FROM custom_fields WHERE library_id = ?))
+         RETURNING *",
+    )
+    .bind(&id)
+    .bind(library_id)
+    .bind(name)
+    .bind(field_type)
+    .bind(sqlx::types::Json(options))
+    .bind(library_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn update_custom_field(
+    pool: &SqlitePool,
+    id: &str,
+    name: &str,
+    options: &[String],
+    position: i64,
+) -> Result<CustomField, sqlx::Error> {
+    sqlx::query_as::<_, CustomField>(
+        "UPDATE custom_fields SET name = ?, options = ?, position = ? WHERE id = ? RETURNING *",
+    )
+    .bind(name)
+    .bind(sqlx::types::Json(options))
+    .bind(position)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn delete_custom_field(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM custom_fields WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Set a field value on an asset; `None` for both values removes it
+pub async fn set_asset_field_value(
+    pool: &SqlitePool,
+    asset_id: &str,
+    field_id: &str,
+    text_value: Option<&str>,
+    number_value: Option<f64>,
+) -> Result<(), sqlx::Error> {
+    if text_value.is_none() && number_value.is_none() {
+        sqlx::query("DELETE FROM asset_field_values WHERE asset_id = ? AND field_id = ?")
+            .bind(asset_id)
+            .bind(field_id)
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO asset_field_values (asset_id, field_id, text_value, number_value) VALUES (?, ?, ?, ?)
+         ON CONFLICT(asset_id, field_id) DO UPDATE SET
+            text_value = excluded.text_value,
+            number_value = excluded.number_value,
+            updated_at = datetime('now')",
+    )
+    .bind(asset_id)
+    .bind(field_id)
+    .bind(text_value)
+    .bind(number_value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Set (or with both values `None`, clear) a custom field value on several assets in one statement
+pub async fn set_assets_field_value(
+    pool: &SqlitePool,
+    asset_ids: &[String],
+    field_id: &str,
+    text_value: Option<&str>,
+    number_value: Option<f64>,
+) -> Result<(), sqlx::Error> {
+    if text_value.is_none() && number_value.is_none() {
+        sqlx::query(
+            "DELETE FROM asset_field_values WHERE field_id = ? AND asset_id IN (SELECT value FROM json_each(?))",
+        )
+        .bind(field_id)
+        .bind(sqlx::types::Json(asset_ids))
+        .execute(pool)
+        .await?;
+        return Ok(());
+    }
+
+    // `WHERE true` keeps SQLite from reading ON CONFLICT as part of the SELECT
+    sqlx::query(
+        "INSERT INTO asset_field_values (asset_id, field_id, text_value, number_value)
+         SELECT value, ?, ?, ? FROM json_each(?) WHERE true
+         ON CONFLICT(asset_id, field_id) DO UPDATE SET
+            text_value = excluded.text_value,
+            number_value = excluded.number_value,
+            updated_at = datetime('now')",
+    )
+    .bind(field_id)
+    .bind(text_value)
+    .bind(number_value)
+    .bind(sqlx::types::Json(asset_ids))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Field values set on an asset, in field order
+pub async fn get_asset_field_values(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<AssetFieldValue>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String, String, Option<String>, Option<f64>)>(
+        "SELECT f.id, f.name, f.field_type, v.text_value, v.number_value
+         FROM asset_field_values v
+         JOIN custom_fields f ON f.id = v.field_id
+         WHERE v.asset_id = ?
+         ORDER BY f.position, f.name",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(field_id, name, field_type, text_value, number_value)| {
+            let value = match (field_type.as_str(), number_value) {
+                ("boolean", Some(n)) => serde_json::Value::Bool(n != 0.0),
+                ("number", Some(n)) => serde_json::json!(n),
+                _ => text_value.map(serde_json::Value::String).unwrap_or_default(),
+            };
+            AssetFieldValue {
+                field_id,
+                name,
+                field_type,
+                value,
+            }
+        })
+        .collect())
+}
+
+// --- Asset link queries ---
+
+pub async fn insert_asset_link(
+    pool: &SqlitePool,
+    asset_id: &str,
+    target_asset_id: Option<&str>,
+    link_type: &str,
+    reference_path: &str,
+    reference_hash: &str,
+) -> Result<(), sqlx::Error> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO asset_links (id, asset_id, target_asset_id, link_type, reference_path, reference_hash) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(asset_id)
+    .bind(target_asset_id)
+    .bind(link_type)
+    .bind(reference_path)
+    .bind(reference_hash)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Links where the asset is either the referencing or the referenced side
+pub async fn get_asset_links(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<AssetLink>, sqlx::Error> {
+    sqlx::query_as::<_, AssetLink>(
+        "SELECT * FROM asset_links WHERE asset_id = ? OR target_asset_id = ? ORDER BY created_at, reference_path",
+    )
+    .bind(asset_id)
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Point unresolved links with a matching file hash at a newly imported asset
+pub async fn resolve_asset_links(
+    pool: &SqlitePool,
+    library_id: &str,
+    target_asset_id: &str,
+    file_hash: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE asset_links SET target_asset_id = ?
+         WHERE target_asset_id IS NULL AND reference_hash = ? AND reference_hash != ''
+           AND asset_id IN (SELECT id FROM assets WHERE library_id = ?)",
+    )
+    .bind(target_asset_id)
+    .bind(file_hash)
+    .bind(library_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn find_asset_by_hash(
+    pool: &SqlitePool,
+    library_id: &str,
+    file_hash: &str,
+) -> Result<Option<Asset>, sqlx::Error> {
+    sqlx::query_as::<_, Asset>(
+        "SELECT * FROM assets WHERE library_id = ? AND file_hash = ? ORDER BY imported_at LIMIT 1",
+    )
+    .bind(library_id)
+    .bind(file_hash)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn find_asset_by_original_name(
+    pool: &SqlitePool,
+    library_id: &str,
+    original_name: &str,
+) -> Result<Option<Asset>, sqlx::Error> {
+    sqlx::query_as::<_, Asset>(
+        "SELECT * FROM assets WHERE library_id = ? AND original_name = ? ORDER BY imported_at LIMIT 1",
+    )
+    .bind(library_id)
+    .bind(original_name)
+    .fetch_optional(pool)
+    .await
+}
+
+// --- Collection queries ---
+
+/// Append the conditions selecting a collection's assets: its members for manual
+/// collections, the evaluated query for smart ones
+fn push_collection_conditions(
+    collection: &Collection,
+    conditions: &mut Vec<String>,
+    binds: &mut Vec<SqlValue>,
+) {
+    conditions.push("library_id = ?".to_string());
+    binds.push(SqlValue::Text(collection.library_id.clone()));
+
+    let Some(query) = collection.query.as_ref().filter(|_| collection.kind == "smart") else {
+        conditions.push(
+            "assets.id IN (SELECT asset_id FROM collection_assets WHERE collection_id = ?)".to_string(),
+        );
+        binds.push(SqlValue::Text(collection.id.clone()));
+        return;
+    };
+    push_query_conditions(&query.0, conditions, binds);
+}
+
+/// Append the conditions of an asset query (as stored by smart collections)
+fn push_query_conditions(
+    query: &SmartCollectionQuery,
+    conditions: &mut Vec<String>,
+    binds: &mut Vec<SqlValue>,
+) {
+    if let Some(text) = query.text.as_deref().filter(|t| !t.trim().is_empty()) {
+        push_text_condition(text, conditions, binds);
+    }
+    if !query.tag_ids.is_empty() {
+        let placeholders = vec!["?"; query.tag_ids.len()].join(",");
+        if query.match_all_tags {
+            conditions.push(format!(
+                "assets.id IN (SELECT asset_id FROM asset_tags WHERE tag_id IN ({}) GROUP BY asset_id HAVING COUNT(DISTINCT tag_id) = ?)",
+                placeholders
+            ));
+            binds.extend(query.tag_ids.iter().map(|t| SqlValue::Text(t.clone())));
+            let distinct_tags = query.tag_ids.iter().collect::<std::collections::HashSet<_>>().len();
+            binds.push(SqlValue::Int(distinct_tags as i64));
+        } else {
+            conditions.push(format!(
+                "assets.id IN (SELECT asset_id FROM asset_tags WHERE tag_id IN ({}))",
+                placeholders
+            ));
+            binds.extend(query.tag_ids.iter().map(|t| SqlValue::Text(t.clone())));
+        }
+    }
+    if let Some(ft) = &query.file_type {
+        conditions.push("file_type = ?".to_string());
+        binds.push(SqlValue::Text(ft.clone()));
+    }
+    if let Some(folder) = &query.folder_path {
+        if !query.include_subfolders {
+            conditions.push("folder_path = ?".to_string());
+            binds.push(SqlValue::Text(folder.clone()));
+        } else if folder != "/" {
+            conditions.push(
+                "(folder_path = ? OR substr(folder_path, 1, length(?) + 1) = ? || '/')".to_string(),
+            );
+            for _ in 0..3 {
+                binds.push(SqlValue::Text(folder.clone()));
+            }
+        }
+    }
+    for (column, op, value) in [
+        ("width", ">=", query.min_width),
+        ("width", "<=", query.max_width),
+        ("height", ">=", query.min_height),
+        ("height", "<=", query.max_height),
+    ] {
+        if let Some(v) = value {
+            conditions.push(format!("{} {} ?", column, op));
+            binds.push(SqlValue::Int(v));
+        }
+    }
+    push_asset_filter(&query.filter, "assets.id", conditions, binds);
+}
+
+/// IDs of all assets of a library matching a query, oldest first
+pub async fn get_query_asset_ids(
+    pool: &SqlitePool,
+    library_id: &str,
+    query: &SmartCollectionQuery,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut conditions = vec!["library_id = ?".to_string()];
+    let mut binds = vec![SqlValue::Text(library_id.to_string())];
+    push_query_conditions(query, &mut conditions, &mut binds);
+    let sql = format!(
+        "SELECT id FROM assets WHERE {} ORDER BY imported_at, id",
+        conditions.join(" AND ")
+    );
+    bind_values!(sqlx::query_scalar::<_, String>(&sql), &binds)
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn get_collection(pool: &SqlitePool, id: &str) -> Result<Collection, sqlx::Error> {
+    sqlx::query_as::<_, Collection>("SELECT * FROM collections WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn get_collection_by_name(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+) -> Result<Option<Collection>, sqlx::Error> {
+    sqlx::query_as::<_, Collection>("SELECT * FROM collections WHERE library_id = ? AND name = ?")
+        .bind(library_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn count_collection_assets(
+    pool: &SqlitePool,
+    collection: &Collection,
+) -> Result<i64, sqlx::Error> {
+    let mut conditions = Vec::new();
+    let mut binds = Vec::new();
+    push_collection_conditions(collection, &mut conditions, &mut binds);
+    let sql = format!("SELECT COUNT(*) FROM assets WHERE {}", conditions.join(" AND "));
+    bind_values!(sqlx::query_scalar::<_, i64>(&sql), &binds)
+        .fetch_one(pool)
+        .await
+}
+
+/// Collections of a library by name, with their (evaluated) asset counts
+pub async fn get_collections(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<CollectionInfo>, sqlx::Error> {
+    let collections = sqlx::query_as::<_, Collection>(
+        "SELECT * FROM collections WHERE library_id = ? ORDER BY name COLLATE NOCASE",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut infos = Vec::with_capacity(collections.len());
+    for collection in collections {
+        let asset_count = count_collection_assets(pool, &collection).await?;
+        infos.push(CollectionInfo {
+            collection,
+            asset_count,
+        });
+    }
+    Ok(infos)
+}
+
+pub async fn create_collection(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    description: &str,
+    query: Option<&SmartCollectionQuery>,
+) -> Result<Collection, sqlx::Error> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query_as::<_, Collection>(
+        "INSERT INTO collections (id, library_id, name, description, kind, query) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+    )
+    .bind(&id)
+    .bind(library_id)
+    .bind(name)
+    .bind(description)
+    .bind(if query.is_some() { "smart" } else { "manual" })
+    .bind(query.map(sqlx::types::Json))
+    .fetch_one(pool)
+    .await
+}
+
+/// Update a collection's name, description and (for smart collections) query
+pub async fn update_collection(
+    pool: &SqlitePool,
+    id: &str,
+    name: &str,
+    description: &str,
+    query: Option<&SmartCollectionQuery>,
+) -> Result<Collection, sqlx::Error> {
+    sqlx::query_as::<_, Collection>(
+        "UPDATE collections SET name = ?, description = ?, query = CASE WHEN kind = 'smart' THEN ? ELSE NULL END,
+            updated_at = datetime('now')
+         WHERE id = ? RETURNING *",
+    )
+    .bind(name)
+    .bind(description)
+    .bind(query.map(sqlx::types::Json))
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn delete_collection(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM collections WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Append assets to a manual collection, skipping ones already in it
+pub async fn add_collection_assets(
+    pool: &SqlitePool,
+    collection_id: &str,
+    asset_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for asset_id in asset_ids {
+        sqlx::query(
+            "INSERT OR IGNORE INTO collection_assets (collection_id, asset_id, position)
+             VALUES (?1, ?2, (SELECT COALESCE(MAX(position), -1) + 1 FROM collection_assets WHERE collection_id = ?1))",
+        )
+        .bind(collection_id)
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query("UPDATE collections SET updated_at = datetime('now') WHERE id = ?")
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn remove_collection_assets(
+    pool: &SqlitePool,
+    collection_id: &str,
+    asset_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for asset_id in asset_ids {
+        sqlx::query("DELETE FROM collection_assets WHERE collection_id = ? AND asset_id = ?")
+            .bind(collection_id)
+            .bind(asset_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    sqlx::query("UPDATE collections SET updated_at = datetime('now') WHERE id = ?")
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Put `asset_ids` first, in the given order; the remaining members keep their relative order after them
+pub async fn reorder_collection_assets(
+    pool: &SqlitePool,
+    collection_id: &str,
+    asset_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE collection_assets SET position = position + ? WHERE collection_id = ?")
+        .bind(asset_ids.len() as i64)
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await?;
+    for (position, asset_id) in asset_ids.iter().enumerate() {
+        sqlx::query("UPDATE collection_assets SET position = ? WHERE collection_id = ? AND asset_id = ?")
+            .bind(position as i64)
+            .bind(collection_id)
+            .bind(asset_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// One page of a collection's assets. Manual collections can also be sorted by "position".
+pub async fn get_collection_assets(
+    pool: &SqlitePool,
+    collection: &Collection,
+    page: u32,
+    page_size: u32,
+    sort_by: &str,
+    sort_order: &str,
+    cursor: Option<&AssetCursor>,
+) -> Result<PaginatedAssets, sqlx::Error> {
+    let mut conditions = Vec::new();
+    let mut binds = Vec::new();
+    push_collection_conditions(collection, &mut conditions, &mut binds);
+
+    let mut order_binds = Vec::new();
+    let order_column = if sort_by == "position" && collection.kind == "manual" {
+        order_binds.push(SqlValue::Text(collection.id.clone()));
+        "(SELECT position FROM collection_assets WHERE collection_id = ? AND asset_id = assets.id)"
+    } else {
+        asset_order_column(sort_by, &mut order_binds)
+    };
+
+    fetch_asset_page(
+        pool,
+        &conditions,
+        &binds,
+        order_column,
+        &order_binds,
+        sort_order,
+        page,
+        page_size,
+        cursor,
+    )
+    .await
+}
+
+/// Manual collections an asset belongs to
+pub async fn get_asset_collections(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<Collection>, sqlx::Error> {
+    sqlx::query_as::<_, Collection>(
+        "SELECT c.* FROM collections c
+         INNER JOIN collection_assets ca ON ca.collection_id = c.id
+         WHERE ca.asset_id = ?
+         ORDER BY c.name COLLATE NOCASE",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+// --- Tag queries ---
+
+/// Create a tag, below `parent_id` if given
+pub async fn create_tag(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    color: &str,
+    category: &str,
+    is_ai: bool,
+    parent_id: Option<&str>,
+) -> Result<Tag, sqlx::Error> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query_as::<_, Tag>(
+        "INSERT INTO tags (id, library_id, name, color, category, is_ai, parent_id, path)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, COALESCE((SELECT path || '/' FROM tags WHERE id = ?7), '') || ?3)
+         RETURNING *",
+    )
+    .bind(&id)
+    .bind(library_id)
+    .bind(name)
+    .bind(color)
+    .bind(category)
+    .bind(is_ai)
+    .bind(parent_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_tag(pool: &SqlitePool, id: &str) -> Result<Tag, sqlx::Error> {
+    sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Look up a tag by path, ignoring the case of Latin letters and preferring an exact match
+pub async fn get_tag_by_path(
+    pool: &SqlitePool,
+    library_id: &str,
+    path: &str,
+) -> Result<Option<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT * FROM tags WHERE library_id = ?1 AND path = ?2 COLLATE NOCASE ORDER BY path = ?2 DESC LIMIT 1",
+    )
+    .bind(library_id)
+    .bind(path)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Find or create the tag at `name`, which may be a path like "character/enemy/boss";
+/// missing parent tags are created along the way. A name that is an alias resolves to
+/// its canonical tag.
+pub async fn get_or_create_tag(
+    pool: &SqlitePool,
+    library_id: &str,
+    name: &str,
+    is_ai: bool,
+) -> Result<Tag, sqlx::Error> {
+    let mut segments: Vec<&str> = name.split('/').map(str::trim).filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        segments.push(name);
+    }
+    if let Some(tag) = get_tag_by_path(pool, library_id, &segments.join("/")).await? {
+        return Ok(tag);
+    }
+    if let Some(tag) = get_tag_by_alias(pool, library_id, name.trim()).await? {
+        return Ok(tag);
+    }
+
+    let mut tag: Option<Tag> = None;
+    for (i, segment) in segments.iter().enumerate() {
+        let path = segments[..=i].join("/");
+        tag = Some(match get_tag_by_path(pool, library_id, &path).await? {
+            Some(existing) => existing,
+            None => {
+                let parent_id = tag.as_ref().map(|p| p.id.as_str());
+                create_tag(pool, library_id, segment, "#808080", "", is_ai, parent_id).await?
+            }
+        });
+    }
+    tag.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Tags of a library ordered by path, with direct asset counts and counts rolled up
+/// over each tag's subtree
+pub async fn list_tags(
+    pool: &SqlitePool,
+    library_id: &str,
+    category: Option<&str>,
+) -> Result<Vec<TagWithCount>, sqlx::Error> {
+    let sql = format!(
+        "WITH RECURSIVE subtree(root_id, tag_id) AS (
+            SELECT id, id FROM tags WHERE library_id = ?
+            UNION ALL
+            SELECT s.root_id, t.id FROM tags t INNER JOIN subtree s ON t.parent_id = s.tag_id
+         ),
+         counts AS (
+            SELECT s.root_id,
+                COUNT(DISTINCT CASE WHEN at.tag_id = s.root_id THEN at.asset_id END) AS asset_count,
+                COUNT(DISTINCT at.asset_id) AS total_count
+            FROM subtree s INNER JOIN asset_tags at ON at.tag_id = s.tag_id
+            GROUP BY s.root_id
+         )
+         SELECT t.*, COALESCE(c.asset_count, 0) AS asset_count, COALESCE(c.total_count, 0) AS total_count
+         FROM tags t
+         LEFT JOIN counts c ON c.root_id = t.id
+         WHERE t.library_id = ?{}
+         ORDER BY t.path",
+        if category.is_some() { " AND t.category = ?" } else { "" }
+    );
+
+    let mut query = sqlx::query_as::<_, TagWithCount>(&sql)
+        .bind(library_id)
+        .bind(library_id);
+    if let Some(cat) = category {
+        query = query.bind(cat);
+    }
+    query.fetch_all(pool).await
+}
+
+/// Rename a tag, updating the paths of the tags below it
+pub async fn rename_tag(pool: &SqlitePool, id: &str, new_name: &str) -> Result<(), sqlx::Error> {
+    let tag = get_tag(pool, id).await?;
+    let new_path = match tag.path.rsplit_once('/') {
+        Some((parent_path, _)) => format!("{}/{}", parent_path, new_name),
+        None => new_name.to_string(),
+    };
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE tags SET name = ? WHERE id = ?")
+        .bind(new_name)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    update_tag_subtree_paths(&mut tx, &tag.library_id, &tag.path, &new_path).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Move a tag and everything below it under `parent` (or to the top level with `None`)
+pub async fn move_tag(pool: &SqlitePool, tag: &Tag, parent: Option<&Tag>) -> Result<(), sqlx::Error> {
+    let new_path = match parent {
+        Some(parent) => format!("{}/{}", parent.path, tag.name),
+        None => tag.name.clone(),
+    };
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE tags SET parent_id = ? WHERE id = ?")
+        .bind(parent.map(|p| p.id.as_str()))
+        .bind(&tag.id)
+        .execute(&mut *tx)
+        .await?;
+    update_tag_subtree_paths(&mut tx, &tag.library_id, &tag.path, &new_path).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Replace the `old_path` prefix of a tag and its descendants with `new_path`
+async fn update_tag_subtree_paths(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    library_id: &str,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE tags SET path = ?1 || substr(path, length(?2) + 1)
+         WHERE library_id = ?3 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')",
+    )
+    .bind(new_path)
+    .bind(old_path)
+    .bind(library_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Merge `sources` into `target` in one transaction: their assets get the target tag,
+/// their child tags move below the target, their aliases point to it and, with
+/// `keep_aliases`, their paths become aliases of it. The sources are then deleted.
+pub async fn merge_tags(
+    pool: &SqlitePool,
+    target: &Tag,
+    sources: &[Tag],
+    keep_aliases: bool,
+) -> Result<(), sqlx::Error> {
+    let source_ids: Vec<&str> = sources.iter().map(|t| t.id.as_str()).collect();
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO asset_tags (asset_id, tag_id, confidence, created_at)
+         SELECT asset_id, ?, confidence, created_at FROM asset_tags WHERE tag_id IN (SELECT value FROM json_each(?))",
+    )
+    .bind(&target.id)
+    .bind(sqlx::types::Json(&source_ids))
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE tag_aliases SET tag_id = ? WHERE tag_id IN (SELECT value FROM json_each(?))")
+        .bind(&target.id)
+        .bind(sqlx::types::Json(&source_ids))
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE tags SET parent_id = ? WHERE parent_id IN (SELECT value FROM json_each(?))")
+        .bind(&target.id)
+        .bind(sqlx::types::Json(&source_ids))
+        .execute(&mut *tx)
+        .await?;
+    for source in sources {
+        sqlx::query(
+            "UPDATE tags SET path = ?1 || substr(path, length(?2) + 1)
+             WHERE library_id = ?3 AND substr(path, 1, length(?2) + 1) = ?2 || '/'",
+        )
+        .bind(&target.path)
+        .bind(&source.path)
+        .bind(&target.library_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query("DELETE FROM tags WHERE id IN (SELECT value FROM json_each(?))")
+        .bind(sqlx::types::Json(&source_ids))
+        .execute(&mut *tx)
+        .await?;
+
+    if keep_aliases {
+        for source in sources.iter().filter(|s| s.path != target.path) {
+            sqlx::query(
+                "INSERT INTO tag_aliases (library_id, alias, tag_id) VALUES (?, ?, ?)
+                 ON CONFLICT(library_id, alias) DO UPDATE SET tag_id = excluded.tag_id",
+            )
+            .bind(&target.library_id)
+            .bind(&source.path)
+            .bind(&target.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Direct children of a tag
+pub async fn get_child_tags(pool: &SqlitePool, parent_id: &str) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE parent_id = ? ORDER BY path")
+        .bind(parent_id)
+        .fetch_all(pool)
+        .await
+}
+
+// --- Tag alias queries ---
+
+pub async fn get_tag_by_alias(
+    pool: &SqlitePool,
+    library_id: &str,
+    alias: &str,
+) -> Result<Option<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT t.* FROM tags t INNER JOIN tag_aliases a ON a.tag_id = t.id
+         WHERE a.library_id = ? AND a.alias = ?",
+    )
+    .bind(library_id)
+    .bind(alias)
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn get_tag_aliases(pool: &SqlitePool, library_id: &str) -> Result<Vec<TagAlias>, sqlx::Error> {
+    sqlx::query_as::<_, TagAlias>(
+        "SELECT a.*, t.path AS tag_path FROM tag_aliases a INNER JOIN tags t ON t.id = a.tag_id
+         WHERE a.library_id = ?
+         ORDER BY t.path, a.alias",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Add an alias for a tag, re-pointing it if it already resolves to another tag
+pub async fn save_tag_alias(
+    pool: &SqlitePool,
+    library_id: &str,
+    alias: &str,
+    tag_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO tag_aliases (library_id, alias, tag_id) VALUES (?, ?, ?)
+         ON CONFLICT(library_id, alias) DO UPDATE SET tag_id = excluded.tag_id, created_at = datetime('now')",
+    )
+    .bind(library_id)
+    .bind(alias)
+    .bind(tag_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_tag_alias(pool: &SqlitePool, library_id: &str, alias: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM tag_aliases WHERE library_id = ? AND alias = ?")
+        .bind(library_id)
+        .bind(alias)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_tag(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn assign_tags(
+    pool: &SqlitePool,
+    asset_id: &str,
+    tag_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    assign_tags_to_assets(pool, std::slice::from_ref(&asset_id.to_string()), tag_ids).await?;
+    Ok(())
+}
+
+pub async fn remove_tags(
+    pool: &SqlitePool,
+    asset_id: &str,
+    tag_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    remove_tags_from_assets(pool, std::slice::from_ref(&asset_id.to_string()), tag_ids).await?;
+    Ok(())
+}
+
+/// Give every asset every tag in one statement, skipping existing assignments.
+/// Returns the number of new assignments.
+pub async fn assign_tags_to_assets(
+    pool: &SqlitePool,
+    asset_ids: &[String],
+    tag_ids: &[String],
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO asset_tags (asset_id, tag_id)
+         SELECT a.value, t.value FROM json_each(?) a, json_each(?) t",
+    )
+    .bind(sqlx::types::Json(asset_ids))
+    .bind(sqlx::types::Json(tag_ids))
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Remove the tags from all assets in one statement. Returns the number of removed assignments.
+pub async fn remove_tags_from_assets(
+    pool: &SqlitePool,
+    asset_ids: &[String],
+    tag_ids: &[String],
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM asset_tags
+         WHERE asset_id IN (SELECT value FROM json_each(?)) AND tag_id IN (SELECT value FROM json_each(?))",
+    )
+    .bind(sqlx::types::Json(asset_ids))
+    .bind(sqlx::types::Json(tag_ids))
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// The tags with the given IDs; unknown IDs are left out
+pub async fn get_tags_by_ids(pool: &SqlitePool, ids: &[String]) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id IN (SELECT value FROM json_each(?))")
+        .bind(sqlx::types::Json(ids))
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn get_asset_tags(pool: &SqlitePool, asset_id: &str) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT t.* FROM tags t INNER JOIN asset_tags at ON t.id = at.tag_id WHERE at.asset_id = ? ORDER BY t.path",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
 
 // --- Search queries ---
 
+/// Quote user input as a single FTS5 phrase
+fn fts_phrase(query: &str) -> String {
+    format!("\"{}\"", query.replace('"', "\"\""))
+}
+
+/// Append a text search: an FTS5 match, or a tag whose name or path is the text, directly
+/// or through an alias. Tags only reach assets of their own library.
+fn push_text_condition(text: &str, conditions: &mut Vec<String>, binds: &mut Vec<SqlValue>) {
+    conditions.push(
+        "(assets.rowid IN (SELECT rowid FROM assets_fts WHERE assets_fts MATCH ?)
+          OR assets.id IN (SELECT asset_id FROM asset_tags WHERE tag_id IN (
+            SELECT id FROM tags WHERE path = ? COLLATE NOCASE OR name = ? COLLATE NOCASE
+            UNION SELECT tag_id FROM tag_aliases WHERE alias = ?)))"
+            .to_string(),
+    );
+    let term = text.trim().to_string();
+    binds.push(SqlValue::Text(fts_phrase(text)));
+    binds.push(SqlValue::Text(term.clone()));
+    binds.push(SqlValue::Text(term.clone()));
+    binds.push(SqlValue::Text(term));
+}
+
 pub async fn search_keyword(
     pool: &SqlitePool,
     library_id: &str,
     query: &str,
     tag_ids: Option<&[String]>,
+    include_tag_descendants: bool,
     file_type: Option<&str>,
+    filter: &AssetFilter,
     page: u32,
     page_size: u32,
+    sort_by: &str,
+    sort_order: &str,
+    cursor: Option<&AssetCursor>,
 ) -> Result<PaginatedAssets, sqlx::Error> {
-    let offset = (page.saturating_sub(1)) * page_size;
+    let mut order_binds = Vec::new();
+    let order_column = asset_order_column(sort_by, &mut order_binds);
+
+    let mut conditions = vec!["library_id = ?".to_string()];
+    let mut binds = vec![SqlValue::Text(library_id.to_string())];
+    push_text_condition(query, &mut conditions, &mut binds);
+    if let Some(ft) = file_type {
+        conditions.push("file_type = ?".to_string());
+        binds.push(SqlValue::Text(ft.to_string()));
+    }
+    if let Some(tags) = tag_ids.filter(|t| !t.is_empty()) {
+        push_tags_condition(tags, include_tag_descendants, &mut conditions, &mut binds);
+    }
+    push_asset_filter(filter, "assets.id", &mut conditions, &mut binds);
 
-    // Use FTS5 for text search
-    let fts_query = format!("\"{}\"", query.replace('"', "\"\""));
+    fetch_asset_page(
+        pool,
+        &conditions,
+        &binds,
+        order_column,
+        &order_binds,
+        sort_order,
+        page,
+        page_size,
+        cursor,
+    )
+    .await
+}
 
-    let base_sql = if let Some(tags) = tag_ids {
-        if tags.is_empty() {
-            format!(
-                "SELECT a.* FROM assets a
-                 INNER JOIN assets_fts f ON a.rowid = f.rowid
-                 WHERE a.library_id = ? AND assets_fts MATCH ?{}",
-                if file_type.is_some() {
-                    " AND a.file_type = ?"
-                } else {
-                    ""
-                }
-            )
-        } else {
-            let placeholders: Vec<&str> = tags.iter().map(|_| "?").collect();
-            format!(
-                "SELECT DISTINCT a.* FROM assets a
-                 INNER JOIN assets_fts f ON a.rowid = f.rowid
-                 INNER JOIN asset_tags at ON a.id = at.asset_id
-                 WHERE a.library_id = ? AND assets_fts MATCH ? AND at.tag_id IN ({}){}",
-                placeholders.join(","),
-                if file_type.is_some() {
-                    " AND a.file_type = ?"
-                } else {
-                    ""
-                }
-            )
-        }
+/// Append a condition matching assets with any of `tag_ids`, and with
+/// `include_descendants` also any tag below them
+fn push_tags_condition(
+    tag_ids: &[String],
+    include_descendants: bool,
+    conditions: &mut Vec<String>,
+    binds: &mut Vec<SqlValue>,
+) {
+    let tags_sql = if include_descendants {
+        "WITH RECURSIVE subtree(id) AS (
+            SELECT value FROM json_each(?)
+            UNION SELECT t.id FROM tags t INNER JOIN subtree s ON t.parent_id = s.id
+         ) SELECT id FROM subtree"
     } else {
-        format!(
-            "SELECT a.* FROM assets a
-             INNER JOIN assets_fts f ON a.rowid = f.rowid
-             WHERE a.library_id = ? AND assets_fts MATCH ?{}",
-            if file_type.is_some() {
-                " AND a.file_type = ?"
-            } else {
-                ""
-            }
-        )
+        "SELECT value FROM json_each(?)"
     };
+    conditions.push(format!(
+        "assets.id IN (SELECT asset_id FROM asset_tags WHERE tag_id IN ({}))",
+        tags_sql
+    ));
+    binds.push(SqlValue::Text(serde_json::to_string(tag_ids).unwrap_or_default()));
+}
 
-    let count_sql = format!(
-        "SELECT COUNT(*) FROM ({}) sub",
-        base_sql
-    );
-    let data_sql = format!(
-        "{} ORDER BY a.imported_at DESC LIMIT ? OFFSET ?",
-        base_sql
-    );
+/// Assets with any (or with `match_all`, every) of the tags, newest first unless `sort_by`
+/// is given, paginated like `get_assets`. With `include_descendants` a tag also matches
+/// assets tagged with a tag below it.
+pub async fn search_by_tags(
+    pool: &SqlitePool,
+    library_id: &str,
+    tag_ids: &[String],
+    match_all: bool,
+    include_descendants: bool,
+    filter: &AssetFilter,
+    page: u32,
+    page_size: u32,
+    sort_by: &str,
+    sort_order: &str,
+    cursor: Option<&AssetCursor>,
+) -> Result<PaginatedAssets, sqlx::Error> {
+    if tag_ids.is_empty() {
+        return Ok(PaginatedAssets {
+            assets: vec![],
+            total: 0,
+            page,
+            page_size,
+            next_cursor: None,
+        });
+    }
 
-    // For simplicity in this initial version, handle the common case (no tag filter)
-    let total: i64;
-    let assets: Vec<Asset>;
+    let mut order_binds = Vec::new();
+    let order_column = asset_order_column(sort_by, &mut order_binds);
 
-    if tag_ids.is_none() || tag_ids.map(|t| t.is_empty()).unwrap_or(true) {
-        if let Some(ft) = file_type {
-            total = sqlx::query_scalar(&count_sql)
-                .bind(library_id)
-                .bind(&fts_query)
-                .bind(ft)
-                .fetch_one(pool)
-                .await?;
-            assets = sqlx::query_as::<_, Asset>(&data_sql)
-                .bind(library_id)
-                .bind(&fts_query)
-                .bind(ft)
-                .bind(page_size)
-                .bind(offset)
-                .fetch_all(pool)
-                .await?;
-        } else {
-            total = sqlx::query_scalar(&count_sql)
-                .bind(library_id)
-                .bind(&fts_query)
-                .fetch_one(pool)
-                .await?;
-            assets = sqlx::query_as::<_, Asset>(&data_sql)
-                .bind(library_id)
-                .bind(&fts_query)
-                .bind(page_size)
-                .bind(offset)
-                .fetch_all(pool)
-                .await?;
+    let mut conditions = vec!["library_id = ?".to_string()];
+    let mut binds = vec![SqlValue::Text(library_id.to_string())];
+    if match_all {
+        for tag_id in tag_ids {
+            push_tags_condition(std::slice::from_ref(tag_id), include_descendants, &mut conditions, &mut binds);
         }
     } else {
-        // Fallback for tag-filtered search - simplified
-        total = 0;
-        assets = vec![];
+        push_tags_condition(tag_ids, include_descendants, &mut conditions, &mut binds);
     }
+    push_asset_filter(filter, "assets.id", &mut conditions, &mut binds);
 
-    Ok(PaginatedAssets {
-        assets,
-        total,
+    fetch_asset_page(
+        pool,
+        &conditions,
+        &binds,
+        order_column,
+        &order_binds,
+        sort_order,
         page,
         page_size,
-    })
+        cursor,
+    )
+    .await
 }
 
-pub async fn search_by_tags(
+// --- License queries ---
+
+pub async fn get_asset_license(
     pool: &SqlitePool,
-    library_id: &str,
-    tag_ids: &[String],
-    match_all: bool,
-) -> Result<Vec<Asset>, sqlx::Error> {
-    if tag_ids.is_empty() {
-        return Ok(vec![]);
+    asset_id: &str,
+) -> Result<Option<AssetLicense>, sqlx::Error> {
+    sqlx::query_as::<_, AssetLicense>("SELECT * FROM asset_licenses WHERE asset_id = ?")
+        .bind(asset_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Store the licenses of several assets in one transaction
+pub async fn save_asset_licenses(
+    pool: &SqlitePool,
+    licenses: &[AssetLicense],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for license in licenses {
+        sqlx::query(
+            "INSERT OR REPLACE INTO asset_licenses (asset_id, license, author, source_url, attribution, requires_attribution, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, datetime('now'))",
+        )
+        .bind(&license.asset_id)
+        .bind(&license.license)
+        .bind(&license.author)
+        .bind(&license.source_url)
+        .bind(&license.attribution)
+        .bind(license.requires_attribution)
+        .execute(&mut *tx)
+        .await?;
     }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Licenses used in a library with their asset counts, most used first
+pub async fn get_license_usage(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<LicenseUsage>, sqlx::Error> {
+    sqlx::query_as::<_, LicenseUsage>(
+        "SELECT l.license, MAX(l.requires_attribution) AS requires_attribution, COUNT(*) AS asset_count
+         FROM asset_licenses l
+         JOIN assets a ON a.id = l.asset_id
+         WHERE a.library_id = ? AND l.license != ''
+         GROUP BY l.license COLLATE NOCASE
+         ORDER BY asset_count DESC, l.license",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
+}
 
-    let placeholders: Vec<String> = tag_ids.iter().map(|_| "?".to_string()).collect();
-    let ph = placeholders.join(",");
+// --- Comment queries ---
 
-    let sql = if match_all {
-        format!(
-            "SELECT a.* FROM assets a
-             INNER JOIN asset_tags at ON a.id = at.asset_id
-             WHERE a.library_id = ? AND at.tag_id IN ({})
-             GROUP BY a.id
-             HAVING COUNT(DISTINCT at.tag_id) = ?",
-            ph
-        )
+pub async fn insert_comment(
+    pool: &SqlitePool,
+    comment: &AssetComment,
+) -> Result<AssetComment, sqlx::Error> {
+    sqlx::query_as::<_, AssetComment>(
+        "INSERT INTO asset_comments (id, asset_id, parent_id, author, body, anchor_x, anchor_y, anchor_width, anchor_height, anchor_time_ms)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+    )
+    .bind(&comment.id)
+    .bind(&comment.asset_id)
+    .bind(&comment.parent_id)
+    .bind(&comment.author)
+    .bind(&comment.body)
+    .bind(comment.anchor_x)
+    .bind(comment.anchor_y)
+    .bind(comment.anchor_width)
+    .bind(comment.anchor_height)
+    .bind(comment.anchor_time_ms)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_comment(pool: &SqlitePool, id: &str) -> Result<AssetComment, sqlx::Error> {
+    sqlx::query_as::<_, AssetComment>("SELECT * FROM asset_comments WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Comments and replies of an asset, oldest first
+pub async fn get_asset_comments(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Vec<AssetComment>, sqlx::Error> {
+    sqlx::query_as::<_, AssetComment>(
+        "SELECT * FROM asset_comments WHERE asset_id = ? ORDER BY created_at, rowid",
+    )
+    .bind(asset_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn update_comment_body(
+    pool: &SqlitePool,
+    id: &str,
+    body: &str,
+) -> Result<AssetComment, sqlx::Error> {
+    sqlx::query_as::<_, AssetComment>(
+        "UPDATE asset_comments SET body = ?, updated_at = datetime('now') WHERE id = ? RETURNING *",
+    )
+    .bind(body)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn set_comment_resolved(
+    pool: &SqlitePool,
+    id: &str,
+    resolved: bool,
+) -> Result<AssetComment, sqlx::Error> {
+    sqlx::query_as::<_, AssetComment>(
+        "UPDATE asset_comments
+         SET resolved = ?1, resolved_at = CASE WHEN ?1 THEN datetime('now') ELSE NULL END, updated_at = datetime('now')
+         WHERE id = ?2 RETURNING *",
+    )
+    .bind(resolved)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete a comment with its replies
+pub async fn delete_comment(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM asset_comments WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Comments of a library whose text or author matches the query, newest first.
+/// Without `include_resolved`, comments in resolved threads are skipped.
+pub async fn search_comments(
+    pool: &SqlitePool,
+    library_id: &str,
+    query: &str,
+    include_resolved: bool,
+    limit: u32,
+) -> Result<Vec<AssetComment>, sqlx::Error> {
+    let resolved_clause = if include_resolved {
+        ""
     } else {
-        format!(
-            "SELECT DISTINCT a.* FROM assets a
-             INNER JOIN asset_tags at ON a.id = at.asset_id
-             WHERE a.library_id = ? AND at.tag_id IN ({})",
-            ph
-        )
+        " AND c.resolved = 0 AND NOT EXISTS (
+             SELECT 1 FROM asset_comments p WHERE p.id = c.parent_id AND p.resolved = 1)"
     };
+    let sql = format!(
+        "SELECT c.* FROM asset_comments c
+         INNER JOIN asset_comments_fts f ON c.rowid = f.rowid
+         INNER JOIN assets a ON a.id = c.asset_id
+         WHERE a.library_id = ? AND asset_comments_fts MATCH ?{}
+         ORDER BY c.created_at DESC
+         LIMIT ?",
+        resolved_clause
+    );
+    sqlx::query_as::<_, AssetComment>(&sql)
+        .bind(library_id)
+        .bind(fts_phrase(query))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
 
-    let mut query = sqlx::query_as::<_, Asset>(&sql).bind(library_id);
-    for tag_id in tag_ids {
-        query = query.bind(tag_id);
-    }
-    if match_all {
-        query = query.bind(tag_ids.len() as i64);
-    }
+// --- AI name suggestion queries ---
 
-    query.fetch_all(pool).await
+/// Store the latest suggested name of an asset as pending review
+pub async fn save_ai_name_suggestion(
+    pool: &SqlitePool,
+    asset_id: &str,
+    suggested_name: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO ai_name_suggestions (asset_id, suggested_name) VALUES (?, ?)
+         ON CONFLICT(asset_id) DO UPDATE SET suggested_name = excluded.suggested_name, status = 'pending', created_at = datetime('now')",
+    )
+    .bind(asset_id)
+    .bind(suggested_name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_ai_name_suggestion(
+    pool: &SqlitePool,
+    asset_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>("SELECT suggested_name FROM ai_name_suggestions WHERE asset_id = ?")
+        .bind(asset_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Set the status (pending, accepted or rejected) of an asset's name suggestion
+pub async fn set_ai_name_suggestion_status(
+    pool: &SqlitePool,
+    asset_id: &str,
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE ai_name_suggestions SET status = ? WHERE asset_id = ?")
+        .bind(status)
+        .bind(asset_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Pending name suggestions of a library, oldest first
+pub async fn get_pending_name_suggestions(
+    pool: &SqlitePool,
+    library_id: &str,
+) -> Result<Vec<NameSuggestion>, sqlx::Error> {
+    sqlx::query_as::<_, NameSuggestion>(
+        "SELECT s.asset_id, a.file_name, a.folder_path, s.suggested_name, s.created_at
+         FROM ai_name_suggestions s
+         JOIN assets a ON a.id = s.asset_id
+         WHERE a.library_id = ? AND s.status = 'pending'
+         ORDER BY s.created_at, a.file_name",
+    )
+    .bind(library_id)
+    .fetch_all(pool)
+    .await
 }
 
 // --- AI Config queries ---
@@ -611,21 +2888,3 @@ pub async fn get_all_embeddings(
     .fetch_all(pool)
     .await
 }
-
-/// Update folder path for all assets when a folder is renamed
-pub async fn update_folder_path(
-    pool: &SqlitePool,
-    library_id: &str,
-    old_path: &str,
-    new_path: &str,
-) -> Result<(), sqlx::Error> {
-    sqlx::query(
-        "UPDATE assets SET folder_path = ? WHERE library_id = ? AND folder_path = ?",
-    )
-    .bind(new_path)
-    .bind(library_id)
-    .bind(old_path)
-    .execute(pool)
-    .await?;
-    Ok(())
-}
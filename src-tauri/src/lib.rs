@@ -146,35 +146,99 @@ pub fn run() {
             commands::library::create_library,
             commands::library::list_libraries,
             commands::library::get_library,
+            commands::library::set_library_auto_apply_ai_names,
             commands::library::delete_library,
             commands::library::delete_library_with_files,
             // Asset commands
             commands::asset::import_assets,
             commands::asset::get_assets,
             commands::asset::get_asset_detail,
+            commands::asset::refresh_image_metadata,
+            commands::asset::refresh_color_palettes,
             commands::asset::rename_asset,
             commands::asset::update_description,
+            commands::asset::bulk_update_description,
+            commands::asset::set_asset_rating,
+            commands::asset::set_asset_color_label,
+            commands::asset::set_asset_favorite,
             commands::asset::delete_assets,
             commands::asset::move_assets,
-            commands::asset::get_folders,
+            commands::rename::batch_rename,
+            commands::export::export_assets,
             commands::asset::get_asset_file_path,
             commands::asset::get_thumbnail_path,
             commands::asset::get_thumbnail_data,
-            commands::asset::create_folder,
-            commands::asset::rename_folder,
+            // Folder commands
+            commands::folder::get_folders,
+            commands::folder::create_folder,
+            commands::folder::update_folder,
+            commands::folder::rename_folder,
+            commands::folder::move_folder,
+            commands::folder::get_folder_delete_summary,
+            commands::folder::delete_folder,
+            // Version commands
+            commands::version::replace_asset_file,
+            commands::version::list_asset_versions,
+            commands::version::get_version_thumbnail_data,
+            commands::version::get_version_file_path,
+            commands::version::revert_asset_version,
+            // Collection commands
+            commands::collection::get_collections,
+            commands::collection::create_collection,
+            commands::collection::update_collection,
+            commands::collection::delete_collection,
+            commands::collection::add_to_collection,
+            commands::collection::remove_from_collection,
+            commands::collection::reorder_collection,
+            commands::collection::get_collection_assets,
+            // Comment commands
+            commands::comment::add_comment,
+            commands::comment::get_asset_comments,
+            commands::comment::update_comment,
+            commands::comment::set_comment_resolved,
+            commands::comment::delete_comment,
+            commands::comment::search_comments,
+            // License commands
+            commands::license::set_asset_license,
+            commands::license::get_license_usage,
+            commands::license::generate_credits,
+            // Usage commands
+            commands::usage::scan_project_usage,
+            commands::usage::get_usage_projects,
+            commands::usage::clear_project_usage,
+            // Custom field commands
+            commands::field::get_custom_fields,
+            commands::field::create_custom_field,
+            commands::field::update_custom_field,
+            commands::field::delete_custom_field,
+            commands::field::set_asset_field_value,
+            commands::field::bulk_set_field_value,
             // Tag commands
             commands::tag::create_tag,
             commands::tag::list_tags,
             commands::tag::rename_tag,
+            commands::tag::move_tag,
             commands::tag::delete_tag,
+            commands::tag::merge_tags,
+            commands::tag::add_tag_alias,
+            commands::tag::remove_tag_alias,
+            commands::tag::get_tag_aliases,
             commands::tag::assign_tags,
             commands::tag::remove_tags,
+            commands::tag::bulk_assign_tags,
+            commands::tag::bulk_remove_tags,
             commands::tag::get_asset_tags,
+            // Bulk commands
+            commands::bulk::bulk_query_operation,
             // Search commands
             commands::search::search_keyword,
             commands::search::search_by_tags,
+            commands::search::search_by_color,
             // AI commands
             commands::ai::ai_tag_asset,
+            commands::ai::get_name_suggestions,
+            commands::ai::accept_name_suggestions,
+            commands::ai::reject_name_suggestions,
             commands::ai::ai_semantic_search,
             commands::ai::save_ai_config,
             commands::ai::get_ai_config,
@@ -184,6 +248,10 @@ pub fn run() {
             commands::processing::merge_spritesheet,
             commands::processing::split_image,
             commands::processing::compress_image,
+            commands::processing::export_aseprite_tag,
+            commands::processing::get_asset_sources,
+            commands::processing::get_asset_derivatives,
+            commands::processing::rerun_processing,
             // Menu commands
             commands::menu::update_menu_language,
         ])
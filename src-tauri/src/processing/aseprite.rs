@@ -0,0 +1,611 @@
+use image::{DynamicImage, RgbaImage};
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::AppError;
+
+const HEADER_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+
+const CHUNK_OLD_PALETTE_256: u16 = 0x0004;
+const CHUNK_OLD_PALETTE_64: u16 = 0x0011;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+
+const LAYER_FLAG_VISIBLE: u16 = 1;
+const LAYER_FLAG_BACKGROUND: u16 = 8;
+const HEADER_FLAG_LAYER_OPACITY: u32 = 1;
+
+/// Indexed sprites have at most 256 colors; larger palette entries mean a corrupt file
+const MAX_PALETTE_SIZE: usize = 256;
+
+/// Upper bound on canvas pixels so a forged header cannot force a huge allocation
+const MAX_CANVAS_PIXELS: u64 = 8192 * 8192;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AsepriteLayer {
+    pub name: String,
+    pub layer_type: String, // "image", "group" or "tilemap"
+    pub child_level: u16,
+    pub visible: bool,
+    pub opacity: u8,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AsepriteTag {
+    pub name: String,
+    pub from: u16,
+    pub to: u16,
+    pub direction: String, // "forward", "reverse", "pingpong" or "pingpong_reverse"
+}
+
+/// Metadata stored for imported Aseprite documents
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AsepriteMetadata {
+    pub color_depth: u16,
+    pub frame_count: usize,
+    pub frame_durations: Vec<u16>,
+    pub total_duration_ms: i64,
+    pub tags: Vec<AsepriteTag>,
+    pub layers: Vec<AsepriteLayer>,
+}
+
+enum CelContent {
+    Image {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    },
+    Linked(u16),
+    Unsupported,
+}
+
+struct Cel {
+    layer_index: u16,
+    x: i32,
+    y: i32,
+    opacity: u8,
+    content: CelContent,
+}
+
+struct Frame {
+    duration_ms: u16,
+    cels: Vec<Cel>,
+}
+
+struct LayerInfo {
+    flags: u16,
+    layer_type: u16,
+    child_level: u16,
+    opacity: u8,
+    name: String,
+}
+
+/// A parsed `.aseprite` / `.ase` document
+pub struct AsepriteFile {
+    pub width: u32,
+    pub height: u32,
+    color_depth: u16,
+    transparent_index: u8,
+    palette: Vec<[u8; 4]>,
+    layers: Vec<LayerInfo>,
+    frames: Vec<Frame>,
+    tags: Vec<AsepriteTag>,
+}
+
+/// Little-endian reader over the raw file bytes
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], AppError> {
+        if self.pos + len > self.data.len() {
+            return Err(AppError::InvalidInput(
+                "Truncated Aseprite file".to_string(),
+            ));
+        }
+        let slice = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), AppError> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, AppError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, AppError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, AppError> {
+        let b = self.bytes(2)?;
+        Ok(i16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, AppError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, AppError> {
+        let len = self.u16()? as usize;
+        Ok(String::from_utf8_lossy(self.bytes(len)?).to_string())
+    }
+}
+
+/// Check whether a path points to an Aseprite document
+pub fn is_aseprite(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| matches!(e.to_lowercase().as_str(), "aseprite" | "ase"))
+        .unwrap_or(false)
+}
+
+/// Open any supported image, rendering the first frame of Aseprite documents
+pub fn open_image(path: &Path) -> Result<DynamicImage, AppError> {
+    if is_aseprite(path) {
+        let file = AsepriteFile::open(path)?;
+        Ok(DynamicImage::ImageRgba8(file.render_frame(0)?))
+    } else {
+        Ok(image::open(path)?)
+    }
+}
+
+impl AsepriteFile {
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, AppError> {
+        let mut r = Reader::new(data);
+
+        // Header (128 bytes)
+        r.skip(4)?; // file size
+        if r.u16()? != HEADER_MAGIC {
+            return Err(AppError::InvalidInput(
+                "Not an Aseprite file".to_string(),
+            ));
+        }
+        let frame_count = r.u16()?;
+        let width = r.u16()? as u32;
+        let height = r.u16()? as u32;
+        let color_depth = r.u16()?;
+        let header_flags = r.u32()?;
+        r.skip(2 + 4 + 4)?; // speed (deprecated) and two reserved dwords
+        let transparent_index = r.u8()?;
+        r.skip(3 + 2 + 1 + 1 + 2 + 2 + 2 + 2 + 84)?;
+
+        if !matches!(color_depth, 8 | 16 | 32) {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported Aseprite color depth: {}",
+                color_depth
+            )));
+        }
+        if width as u64 * height as u64 > MAX_CANVAS_PIXELS {
+            return Err(AppError::InvalidInput(format!(
+                "Aseprite canvas too large: {}x{}",
+                width, height
+            )));
+        }
+
+        let mut file = AsepriteFile {
+            width,
+            height,
+            color_depth,
+            transparent_index,
+            palette: Vec::new(),
+            layers: Vec::new(),
+            frames: Vec::with_capacity(frame_count as usize),
+            tags: Vec::new(),
+        };
+
+        for _ in 0..frame_count {
+            let frame_start = r.pos;
+            let frame_size = r.u32()? as usize;
+            if r.u16()? != FRAME_MAGIC {
+                return Err(AppError::InvalidInput(
+                    "Corrupted Aseprite frame header".to_string(),
+                ));
+            }
+            let old_chunk_count = r.u16()?;
+            let duration_ms = r.u16()?;
+            r.skip(2)?;
+            let new_chunk_count = r.u32()?;
+            let chunk_count = if new_chunk_count == 0 {
+                old_chunk_count as u32
+            } else {
+                new_chunk_count
+            };
+
+            let mut frame = Frame {
+                duration_ms,
+                cels: Vec::new(),
+            };
+
+            for _ in 0..chunk_count {
+                let chunk_start = r.pos;
+                let chunk_size = r.u32()? as usize;
+                let chunk_type = r.u16()?;
+                if chunk_size < 6 {
+                    return Err(AppError::InvalidInput(
+                        "Corrupted Aseprite chunk".to_string(),
+                    ));
+                }
+                let body = r.bytes(chunk_size - 6)?;
+                let mut cr = Reader::new(body);
+
+                match chunk_type {
+                    CHUNK_LAYER => file.layers.push(parse_layer(&mut cr, header_flags)?),
+                    CHUNK_CEL => frame.cels.push(parse_cel(&mut cr, color_depth, width, height)?),
+                    CHUNK_TAGS => file.tags = parse_tags(&mut cr)?,
+                    CHUNK_PALETTE => parse_palette(&mut cr, &mut file.palette)?,
+                    // The new palette chunk supersedes the old ones when present
+                    CHUNK_OLD_PALETTE_256 | CHUNK_OLD_PALETTE_64 if file.palette.is_empty() => {
+                        parse_old_palette(&mut cr, &mut file.palette, chunk_type == CHUNK_OLD_PALETTE_64)?;
+                    }
+                    _ => {}
+                }
+
+                r.pos = chunk_start + chunk_size;
+            }
+
+            // Trust the frame size over the chunk count when skipping to the next frame
+            let frame_end = frame_start + frame_size;
+            if frame_size > 0 && frame_end <= data.len() {
+                r.pos = frame_end;
+            }
+            file.frames.push(frame);
+        }
+
+        Ok(file)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn tags(&self) -> &[AsepriteTag] {
+        &self.tags
+    }
+
+    pub fn metadata(&self) -> AsepriteMetadata {
+        let frame_durations: Vec<u16> = self.frames.iter().map(|f| f.duration_ms).collect();
+        let layers = self
+            .layers
+            .iter()
+            .map(|l| AsepriteLayer {
+                name: l.name.clone(),
+                layer_type: match l.layer_type {
+                    1 => "group",
+                    2 => "tilemap",
+                    _ => "image",
+                }
+                .to_string(),
+                child_level: l.child_level,
+                visible: l.flags & LAYER_FLAG_VISIBLE != 0,
+                opacity: l.opacity,
+            })
+            .collect();
+
+        AsepriteMetadata {
+            color_depth: self.color_depth,
+            frame_count: self.frames.len(),
+            total_duration_ms: frame_durations.iter().map(|d| *d as i64).sum(),
+            frame_durations,
+            tags: self.tags.clone(),
+            layers,
+        }
+    }
+
+    /// Frame indices covered by a tag, in the order they should appear in a sheet.
+    /// Ping-pong tags list each frame once, in the tag's base direction.
+    pub fn tag_frames(&self, tag_name: &str) -> Option<Vec<usize>> {
+        let tag = self.tags.iter().find(|t| t.name == tag_name)?;
+        let (from, to) = (tag.from as usize, tag.to as usize);
+        let frames: Vec<usize> = match tag.direction.as_str() {
+            "reverse" | "pingpong_reverse" => (from..=to).rev().collect(),
+            _ => (from..=to).collect(),
+        };
+        Some(frames.into_iter().filter(|i| *i < self.frames.len()).collect())
+    }
+
+    /// Composite all visible image layers of a frame into an RGBA image.
+    /// Blend modes other than normal are rendered as normal.
+    pub fn render_frame(&self, frame_index: usize) -> Result<RgbaImage, AppError> {
+        let frame = self.frames.get(frame_index).ok_or_else(|| {
+            AppError::InvalidInput(format!("Frame {} does not exist", frame_index))
+        })?;
+
+        let mut canvas = RgbaImage::new(self.width, self.height);
+        let visible = self.effective_layer_visibility();
+
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            if !visible[layer_index] || layer.layer_type != 0 {
+                continue;
+            }
+            let Some(cel) = self.find_cel(frame, layer_index as u16) else {
+                continue;
+            };
+            let CelContent::Image {
+                width,
+                height,
+                pixels,
+            } = &cel.content
+            else {
+                continue;
+            };
+
+            let is_background = layer.flags & LAYER_FLAG_BACKGROUND != 0;
+            let opacity = (cel.opacity as u32 * layer.opacity as u32) / 255;
+
+            for py in 0..*height {
+                for px in 0..*width {
+                    let cx = cel.x + px as i32;
+                    let cy = cel.y + py as i32;
+                    if cx < 0 || cy < 0 || cx >= self.width as i32 || cy >= self.height as i32 {
+                        continue;
+                    }
+                    let index = (py * width + px) as usize;
+                    let src = self.pixel_rgba(pixels, index, is_background);
+                    let dst = canvas.get_pixel_mut(cx as u32, cy as u32);
+                    blend_normal(&mut dst.0, src, opacity as u8);
+                }
+            }
+        }
+
+        Ok(canvas)
+    }
+
+    /// A layer is only drawn when it and all of its parent groups are visible
+    fn effective_layer_visibility(&self) -> Vec<bool> {
+        let mut by_level: Vec<bool> = Vec::new();
+        self.layers
+            .iter()
+            .map(|layer| {
+                let level = layer.child_level as usize;
+                let parent_visible = level == 0 || by_level.get(level - 1).copied().unwrap_or(true);
+                let visible = parent_visible && layer.flags & LAYER_FLAG_VISIBLE != 0;
+                by_level.truncate(level);
+                by_level.push(visible);
+                visible
+            })
+            .collect()
+    }
+
+    fn find_cel<'a>(&'a self, frame: &'a Frame, layer_index: u16) -> Option<&'a Cel> {
+        let cel = frame.cels.iter().find(|c| c.layer_index == layer_index)?;
+        match cel.content {
+            CelContent::Linked(target) => self
+                .frames
+                .get(target as usize)?
+                .cels
+                .iter()
+                .find(|c| c.layer_index == layer_index && !matches!(c.content, CelContent::Linked(_))),
+            _ => Some(cel),
+        }
+    }
+
+    fn pixel_rgba(&self, pixels: &[u8], index: usize, is_background: bool) -> [u8; 4] {
+        match self.color_depth {
+            32 => {
+                let p = &pixels[index * 4..index * 4 + 4];
+                [p[0], p[1], p[2], p[3]]
+            }
+            16 => {
+                let v = pixels[index * 2];
+                [v, v, v, pixels[index * 2 + 1]]
+            }
+            _ => {
+                let i = pixels[index];
+                if i == self.transparent_index && !is_background {
+                    [0, 0, 0, 0]
+                } else {
+                    self.palette.get(i as usize).copied().unwrap_or([0, 0, 0, 0])
+                }
+            }
+        }
+    }
+}
+
+fn parse_layer(r: &mut Reader, header_flags: u32) -> Result<LayerInfo, AppError> {
+    let flags = r.u16()?;
+    let layer_type = r.u16()?;
+    let child_level = r.u16()?;
+    r.skip(2 + 2 + 2)?; // default width/height, blend mode
+    let opacity = r.u8()?;
+    r.skip(3)?;
+    let name = r.string()?;
+
+    Ok(LayerInfo {
+        flags,
+        layer_type,
+        child_level,
+        opacity: if header_flags & HEADER_FLAG_LAYER_OPACITY != 0 {
+            opacity
+        } else {
+            255
+        },
+        name,
+    })
+}
+
+fn parse_cel(
+    r: &mut Reader,
+    color_depth: u16,
+    canvas_width: u32,
+    canvas_height: u32,
+) -> Result<Cel, AppError> {
+    let layer_index = r.u16()?;
+    let x = r.i16()? as i32;
+    let y = r.i16()? as i32;
+    let opacity = r.u8()?;
+    let cel_type = r.u16()?;
+    r.skip(2 + 5)?; // z-index and reserved
+
+    let bytes_per_pixel = (color_depth / 8) as usize;
+    let content = match cel_type {
+        0 | 2 => {
+            let width = r.u16()? as u32;
+            let height = r.u16()? as u32;
+            if width > canvas_width || height > canvas_height {
+                return Err(AppError::InvalidInput(format!(
+                    "Aseprite cel {}x{} exceeds the {}x{} canvas",
+                    width, height, canvas_width, canvas_height
+                )));
+            }
+            let expected = width as usize * height as usize * bytes_per_pixel;
+            let rest = r.bytes(r.data.len() - r.pos)?;
+            let pixels = if cel_type == 0 {
+                rest.to_vec()
+            } else {
+                // Never inflate past the cel size, whatever the stream claims
+                let mut decoded = Vec::new();
+                flate2::read::ZlibDecoder::new(rest)
+                    .take(expected as u64)
+                    .read_to_end(&mut decoded)
+                    .map_err(|e| {
+                        AppError::InvalidInput(format!("Invalid Aseprite cel data: {}", e))
+                    })?;
+                decoded
+            };
+            if pixels.len() < expected {
+                return Err(AppError::InvalidInput(
+                    "Truncated Aseprite cel data".to_string(),
+                ));
+            }
+            CelContent::Image {
+                width,
+                height,
+                pixels,
+            }
+        }
+        1 => CelContent::Linked(r.u16()?),
+        _ => CelContent::Unsupported,
+    };
+
+    Ok(Cel {
+        layer_index,
+        x,
+        y,
+        opacity,
+        content,
+    })
+}
+
+fn parse_tags(r: &mut Reader) -> Result<Vec<AsepriteTag>, AppError> {
+    let count = r.u16()?;
+    r.skip(8)?;
+    let mut tags = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let from = r.u16()?;
+        let to = r.u16()?;
+        let direction = match r.u8()? {
+            1 => "reverse",
+            2 => "pingpong",
+            3 => "pingpong_reverse",
+            _ => "forward",
+        };
+        r.skip(2 + 6 + 3 + 1)?; // repeat, reserved, deprecated color, extra byte
+        let name = r.string()?;
+        tags.push(AsepriteTag {
+            name,
+            from,
+            to,
+            direction: direction.to_string(),
+        });
+    }
+    Ok(tags)
+}
+
+fn parse_palette(r: &mut Reader, palette: &mut Vec<[u8; 4]>) -> Result<(), AppError> {
+    let size = r.u32()? as usize;
+    let first = r.u32()? as usize;
+    let last = r.u32()? as usize;
+    r.skip(8)?;
+    if size > MAX_PALETTE_SIZE || first > last || last >= size {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid Aseprite palette: {} colors, entries {} to {}",
+            size, first, last
+        )));
+    }
+    if palette.len() < size {
+        palette.resize(size, [0, 0, 0, 0]);
+    }
+    for index in first..=last {
+        let flags = r.u16()?;
+        let color = [r.u8()?, r.u8()?, r.u8()?, r.u8()?];
+        if flags & 1 != 0 {
+            r.string()?;
+        }
+        if index >= palette.len() {
+            palette.resize(index + 1, [0, 0, 0, 0]);
+        }
+        palette[index] = color;
+    }
+    Ok(())
+}
+
+fn parse_old_palette(
+    r: &mut Reader,
+    palette: &mut Vec<[u8; 4]>,
+    six_bit: bool,
+) -> Result<(), AppError> {
+    let packets = r.u16()?;
+    let mut index = 0usize;
+    for _ in 0..packets {
+        index += r.u8()? as usize;
+        let count = match r.u8()? {
+            0 => 256,
+            n => n as usize,
+        };
+        for _ in 0..count {
+            if index >= MAX_PALETTE_SIZE {
+                return Err(AppError::InvalidInput(
+                    "Invalid Aseprite palette: more than 256 colors".to_string(),
+                ));
+            }
+            let mut rgb = [r.u8()?, r.u8()?, r.u8()?];
+            if six_bit {
+                for c in rgb.iter_mut() {
+                    *c = (*c << 2) | (*c >> 4);
+                }
+            }
+            if index >= palette.len() {
+                palette.resize(index + 1, [0, 0, 0, 0]);
+            }
+            palette[index] = [rgb[0], rgb[1], rgb[2], 255];
+            index += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Source-over blend of `src` (scaled by `opacity`) onto `dst`
+fn blend_normal(dst: &mut [u8; 4], src: [u8; 4], opacity: u8) {
+    let sa = (src[3] as f32 / 255.0) * (opacity as f32 / 255.0);
+    if sa <= 0.0 {
+        return;
+    }
+    let da = dst[3] as f32 / 255.0;
+    let out_a = sa + da * (1.0 - sa);
+    for c in 0..3 {
+        let sc = src[c] as f32;
+        let dc = dst[c] as f32;
+        dst[c] = ((sc * sa + dc * da * (1.0 - sa)) / out_a).round() as u8;
+    }
+    dst[3] = (out_a * 255.0).round() as u8;
+}
@@ -2,6 +2,7 @@ use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
 use std::path::Path;
 
 use crate::error::AppError;
+use crate::processing::aseprite;
 
 /// Remove background using color-key method.
 /// Pixels matching the target color (within tolerance) become transparent.
@@ -10,7 +11,7 @@ pub fn remove_background_color_key(
     target_color: [u8; 3],
     tolerance: u8,
 ) -> Result<DynamicImage, AppError> {
-    let img = image::open(source)?;
+    let img = aseprite::open_image(source)?;
     let (width, height) = img.dimensions();
     let rgba = img.to_rgba8();
 
@@ -1,8 +1,9 @@
-use image::{DynamicImage, ImageFormat, ImageReader};
+use image::{DynamicImage, ImageFormat};
 use std::io::Cursor;
 use std::path::Path;
 
 use crate::error::AppError;
+use crate::processing::aseprite;
 
 /// Compress an image by resizing and/or reducing quality
 /// Returns the compressed image bytes and the format used
@@ -12,7 +13,7 @@ pub fn compress_image(
     max_height: Option<u32>,
     quality: u8, // 1-100, only affects JPEG
 ) -> Result<DynamicImage, AppError> {
-    let img = ImageReader::open(path)?.decode()?;
+    let img = aseprite::open_image(path)?;
 
     let (orig_w, orig_h) = (img.width(), img.height());
     let max_w = max_width.unwrap_or(orig_w);
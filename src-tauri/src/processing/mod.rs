@@ -1,4 +1,6 @@
+pub mod aseprite;
 pub mod background;
 pub mod compress;
 pub mod descriptor;
 pub mod spritesheet;
+pub mod tiled;
@@ -2,6 +2,7 @@ use image::{DynamicImage, GenericImageView, RgbaImage};
 use std::path::Path;
 
 use crate::error::AppError;
+use crate::processing::aseprite;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SpriteFrame {
@@ -26,19 +27,29 @@ pub fn merge_spritesheet(
     columns: u32,
     padding: u32,
 ) -> Result<(DynamicImage, SpritesheetInfo), AppError> {
-    if image_paths.is_empty() {
-        return Err(AppError::InvalidInput("No images provided".to_string()));
-    }
-
-    // Load all images and find max cell size
+    // Load all images
     let images: Vec<(String, DynamicImage)> = image_paths
         .iter()
         .map(|(name, path)| {
-            let img = image::open(path)?;
+            let img = aseprite::open_image(path)?;
             Ok((name.clone(), img))
         })
         .collect::<Result<Vec<_>, AppError>>()?;
 
+    merge_spritesheet_images(&images, columns, padding)
+}
+
+/// Merge already decoded images into a sprite sheet grid.
+pub fn merge_spritesheet_images(
+    images: &[(String, DynamicImage)], // (name, image)
+    columns: u32,
+    padding: u32,
+) -> Result<(DynamicImage, SpritesheetInfo), AppError> {
+    if images.is_empty() {
+        return Err(AppError::InvalidInput("No images provided".to_string()));
+    }
+
+    // Find max cell size
     let max_width = images.iter().map(|(_, img)| img.width()).max().unwrap_or(0);
     let max_height = images.iter().map(|(_, img)| img.height()).max().unwrap_or(0);
 
@@ -89,7 +100,7 @@ pub fn split_image_grid(
     rows: u32,
     cols: u32,
 ) -> Result<Vec<DynamicImage>, AppError> {
-    let img = image::open(source)?;
+    let img = aseprite::open_image(source)?;
     let (width, height) = img.dimensions();
 
     let cell_width = width / cols;
@@ -0,0 +1,763 @@
+use base64::Engine;
+use image::{imageops, DynamicImage, RgbaImage};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+const FLIP_HORIZONTAL: u32 = 0x8000_0000;
+const FLIP_VERTICAL: u32 = 0x4000_0000;
+const FLIP_DIAGONAL: u32 = 0x2000_0000;
+const GID_MASK: u32 = 0x0FFF_FFFF;
+
+/// Largest edge of the rendered map preview, in pixels
+const PREVIEW_MAX_SIZE: u32 = 1024;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TiledLayer {
+    pub name: String,
+    pub layer_type: String, // "tilelayer", "objectgroup", "imagelayer" or "group"
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TiledTileset {
+    pub name: String,
+    pub first_gid: u32,
+    pub source: Option<String>,
+    pub image: Option<String>,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub tile_count: u32,
+    pub columns: u32,
+}
+
+/// Metadata stored for imported Tiled maps (`.tmx`/`.tmj`) and tilesets (`.tsx`/`.tsj`)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TiledMetadata {
+    pub kind: String, // "map" or "tileset"
+    pub orientation: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    pub infinite: bool,
+    pub layers: Vec<TiledLayer>,
+    pub tilesets: Vec<TiledTileset>,
+    pub images: Vec<String>,
+}
+
+/// A file referenced by a Tiled document, as written and resolved on disk
+#[derive(Debug, Clone)]
+pub struct TiledReference {
+    pub link_type: String, // "tileset" or "image"
+    pub reference_path: String,
+    pub resolved_path: PathBuf,
+}
+
+struct Chunk {
+    x: i32,
+    y: i32,
+    width: u32,
+    gids: Vec<u32>,
+}
+
+struct TileLayerData {
+    visible: bool,
+    chunks: Vec<Chunk>,
+}
+
+/// Tileset with paths resolved relative to the file that declared it
+struct ResolvedTileset {
+    info: TiledTileset,
+    margin: u32,
+    spacing: u32,
+    image_path: Option<PathBuf>,
+    tile_images: HashMap<u32, PathBuf>,
+}
+
+/// A parsed Tiled map or tileset
+pub struct TiledDocument {
+    pub metadata: TiledMetadata,
+    references: Vec<TiledReference>,
+    tilesets: Vec<ResolvedTileset>,
+    tile_layers: Vec<TileLayerData>,
+}
+
+/// Check whether a path points to a Tiled map or tileset
+pub fn is_tiled(path: &Path) -> bool {
+    match extension(path).as_str() {
+        "tmx" | "tmj" | "tsj" => true,
+        "tsx" => is_tsx_tileset(path),
+        _ => false,
+    }
+}
+
+/// Whether a `.tsx` file is a Tiled tileset, i.e. XML with a `<tileset>` root element,
+/// rather than TypeScript JSX
+pub fn is_tsx_tileset(path: &Path) -> bool {
+    let mut head = Vec::new();
+    let read = std::fs::File::open(path).and_then(|f| f.take(1024).read_to_end(&mut head));
+    if read.is_err() {
+        return false;
+    }
+    let text = String::from_utf8_lossy(&head);
+    let mut rest = text.trim_start_matches('\u{feff}').trim_start();
+    // Skip the XML declaration, comments and doctype before the root element
+    while rest.starts_with("<?") || rest.starts_with("<!") {
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|i| i + 3)
+        } else {
+            rest.find('>').map(|i| i + 1)
+        };
+        match end {
+            Some(i) => rest = rest[i..].trim_start(),
+            None => return false,
+        }
+    }
+    rest.starts_with("<tileset")
+}
+
+fn invalid(e: impl std::fmt::Display) -> AppError {
+    AppError::InvalidInput(format!("Invalid Tiled file: {}", e))
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+impl TiledDocument {
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        let text = std::fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        let mut doc = TiledDocument {
+            metadata: TiledMetadata {
+                kind: "map".to_string(),
+                orientation: None,
+                width: 0,
+                height: 0,
+                tile_width: 0,
+                tile_height: 0,
+                infinite: false,
+                layers: Vec::new(),
+                tilesets: Vec::new(),
+                images: Vec::new(),
+            },
+            references: Vec::new(),
+            tilesets: Vec::new(),
+            tile_layers: Vec::new(),
+        };
+
+        match extension(path).as_str() {
+            "tmx" => doc.parse_tmx(&text, &base_dir)?,
+            "tmj" => doc.parse_tmj(&text, &base_dir)?,
+            "tsx" | "tsj" => {
+                doc.metadata.kind = "tileset".to_string();
+                let tileset = load_tileset_text(&text, &extension(path), 1, None, &base_dir)?;
+                doc.metadata.tile_width = tileset.info.tile_width;
+                doc.metadata.tile_height = tileset.info.tile_height;
+                doc.add_tileset(tileset, &base_dir);
+            }
+            _ => return Err(invalid("unsupported extension")),
+        }
+
+        Ok(doc)
+    }
+
+    /// Files referenced by this document (external tilesets and images)
+    pub fn references(&self) -> &[TiledReference] {
+        &self.references
+    }
+
+    /// Pixel size of the map, or of the tileset image for tilesets
+    pub fn pixel_size(&self) -> Option<(u32, u32)> {
+        if self.metadata.kind == "tileset" {
+            let path = self.tilesets.first()?.image_path.as_ref()?;
+            return image::image_dimensions(path).ok();
+        }
+        self.map_pixel_size()
+    }
+
+    /// Pixel size of the map's tile bounds; `None` if it does not fit in a `u32`
+    fn map_pixel_size(&self) -> Option<(u32, u32)> {
+        let (min_x, min_y, max_x, max_y) = self.tile_bounds()?;
+        let width = u32::try_from(max_x.checked_sub(min_x)?).ok()?;
+        let height = u32::try_from(max_y.checked_sub(min_y)?).ok()?;
+        Some((
+            width.checked_mul(self.metadata.tile_width)?,
+            height.checked_mul(self.metadata.tile_height)?,
+        ))
+    }
+
+    fn tile_bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        if !self.metadata.infinite {
+            return Some((0, 0, self.metadata.width as i32, self.metadata.height as i32));
+        }
+        let mut bounds = None;
+        for c in self.tile_layers.iter().flat_map(|l| l.chunks.iter()) {
+            let width = i32::try_from(c.width).ok()?;
+            let height = i32::try_from(c.gids.len() / c.width.max(1) as usize).ok()?;
+            let (x0, y0) = (c.x, c.y);
+            let (x1, y1) = (c.x.checked_add(width)?, c.y.checked_add(height)?);
+            bounds = Some(match bounds {
+                None => (x0, y0, x1, y1),
+                Some((a, b, c, d)) => (a.min(x0), b.min(y0), c.max(x1), d.max(y1)),
+            });
+        }
+        bounds
+    }
+
+    /// Render a downscaled preview of the map from its tileset images.
+    /// Returns `None` when the referenced tilesets are not available.
+    /// Non-orthogonal maps are drawn on an orthogonal grid.
+    pub fn render_preview(&self) -> Option<RgbaImage> {
+        if self.metadata.kind == "tileset" {
+            let path = self.tilesets.first()?.image_path.as_ref()?;
+            return image::open(path).ok().map(|img| img.to_rgba8());
+        }
+
+        let (min_x, min_y, _, _) = self.tile_bounds()?;
+        let (tw, th) = (self.metadata.tile_width, self.metadata.tile_height);
+        let (full_w, full_h) = self.map_pixel_size()?;
+        if full_w == 0 || full_h == 0 {
+            return None;
+        }
+
+        let scale = (PREVIEW_MAX_SIZE as f64 / full_w.max(full_h) as f64).min(1.0);
+        let canvas_w = ((full_w as f64 * scale).ceil() as u32).max(1);
+        let canvas_h = ((full_h as f64 * scale).ceil() as u32).max(1);
+        let mut canvas = RgbaImage::new(canvas_w, canvas_h);
+
+        let mut sheets: HashMap<PathBuf, Option<DynamicImage>> = HashMap::new();
+        let mut tiles: HashMap<u32, Option<RgbaImage>> = HashMap::new();
+        let mut drawn_any = false;
+
+        for layer in self.tile_layers.iter().filter(|l| l.visible) {
+            for chunk in &layer.chunks {
+                for (i, raw_gid) in chunk.gids.iter().enumerate() {
+                    if raw_gid & GID_MASK == 0 {
+                        continue;
+                    }
+                    let tile = tiles
+                        .entry(*raw_gid)
+                        .or_insert_with(|| self.tile_image(*raw_gid, scale, &mut sheets));
+                    let Some(tile) = tile else {
+                        continue;
+                    };
+
+                    let columns = chunk.width.max(1);
+                    let tx = chunk.x + (i as u32 % columns) as i32 - min_x;
+                    let ty = chunk.y + (i as u32 / columns) as i32 - min_y;
+                    // Tiles taller than the grid are anchored to the bottom of their cell
+                    let x = (tx as f64 * tw as f64 * scale).floor() as i64;
+                    let y = ((ty + 1) as f64 * th as f64 * scale).floor() as i64 - tile.height() as i64;
+                    imageops::overlay(&mut canvas, tile, x, y);
+                    drawn_any = true;
+                }
+            }
+        }
+
+        drawn_any.then_some(canvas)
+    }
+
+    fn tile_image(
+        &self,
+        raw_gid: u32,
+        scale: f64,
+        sheets: &mut HashMap<PathBuf, Option<DynamicImage>>,
+    ) -> Option<RgbaImage> {
+        let gid = raw_gid & GID_MASK;
+        let tileset = self
+            .tilesets
+            .iter()
+            .filter(|t| t.info.first_gid <= gid)
+            .max_by_key(|t| t.info.first_gid)?;
+        let local = gid - tileset.info.first_gid;
+
+        let mut tile = if let Some(path) = &tileset.image_path {
+            let sheet = sheets
+                .entry(path.clone())
+                .or_insert_with(|| image::open(path).ok())
+                .as_ref()?;
+            let (tw, th) = (tileset.info.tile_width, tileset.info.tile_height);
+            let columns = if tileset.info.columns > 0 {
+                tileset.info.columns
+            } else {
+                (sheet
+                    .width()
+                    .saturating_sub(tileset.margin.saturating_mul(2))
+                    .saturating_add(tileset.spacing)
+                    / tw.saturating_add(tileset.spacing).max(1))
+                .max(1)
+            };
+            let sx = (local % columns)
+                .checked_mul(tw.checked_add(tileset.spacing)?)?
+                .checked_add(tileset.margin)?;
+            let sy = (local / columns)
+                .checked_mul(th.checked_add(tileset.spacing)?)?
+                .checked_add(tileset.margin)?;
+            if sx.checked_add(tw)? > sheet.width() || sy.checked_add(th)? > sheet.height() {
+                return None;
+            }
+            sheet.crop_imm(sx, sy, tw, th)
+        } else {
+            let path = tileset.tile_images.get(&local)?;
+            sheets
+                .entry(path.clone())
+                .or_insert_with(|| image::open(path).ok())
+                .clone()?
+        };
+
+        if raw_gid & FLIP_DIAGONAL != 0 {
+            tile = tile.rotate90().fliph();
+        }
+        if raw_gid & FLIP_HORIZONTAL != 0 {
+            tile = tile.fliph();
+        }
+        if raw_gid & FLIP_VERTICAL != 0 {
+            tile = tile.flipv();
+        }
+
+        let w = ((tile.width() as f64 * scale).ceil() as u32).max(1);
+        let h = ((tile.height() as f64 * scale).ceil() as u32).max(1);
+        Some(
+            tile.resize_exact(w, h, imageops::FilterType::Triangle)
+                .to_rgba8(),
+        )
+    }
+
+    fn add_tileset(&mut self, tileset: ResolvedTileset, base_dir: &Path) {
+        if let Some(image) = &tileset.info.image {
+            self.add_image_reference(image, tileset.image_path.clone(), base_dir);
+        }
+        let mut tile_images: Vec<_> = tileset.tile_images.values().cloned().collect();
+        tile_images.sort();
+        for path in tile_images {
+            let written = path
+                .strip_prefix(base_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            self.add_image_reference(&written, Some(path), base_dir);
+        }
+        self.metadata.tilesets.push(tileset.info.clone());
+        self.tilesets.push(tileset);
+    }
+
+    fn add_image_reference(&mut self, written: &str, resolved: Option<PathBuf>, base_dir: &Path) {
+        let resolved_path = resolved.unwrap_or_else(|| base_dir.join(written));
+        if self.references.iter().any(|r| r.resolved_path == resolved_path) {
+            return;
+        }
+        self.metadata.images.push(written.to_string());
+        self.references.push(TiledReference {
+            link_type: "image".to_string(),
+            reference_path: written.to_string(),
+            resolved_path,
+        });
+    }
+
+    fn add_external_tileset(
+        &mut self,
+        source: &str,
+        first_gid: u32,
+        base_dir: &Path,
+    ) -> Result<(), AppError> {
+        let path = base_dir.join(source);
+        self.references.push(TiledReference {
+            link_type: "tileset".to_string(),
+            reference_path: source.to_string(),
+            resolved_path: path.clone(),
+        });
+
+        // A missing external tileset is recorded but cannot contribute to the preview
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            self.metadata.tilesets.push(TiledTileset {
+                name: source.to_string(),
+                first_gid,
+                source: Some(source.to_string()),
+                image: None,
+                tile_width: 0,
+                tile_height: 0,
+                tile_count: 0,
+                columns: 0,
+            });
+            return Ok(());
+        };
+        let tileset_dir = path.parent().unwrap_or(base_dir).to_path_buf();
+        let tileset = load_tileset_text(
+            &text,
+            &extension(&path),
+            first_gid,
+            Some(source.to_string()),
+            &tileset_dir,
+        )?;
+        self.add_tileset(tileset, &tileset_dir);
+        Ok(())
+    }
+
+    fn parse_tmx(&mut self, text: &str, base_dir: &Path) -> Result<(), AppError> {
+        let xml = roxmltree::Document::parse(text).map_err(invalid)?;
+        let map = xml.root_element();
+        if !map.has_tag_name("map") {
+            return Err(invalid("root element is not <map>"));
+        }
+
+        self.metadata.orientation = map.attribute("orientation").map(str::to_string);
+        self.metadata.width = xml_u32(&map, "width");
+        self.metadata.height = xml_u32(&map, "height");
+        self.metadata.tile_width = xml_u32(&map, "tilewidth");
+        self.metadata.tile_height = xml_u32(&map, "tileheight");
+        self.metadata.infinite = map.attribute("infinite") == Some("1");
+
+        for child in map.children().filter(|n| n.is_element()) {
+            if child.has_tag_name("tileset") {
+                let first_gid = xml_u32(&child, "firstgid").max(1);
+                if let Some(source) = child.attribute("source") {
+                    self.add_external_tileset(source, first_gid, base_dir)?;
+                } else {
+                    let tileset = parse_tsx_node(&child, first_gid, None, base_dir);
+                    self.add_tileset(tileset, base_dir);
+                }
+            }
+        }
+
+        self.parse_tmx_layers(&map, true, base_dir)
+    }
+
+    fn parse_tmx_layers(
+        &mut self,
+        parent: &roxmltree::Node,
+        parent_visible: bool,
+        base_dir: &Path,
+    ) -> Result<(), AppError> {
+        for node in parent.children().filter(|n| n.is_element()) {
+            let layer_type = match node.tag_name().name() {
+                "layer" => "tilelayer",
+                "objectgroup" => "objectgroup",
+                "imagelayer" => "imagelayer",
+                "group" => "group",
+                _ => continue,
+            };
+            let visible = node.attribute("visible") != Some("0");
+            self.metadata.layers.push(TiledLayer {
+                name: node.attribute("name").unwrap_or("").to_string(),
+                layer_type: layer_type.to_string(),
+                visible,
+            });
+
+            match layer_type {
+                "tilelayer" => {
+                    let chunks = node
+                        .children()
+                        .find(|n| n.has_tag_name("data"))
+                        .map(|data| parse_tmx_data(&data, xml_u32(&node, "width")))
+                        .transpose()?
+                        .unwrap_or_default();
+                    self.tile_layers.push(TileLayerData {
+                        visible: parent_visible && visible,
+                        chunks,
+                    });
+                }
+                "imagelayer" => {
+                    if let Some(source) = node
+                        .children()
+                        .find(|n| n.has_tag_name("image"))
+                        .and_then(|img| img.attribute("source"))
+                    {
+                        self.add_image_reference(source, None, base_dir);
+                    }
+                }
+                "group" => self.parse_tmx_layers(&node, parent_visible && visible, base_dir)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_tmj(&mut self, text: &str, base_dir: &Path) -> Result<(), AppError> {
+        let map: serde_json::Value = serde_json::from_str(text)?;
+        if map.get("type").and_then(|t| t.as_str()) == Some("tileset") {
+            return Err(invalid("expected a map, found a tileset"));
+        }
+
+        self.metadata.orientation = map["orientation"].as_str().map(str::to_string);
+        self.metadata.width = json_u32(&map, "width");
+        self.metadata.height = json_u32(&map, "height");
+        self.metadata.tile_width = json_u32(&map, "tilewidth");
+        self.metadata.tile_height = json_u32(&map, "tileheight");
+        self.metadata.infinite = map["infinite"].as_bool().unwrap_or(false);
+
+        for tileset in map["tilesets"].as_array().into_iter().flatten() {
+            let first_gid = json_u32(tileset, "firstgid").max(1);
+            if let Some(source) = tileset["source"].as_str() {
+                self.add_external_tileset(source, first_gid, base_dir)?;
+            } else {
+                let resolved = parse_tsj_value(tileset, first_gid, None, base_dir);
+                self.add_tileset(resolved, base_dir);
+            }
+        }
+
+        self.parse_tmj_layers(&map["layers"], true, base_dir)
+    }
+
+    fn parse_tmj_layers(
+        &mut self,
+        layers: &serde_json::Value,
+        parent_visible: bool,
+        base_dir: &Path,
+    ) -> Result<(), AppError> {
+        for layer in layers.as_array().into_iter().flatten() {
+            let layer_type = layer["type"].as_str().unwrap_or("").to_string();
+            let visible = layer["visible"].as_bool().unwrap_or(true);
+            self.metadata.layers.push(TiledLayer {
+                name: layer["name"].as_str().unwrap_or("").to_string(),
+                layer_type: layer_type.clone(),
+                visible,
+            });
+
+            match layer_type.as_str() {
+                "tilelayer" => {
+                    let encoding = layer["encoding"].as_str().unwrap_or("csv");
+                    let compression = layer["compression"].as_str().unwrap_or("");
+                    let mut chunks = Vec::new();
+                    if let Some(list) = layer["chunks"].as_array() {
+                        for chunk in list {
+                            chunks.push(Chunk {
+                                x: chunk["x"].as_i64().unwrap_or(0) as i32,
+                                y: chunk["y"].as_i64().unwrap_or(0) as i32,
+                                width: json_u32(chunk, "width").max(1),
+                                gids: decode_json_data(&chunk["data"], encoding, compression)?,
+                            });
+                        }
+                    } else {
+                        chunks.push(Chunk {
+                            x: 0,
+                            y: 0,
+                            width: json_u32(layer, "width").max(1),
+                            gids: decode_json_data(&layer["data"], encoding, compression)?,
+                        });
+                    }
+                    self.tile_layers.push(TileLayerData {
+                        visible: parent_visible && visible,
+                        chunks,
+                    });
+                }
+                "imagelayer" => {
+                    if let Some(image) = layer["image"].as_str().filter(|s| !s.is_empty()) {
+                        self.add_image_reference(image, None, base_dir);
+                    }
+                }
+                "group" => {
+                    self.parse_tmj_layers(&layer["layers"], parent_visible && visible, base_dir)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn xml_u32(node: &roxmltree::Node, name: &str) -> u32 {
+    node.attribute(name)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+fn json_u32(value: &serde_json::Value, key: &str) -> u32 {
+    value[key].as_u64().unwrap_or(0) as u32
+}
+
+fn load_tileset_text(
+    text: &str,
+    ext: &str,
+    first_gid: u32,
+    source: Option<String>,
+    base_dir: &Path,
+) -> Result<ResolvedTileset, AppError> {
+    if ext == "tsj" || ext == "json" {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        Ok(parse_tsj_value(&value, first_gid, source, base_dir))
+    } else {
+        let xml = roxmltree::Document::parse(text).map_err(invalid)?;
+        let root = xml.root_element();
+        if !root.has_tag_name("tileset") {
+            return Err(invalid("root element is not <tileset>"));
+        }
+        Ok(parse_tsx_node(&root, first_gid, source, base_dir))
+    }
+}
+
+fn parse_tsx_node(
+    node: &roxmltree::Node,
+    first_gid: u32,
+    source: Option<String>,
+    base_dir: &Path,
+) -> ResolvedTileset {
+    let image = node
+        .children()
+        .find(|n| n.has_tag_name("image"))
+        .and_then(|img| img.attribute("source"))
+        .map(str::to_string);
+
+    let mut tile_images = HashMap::new();
+    for tile in node.children().filter(|n| n.has_tag_name("tile")) {
+        let image_source = tile
+            .children()
+            .find(|n| n.has_tag_name("image"))
+            .and_then(|img| img.attribute("source"));
+        if let Some(image_source) = image_source {
+            tile_images.insert(xml_u32(&tile, "id"), base_dir.join(image_source));
+        }
+    }
+
+    ResolvedTileset {
+        info: TiledTileset {
+            name: node.attribute("name").unwrap_or("").to_string(),
+            first_gid,
+            source,
+            image: image.clone(),
+            tile_width: xml_u32(node, "tilewidth"),
+            tile_height: xml_u32(node, "tileheight"),
+            tile_count: xml_u32(node, "tilecount"),
+            columns: xml_u32(node, "columns"),
+        },
+        margin: xml_u32(node, "margin"),
+        spacing: xml_u32(node, "spacing"),
+        image_path: image.as_ref().map(|i| base_dir.join(i)),
+        tile_images,
+    }
+}
+
+fn parse_tsj_value(
+    value: &serde_json::Value,
+    first_gid: u32,
+    source: Option<String>,
+    base_dir: &Path,
+) -> ResolvedTileset {
+    let image = value["image"]
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    let mut tile_images = HashMap::new();
+    for tile in value["tiles"].as_array().into_iter().flatten() {
+        if let Some(image_source) = tile["image"].as_str().filter(|s| !s.is_empty()) {
+            tile_images.insert(json_u32(tile, "id"), base_dir.join(image_source));
+        }
+    }
+
+    ResolvedTileset {
+        info: TiledTileset {
+            name: value["name"].as_str().unwrap_or("").to_string(),
+            first_gid,
+            source,
+            image: image.clone(),
+            tile_width: json_u32(value, "tilewidth"),
+            tile_height: json_u32(value, "tileheight"),
+            tile_count: json_u32(value, "tilecount"),
+            columns: json_u32(value, "columns"),
+        },
+        margin: json_u32(value, "margin"),
+        spacing: json_u32(value, "spacing"),
+        image_path: image.as_ref().map(|i| base_dir.join(i)),
+        tile_images,
+    }
+}
+
+fn parse_tmx_data(data: &roxmltree::Node, layer_width: u32) -> Result<Vec<Chunk>, AppError> {
+    let encoding = data.attribute("encoding").unwrap_or("");
+    let compression = data.attribute("compression").unwrap_or("");
+
+    let chunk_nodes: Vec<_> = data.children().filter(|n| n.has_tag_name("chunk")).collect();
+    if chunk_nodes.is_empty() {
+        return Ok(vec![Chunk {
+            x: 0,
+            y: 0,
+            width: layer_width.max(1),
+            gids: decode_tmx_gids(data, encoding, compression)?,
+        }]);
+    }
+
+    chunk_nodes
+        .iter()
+        .map(|chunk| {
+            Ok(Chunk {
+                x: chunk.attribute("x").and_then(|v| v.parse().ok()).unwrap_or(0),
+                y: chunk.attribute("y").and_then(|v| v.parse().ok()).unwrap_or(0),
+                width: xml_u32(chunk, "width").max(1),
+                gids: decode_tmx_gids(chunk, encoding, compression)?,
+            })
+        })
+        .collect()
+}
+
+fn decode_tmx_gids(
+    node: &roxmltree::Node,
+    encoding: &str,
+    compression: &str,
+) -> Result<Vec<u32>, AppError> {
+    match encoding {
+        "csv" => Ok(parse_csv(node.text().unwrap_or(""))),
+        "base64" => decode_base64_gids(node.text().unwrap_or(""), compression),
+        // Plain XML encoding lists one <tile gid="..."/> per cell
+        _ => Ok(node
+            .children()
+            .filter(|n| n.has_tag_name("tile"))
+            .map(|t| xml_u32(&t, "gid"))
+            .collect()),
+    }
+}
+
+fn decode_json_data(
+    data: &serde_json::Value,
+    encoding: &str,
+    compression: &str,
+) -> Result<Vec<u32>, AppError> {
+    if let Some(list) = data.as_array() {
+        return Ok(list.iter().map(|v| v.as_u64().unwrap_or(0) as u32).collect());
+    }
+    match (encoding, data.as_str()) {
+        ("base64", Some(text)) => decode_base64_gids(text, compression),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn parse_csv(text: &str) -> Vec<u32> {
+    text.split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .collect()
+}
+
+fn decode_base64_gids(text: &str, compression: &str) -> Result<Vec<u32>, AppError> {
+    let raw = base64::engine::general_purpose::STANDARD
+        .decode(text.trim())
+        .map_err(invalid)?;
+    let bytes = match compression {
+        "" => raw,
+        "zlib" => {
+            let mut out = Vec::new();
+            flate2::read::ZlibDecoder::new(raw.as_slice())
+                .read_to_end(&mut out)
+                .map_err(invalid)?;
+            out
+        }
+        "gzip" => {
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(raw.as_slice())
+                .read_to_end(&mut out)
+                .map_err(invalid)?;
+            out
+        }
+        other => return Err(invalid(format!("unsupported compression '{}'", other))),
+    };
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
@@ -1,5 +1,5 @@
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Compute SHA-256 hash of a file
 pub fn compute_file_hash(path: &Path) -> Result<String, std::io::Error> {
@@ -16,6 +16,15 @@ pub fn file_type_from_mime(mime: &str) -> &'static str {
         "audio"
     } else if mime.starts_with("video/") {
         "video"
+    } else if mime.starts_with("application/x-tiled") {
+        "tilemap"
+    } else if mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json" | "application/xml" | "application/yaml" | "application/toml"
+        )
+    {
+        "text"
     } else {
         "other"
     }
@@ -40,6 +49,7 @@ pub fn guess_mime_type(path: &Path) -> String {
         "ico" => "image/x-icon",
         "tiff" | "tif" => "image/tiff",
         "psd" => "image/vnd.adobe.photoshop",
+        "aseprite" | "ase" => "image/x-aseprite",
         // Audio
         "mp3" => "audio/mpeg",
         "wav" => "audio/wav",
@@ -53,14 +63,47 @@ pub fn guess_mime_type(path: &Path) -> String {
         "avi" => "video/x-msvideo",
         "mov" => "video/quicktime",
         "webm" => "video/webm",
-        // Other
+        // Tiled maps and tilesets
+        "tmx" => "application/x-tiled-tmx",
+        "tmj" => "application/x-tiled-tmj",
+        // `.tsx` is also TypeScript JSX; only XML tilesets are Tiled files
+        "tsx" if crate::processing::tiled::is_tsx_tileset(path) => "application/x-tiled-tsx",
+        "tsx" => "text/tsx",
+        "tsj" => "application/x-tiled-tsj",
+        // Text, data and shaders
         "json" => "application/json",
         "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "ini" | "cfg" => "text/x-ini",
+        "glsl" | "vert" | "frag" | "comp" | "geom" | "tesc" | "tese" => "text/x-glsl",
+        "hlsl" | "hlsli" | "fx" | "fxh" => "text/x-hlsl",
+        "wgsl" => "text/wgsl",
+        "shader" => "text/x-shaderlab",
+        "cginc" => "text/x-cg",
         _ => "application/octet-stream",
     }
     .to_string()
 }
 
+/// Convert a folder path to the canonical format: `/` for the root, otherwise
+/// `/a/b` with forward slashes and no trailing slash. Returns `None` for paths
+/// that would escape the library (`..`).
+pub fn normalize_folder_path(path: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part.trim() {
+            "" | "." => {}
+            ".." => return None,
+            name => parts.push(name),
+        }
+    }
+    Some(format!("/{}", parts.join("/")))
+}
+
 /// Copy a file to the library directory, preserving the original extension.
 /// Returns the relative path within the library.
 pub fn copy_to_library(
@@ -92,11 +135,42 @@ pub fn copy_to_library(
     let target_path = target_dir.join(&file_name);
     std::fs::copy(source, &target_path)?;
 
-    // Return relative path from library root
-    let relative = target_path
-        .strip_prefix(library_root)
-        .unwrap_or(&target_path);
-    Ok(relative.to_string_lossy().to_string())
+    Ok(relative_to_library(&target_path, library_root))
+}
+
+/// Directory holding the previous files of an asset
+pub fn versions_dir(library_root: &Path, asset_id: &str) -> PathBuf {
+    library_root.join(".versions").join(asset_id)
+}
+
+/// A path for `file_name` in `dir` that does not exist yet, adding `_1`, `_2`, ...
+/// before the extension on collision
+pub fn unique_file_path(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (file_name, String::new()),
+    };
+    let mut i = 1;
+    loop {
+        let candidate = dir.join(format!("{}_{}{}", stem, i, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        i += 1;
+    }
+}
+
+/// Path relative to the library root with forward slashes, as stored in `relative_path`
+pub fn relative_to_library(path: &Path, library_root: &Path) -> String {
+    path.strip_prefix(library_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
 /// Get image dimensions
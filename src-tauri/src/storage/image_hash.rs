@@ -0,0 +1,55 @@
+use image::imageops::FilterType;
+use image::{GrayImage, Luma};
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::processing::aseprite;
+
+/// Transparent pixels are composited onto this gray before hashing, so that sprites are
+/// compared by their visible pixels rather than by hidden color values
+const BACKGROUND_LUMA: f32 = 128.0;
+
+/// Images whose 9x8 grayscale version spans fewer brightness levels than this are
+/// treated as flat
+const MIN_CONTRAST: u8 = 12;
+
+/// Hash of flat images (empty sprites, solid fills). They carry no shape to compare,
+/// so they should not be matched perceptually.
+pub const FLAT_HASH: u64 = 0;
+
+/// Difference hash of an image: 64 bits comparing the brightness of neighbouring pixels
+/// in a 9x8 grayscale version. Resized, recompressed or slightly edited copies of an
+/// image have hashes a few bits apart. Flat images hash to `FLAT_HASH`.
+pub fn difference_hash(path: &Path) -> Result<u64, AppError> {
+    let img = aseprite::open_image(path)?.to_rgba8();
+    let gray = GrayImage::from_fn(img.width(), img.height(), |x, y| {
+        let [r, g, b, a] = img.get_pixel(x, y).0;
+        let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+        let alpha = a as f32 / 255.0;
+        Luma([(luma * alpha + BACKGROUND_LUMA * (1.0 - alpha)).round() as u8])
+    });
+    let small = image::imageops::resize(&gray, 9, 8, FilterType::Triangle);
+
+    let (min, max) = small
+        .pixels()
+        .fold((u8::MAX, u8::MIN), |(min, max), p| (min.min(p[0]), max.max(p[0])));
+    if max - min < MIN_CONTRAST {
+        return Ok(FLAT_HASH);
+    }
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] < small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    Ok(hash)
+}
+
+/// Number of differing bits between two hashes
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
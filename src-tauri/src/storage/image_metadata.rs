@@ -0,0 +1,366 @@
+use image::{AnimationDecoder, DynamicImage, ImageDecoder, ImageReader};
+use std::collections::BTreeMap;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::db::models::ImageMetadata;
+use crate::error::AppError;
+use crate::processing::aseprite::{self, AsepriteFile};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Technical details found while scanning the raw file rather than the decoded pixels
+#[derive(Default)]
+struct ContainerInfo {
+    dpi: Option<(f64, f64)>,
+    apng_frames: Option<i64>,
+    text_entries: BTreeMap<String, String>,
+}
+
+/// Extract technical metadata (color type, alpha usage, frames, ICC, DPI, text) from an image file
+pub fn extract_image_metadata(path: &Path, asset_id: &str) -> Result<ImageMetadata, AppError> {
+    if aseprite::is_aseprite(path) {
+        return extract_aseprite_metadata(path, asset_id);
+    }
+
+    let bytes = std::fs::read(path)?;
+    let mut container = if bytes.starts_with(PNG_SIGNATURE) {
+        scan_png(&bytes)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        scan_jpeg(&bytes)
+    } else {
+        ContainerInfo::default()
+    };
+
+    let mut decoder = ImageReader::open(path)?
+        .with_guessed_format()?
+        .into_decoder()?;
+    let original_color_type = decoder.original_color_type();
+    let has_alpha = decoder.color_type().has_alpha();
+    let icc_profile = decoder.icc_profile().ok().flatten();
+    if let Some(exif) = decoder.exif_metadata().ok().flatten() {
+        let (entries, dpi) = parse_exif(&exif);
+        container.text_entries.extend(entries);
+        container.dpi = container.dpi.or(dpi);
+    }
+
+    let img = DynamicImage::from_decoder(decoder)?;
+    let alpha_used = has_alpha && image_uses_alpha(&img);
+
+    let channels = original_color_type.channel_count().max(1) as i32;
+    let frame_count = match container.apng_frames {
+        Some(frames) => frames,
+        None => count_animation_frames(path).unwrap_or(1),
+    };
+
+    Ok(ImageMetadata {
+        asset_id: asset_id.to_string(),
+        color_type: format!("{:?}", original_color_type),
+        bit_depth: original_color_type.bits_per_pixel() as i32 / channels,
+        has_alpha,
+        alpha_used,
+        frame_count,
+        has_icc_profile: icc_profile.is_some(),
+        icc_profile_size: icc_profile.map(|p| p.len() as i64),
+        dpi_x: container.dpi.map(|d| d.0),
+        dpi_y: container.dpi.map(|d| d.1),
+        text_entries: sqlx::types::Json(container.text_entries),
+        updated_at: String::new(),
+    })
+}
+
+fn extract_aseprite_metadata(path: &Path, asset_id: &str) -> Result<ImageMetadata, AppError> {
+    let file = AsepriteFile::open(path)?;
+    let meta = file.metadata();
+    let first_frame = DynamicImage::ImageRgba8(file.render_frame(0)?);
+    let (color_type, bit_depth) = match meta.color_depth {
+        8 => ("Indexed8", 8),
+        16 => ("La8", 8),
+        _ => ("Rgba8", 8),
+    };
+
+    Ok(ImageMetadata {
+        asset_id: asset_id.to_string(),
+        color_type: color_type.to_string(),
+        bit_depth,
+        has_alpha: true,
+        alpha_used: image_uses_alpha(&first_frame),
+        frame_count: meta.frame_count as i64,
+        has_icc_profile: false,
+        icc_profile_size: None,
+        dpi_x: None,
+        dpi_y: None,
+        text_entries: sqlx::types::Json(BTreeMap::new()),
+        updated_at: String::new(),
+    })
+}
+
+/// Whether any pixel is not fully opaque
+fn image_uses_alpha(img: &DynamicImage) -> bool {
+    match img {
+        DynamicImage::ImageLumaA8(buf) => buf.pixels().any(|p| p[1] < u8::MAX),
+        DynamicImage::ImageRgba8(buf) => buf.pixels().any(|p| p[3] < u8::MAX),
+        DynamicImage::ImageLumaA16(buf) => buf.pixels().any(|p| p[1] < u16::MAX),
+        DynamicImage::ImageRgba16(buf) => buf.pixels().any(|p| p[3] < u16::MAX),
+        DynamicImage::ImageRgba32F(buf) => buf.pixels().any(|p| p[3] < 1.0),
+        _ => false,
+    }
+}
+
+/// Count frames of animated GIF and WebP files
+fn count_animation_frames(path: &Path) -> Option<i64> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let reader = BufReader::new(std::fs::File::open(path).ok()?);
+    match ext.as_str() {
+        "gif" => {
+            let decoder = image::codecs::gif::GifDecoder::new(reader).ok()?;
+            Some(decoder.into_frames().count() as i64)
+        }
+        "webp" => {
+            let decoder = image::codecs::webp::WebPDecoder::new(reader).ok()?;
+            if !decoder.has_animation() {
+                return Some(1);
+            }
+            Some(decoder.into_frames().count() as i64)
+        }
+        _ => None,
+    }
+}
+
+/// Read pHYs, text and APNG control chunks from a PNG file
+fn scan_png(bytes: &[u8]) -> ContainerInfo {
+    let mut info = ContainerInfo::default();
+    let mut pos = PNG_SIGNATURE.len();
+
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_be_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let data_start = pos + 8;
+        if data_start + len > bytes.len() {
+            break;
+        }
+        let data = &bytes[data_start..data_start + len];
+
+        match kind {
+            b"pHYs" if len >= 9 && data[8] == 1 => {
+                // Pixels per meter
+                let ppm_x = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as f64;
+                let ppm_y = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as f64;
+                info.dpi = Some((ppm_x * 0.0254, ppm_y * 0.0254));
+            }
+            b"acTL" if len >= 8 => {
+                info.apng_frames = Some(u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as i64);
+            }
+            b"tEXt" => {
+                if let Some((key, value)) = split_keyword(data) {
+                    info.text_entries.insert(key, String::from_utf8_lossy(value).to_string());
+                }
+            }
+            b"zTXt" => {
+                if let Some((key, rest)) = split_keyword(data) {
+                    if let Some(text) = rest.get(1..).and_then(inflate) {
+                        info.text_entries.insert(key, String::from_utf8_lossy(&text).to_string());
+                    }
+                }
+            }
+            b"iTXt" => {
+                if let Some((key, text)) = parse_itxt(data) {
+                    info.text_entries.insert(key, text);
+                }
+            }
+            b"IEND" => break,
+            _ => {}
+        }
+
+        pos = data_start + len + 4; // skip CRC
+    }
+
+    info
+}
+
+fn split_keyword(data: &[u8]) -> Option<(String, &[u8])> {
+    let nul = data.iter().position(|b| *b == 0)?;
+    Some((
+        String::from_utf8_lossy(&data[..nul]).to_string(),
+        &data[nul + 1..],
+    ))
+}
+
+fn parse_itxt(data: &[u8]) -> Option<(String, String)> {
+    let (key, rest) = split_keyword(data)?;
+    let compressed = *rest.first()? == 1;
+    let rest = rest.get(2..)?;
+    // Skip language tag and translated keyword
+    let lang_end = rest.iter().position(|b| *b == 0)?;
+    let rest = &rest[lang_end + 1..];
+    let translated_end = rest.iter().position(|b| *b == 0)?;
+    let text = &rest[translated_end + 1..];
+    let text = if compressed { inflate(text)? } else { text.to_vec() };
+    Some((key, String::from_utf8_lossy(&text).to_string()))
+}
+
+fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    flate2::read::ZlibDecoder::new(data)
+        .read_to_end(&mut out)
+        .ok()?;
+    Some(out)
+}
+
+/// Read JFIF density and comments from a JPEG file
+fn scan_jpeg(bytes: &[u8]) -> ContainerInfo {
+    let mut info = ContainerInfo::default();
+    let mut pos = 2;
+
+    while pos + 4 <= bytes.len() && bytes[pos] == 0xFF {
+        let marker = bytes[pos + 1];
+        // Start of scan: no more metadata segments
+        if marker == 0xDA || marker == 0xD9 {
+            break;
+        }
+        let len = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let data_start = pos + 4;
+        let data_end = pos + 2 + len;
+        if len < 2 || data_end > bytes.len() {
+            break;
+        }
+        let data = &bytes[data_start..data_end];
+
+        match marker {
+            0xE0 if data.len() >= 12 && data.starts_with(b"JFIF\0") => {
+                let units = data[7];
+                let x = u16::from_be_bytes([data[8], data[9]]) as f64;
+                let y = u16::from_be_bytes([data[10], data[11]]) as f64;
+                info.dpi = match units {
+                    1 => Some((x, y)),
+                    2 => Some((x * 2.54, y * 2.54)),
+                    _ => None,
+                };
+            }
+            0xFE => {
+                info.text_entries
+                    .insert("Comment".to_string(), String::from_utf8_lossy(data).to_string());
+            }
+            _ => {}
+        }
+
+        pos = data_end;
+    }
+
+    info
+}
+
+/// Descriptive EXIF tags copied into the text entries
+const EXIF_TEXT_TAGS: &[(u16, &str)] = &[
+    (0x010E, "ImageDescription"),
+    (0x010F, "Make"),
+    (0x0110, "Model"),
+    (0x0131, "Software"),
+    (0x0132, "DateTime"),
+    (0x013B, "Artist"),
+    (0x8298, "Copyright"),
+    (0x9003, "DateTimeOriginal"),
+];
+
+const EXIF_X_RESOLUTION: u16 = 0x011A;
+const EXIF_Y_RESOLUTION: u16 = 0x011B;
+const EXIF_RESOLUTION_UNIT: u16 = 0x0128;
+const EXIF_SUB_IFD: u16 = 0x8769;
+
+/// Parse the TIFF structure of an EXIF block for descriptive tags and resolution
+fn parse_exif(data: &[u8]) -> (BTreeMap<String, String>, Option<(f64, f64)>) {
+    let mut entries = BTreeMap::new();
+    let tiff = data.strip_prefix(b"Exif\0\0").unwrap_or(data);
+    if tiff.len() < 8 {
+        return (entries, None);
+    }
+    let little_endian = match &tiff[..2] {
+        b"II" => true,
+        b"MM" => false,
+        _ => return (entries, None),
+    };
+
+    let u16_at = |pos: usize| -> Option<u16> {
+        let b = tiff.get(pos..pos + 2)?;
+        Some(if little_endian {
+            u16::from_le_bytes([b[0], b[1]])
+        } else {
+            u16::from_be_bytes([b[0], b[1]])
+        })
+    };
+    let u32_at = |pos: usize| -> Option<u32> {
+        let b = tiff.get(pos..pos + 4)?;
+        Some(if little_endian {
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+        } else {
+            u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+        })
+    };
+
+    let mut resolution = (None, None);
+    let mut unit = 2; // inches
+    let mut ifds = vec![u32_at(4).unwrap_or(0) as usize];
+    let mut visited = Vec::new();
+
+    while let Some(ifd) = ifds.pop() {
+        if visited.contains(&ifd) {
+            continue;
+        }
+        visited.push(ifd);
+        let Some(count) = u16_at(ifd) else {
+            continue;
+        };
+        for i in 0..count as usize {
+            let entry = ifd + 2 + i * 12;
+            let (Some(tag), Some(kind), Some(n), Some(value)) = (
+                u16_at(entry),
+                u16_at(entry + 2),
+                u32_at(entry + 4),
+                u32_at(entry + 8),
+            ) else {
+                break;
+            };
+
+            if let Some((_, name)) = EXIF_TEXT_TAGS.iter().find(|(t, _)| *t == tag) {
+                // ASCII values longer than four bytes live at the offset
+                if kind == 2 {
+                    let start = if n <= 4 { entry + 8 } else { value as usize };
+                    if let Some(raw) = tiff.get(start..start + n as usize) {
+                        let text = String::from_utf8_lossy(raw)
+                            .trim_end_matches('\0')
+                            .trim()
+                            .to_string();
+                        if !text.is_empty() {
+                            entries.insert(name.to_string(), text);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            match tag {
+                EXIF_X_RESOLUTION | EXIF_Y_RESOLUTION if kind == 5 => {
+                    let rational = u32_at(value as usize)
+                        .zip(u32_at(value as usize + 4))
+                        .filter(|(_, den)| *den != 0)
+                        .map(|(num, den)| num as f64 / den as f64);
+                    if tag == EXIF_X_RESOLUTION {
+                        resolution.0 = rational;
+                    } else {
+                        resolution.1 = rational;
+                    }
+                }
+                EXIF_RESOLUTION_UNIT => unit = u16_at(entry + 8).unwrap_or(2),
+                EXIF_SUB_IFD => ifds.push(value as usize),
+                _ => {}
+            }
+        }
+    }
+
+    let scale = if unit == 3 { 2.54 } else { 1.0 };
+    let dpi = match resolution {
+        (Some(x), Some(y)) => Some((x * scale, y * scale)),
+        _ => None,
+    };
+    (entries, dpi)
+}
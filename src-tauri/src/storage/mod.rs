@@ -1,2 +1,6 @@
 pub mod file_ops;
+pub mod image_hash;
+pub mod image_metadata;
+pub mod palette;
+pub mod text_content;
 pub mod thumbnail;
@@ -0,0 +1,227 @@
+use image::DynamicImage;
+use std::path::Path;
+
+use crate::db::models::PaletteColor;
+use crate::error::AppError;
+use crate::processing::aseprite;
+
+/// Images are scaled down to at most this size before clustering
+const SAMPLE_SIZE: u32 = 128;
+
+/// Pixels with less alpha than this are treated as transparent and ignored
+const MIN_ALPHA: u8 = 128;
+
+const MAX_COLORS: usize = 6;
+const KMEANS_ITERATIONS: usize = 12;
+
+/// Clusters closer than this (CIELAB ΔE) are merged into one palette color
+const MERGE_DISTANCE: f64 = 8.0;
+
+/// Colors covering less than this fraction of the visible pixels are dropped
+const MIN_COVERAGE: f64 = 0.02;
+
+/// A color in CIELAB (D65)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Lab {
+    /// CIE76 color difference
+    pub fn distance(&self, other: &Lab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2)).sqrt()
+    }
+}
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> u8 {
+    let c = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+const WHITE: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+pub fn rgb_to_lab(rgb: [u8; 3]) -> Lab {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let xyz = [
+        0.4124564 * r + 0.3575761 * g + 0.1804375 * b,
+        0.2126729 * r + 0.7151522 * g + 0.0721750 * b,
+        0.0193339 * r + 0.1191920 * g + 0.9503041 * b,
+    ];
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let [fx, fy, fz] = [f(xyz[0] / WHITE[0]), f(xyz[1] / WHITE[1]), f(xyz[2] / WHITE[2])];
+    Lab {
+        l: 116.0 * fy - 16.0,
+        a: 500.0 * (fx - fy),
+        b: 200.0 * (fy - fz),
+    }
+}
+
+pub fn lab_to_rgb(lab: Lab) -> [u8; 3] {
+    let fy = (lab.l + 16.0) / 116.0;
+    let fx = fy + lab.a / 500.0;
+    let fz = fy - lab.b / 200.0;
+    let f_inv = |t: f64| {
+        if t.powi(3) > 216.0 / 24389.0 {
+            t.powi(3)
+        } else {
+            (116.0 * t - 16.0) * 27.0 / 24389.0
+        }
+    };
+    let [x, y, z] = [f_inv(fx) * WHITE[0], f_inv(fy) * WHITE[1], f_inv(fz) * WHITE[2]];
+    [
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    ]
+    .map(linear_to_srgb)
+}
+
+/// Parse `#rrggbb` or `rrggbb`
+pub fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Extract the dominant colors of an image file
+pub fn extract_palette(path: &Path) -> Result<Vec<PaletteColor>, AppError> {
+    let img = aseprite::open_image(path)?;
+    Ok(palette_from_image(&img))
+}
+
+/// Dominant colors of an image, most common first, found by k-means clustering in CIELAB.
+/// Transparent pixels are ignored; coverage is the fraction of the visible pixels.
+pub fn palette_from_image(img: &DynamicImage) -> Vec<PaletteColor> {
+    let sample = img.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgba8();
+    let pixels: Vec<Lab> = sample
+        .pixels()
+        .filter(|p| p[3] >= MIN_ALPHA)
+        .map(|p| rgb_to_lab([p[0], p[1], p[2]]))
+        .collect();
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+
+    let mut centers = initial_centers(&pixels, MAX_COLORS);
+    let mut counts = vec![0usize; centers.len()];
+    for _ in 0..KMEANS_ITERATIONS {
+        let mut sums = vec![Lab::default(); centers.len()];
+        counts.iter_mut().for_each(|c| *c = 0);
+        for pixel in &pixels {
+            let i = nearest(&centers, pixel);
+            sums[i].l += pixel.l;
+            sums[i].a += pixel.a;
+            sums[i].b += pixel.b;
+            counts[i] += 1;
+        }
+        let mut moved = false;
+        for (i, center) in centers.iter_mut().enumerate() {
+            if counts[i] == 0 {
+                continue;
+            }
+            let n = counts[i] as f64;
+            let updated = Lab {
+                l: sums[i].l / n,
+                a: sums[i].a / n,
+                b: sums[i].b / n,
+            };
+            moved |= updated.distance(center) > 0.5;
+            *center = updated;
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    // Merge near-identical clusters, weighting by size
+    let mut clusters: Vec<(Lab, usize)> = centers.into_iter().zip(counts).filter(|(_, n)| *n > 0).collect();
+    clusters.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut merged: Vec<(Lab, usize)> = Vec::new();
+    for (lab, count) in clusters {
+        match merged.iter_mut().find(|(m, _)| m.distance(&lab) < MERGE_DISTANCE) {
+            Some((m, n)) => {
+                let total = (*n + count) as f64;
+                m.l = (m.l * *n as f64 + lab.l * count as f64) / total;
+                m.a = (m.a * *n as f64 + lab.a * count as f64) / total;
+                m.b = (m.b * *n as f64 + lab.b * count as f64) / total;
+                *n += count;
+            }
+            None => merged.push((lab, count)),
+        }
+    }
+    merged.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let total = pixels.len() as f64;
+    merged
+        .into_iter()
+        .map(|(lab, count)| (lab, count as f64 / total))
+        .filter(|(_, coverage)| *coverage >= MIN_COVERAGE)
+        .map(|(lab, coverage)| {
+            let [r, g, b] = lab_to_rgb(lab);
+            PaletteColor {
+                hex: format!("#{:02x}{:02x}{:02x}", r, g, b),
+                l: lab.l,
+                a: lab.a,
+                b: lab.b,
+                coverage,
+            }
+        })
+        .collect()
+}
+
+/// Deterministic farthest-point initialization: start from the mean color, then
+/// repeatedly add the pixel farthest from the chosen centers
+fn initial_centers(pixels: &[Lab], k: usize) -> Vec<Lab> {
+    let n = pixels.len() as f64;
+    let mean = pixels.iter().fold(Lab::default(), |acc, p| Lab {
+        l: acc.l + p.l / n,
+        a: acc.a + p.a / n,
+        b: acc.b + p.b / n,
+    });
+    let mut centers = vec![mean];
+    while centers.len() < k {
+        let farthest = pixels
+            .iter()
+            .map(|p| (p, centers.iter().map(|c| c.distance(p)).fold(f64::MAX, f64::min)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match farthest {
+            Some((pixel, distance)) if distance > MERGE_DISTANCE => centers.push(*pixel),
+            _ => break,
+        }
+    }
+    centers
+}
+
+fn nearest(centers: &[Lab], pixel: &Lab) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i, c.distance(pixel)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
@@ -0,0 +1,166 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::path::Path;
+
+/// Text beyond this size is not indexed for search
+const MAX_INDEXED_BYTES: usize = 256 * 1024;
+/// Files larger than this are not read at all
+const MAX_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+const PREVIEW_SIZE: u32 = 256;
+const PREVIEW_MARGIN: u32 = 8;
+const CHAR_WIDTH: u32 = 2;
+const LINE_HEIGHT: u32 = 4;
+
+/// Text read from a file for indexing
+pub struct TextContent {
+    pub language: String,
+    pub content: String,
+    pub byte_size: i64,
+    pub line_count: i64,
+    pub truncated: bool,
+}
+
+/// Read a text file for indexing, truncated to the indexing limit.
+/// Returns `None` for binary or oversized files.
+pub fn read_text_content(path: &Path) -> Option<TextContent> {
+    if std::fs::metadata(path).ok()?.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(path).ok()?;
+
+    // A NUL byte near the start means this is not a text file
+    if bytes.iter().take(8192).any(|b| *b == 0) {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&bytes);
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+    let truncated = text.len() > MAX_INDEXED_BYTES;
+    let content = if truncated {
+        let mut end = MAX_INDEXED_BYTES;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text[..end].to_string()
+    } else {
+        text.to_string()
+    };
+
+    Some(TextContent {
+        language: detect_language(path, &content),
+        line_count: text.lines().count() as i64,
+        byte_size: bytes.len() as i64,
+        truncated,
+        content,
+    })
+}
+
+/// Detect the language of a text asset from its extension, falling back to its content
+pub fn detect_language(path: &Path, content: &str) -> String {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let by_extension = match ext.as_str() {
+        "glsl" | "vert" | "frag" | "comp" | "geom" | "tesc" | "tese" => Some("glsl"),
+        "hlsl" | "hlsli" | "fx" | "fxh" => Some("hlsl"),
+        "wgsl" => Some("wgsl"),
+        "shader" => Some("shaderlab"),
+        "cginc" => Some("cg"),
+        "json" => Some("json"),
+        "xml" => Some("xml"),
+        "yaml" | "yml" => Some("yaml"),
+        "toml" => Some("toml"),
+        "ini" | "cfg" => Some("ini"),
+        "csv" => Some("csv"),
+        "md" => Some("markdown"),
+        _ => None,
+    };
+    if let Some(language) = by_extension {
+        return language.to_string();
+    }
+
+    let trimmed = content.trim_start();
+    let language = if trimmed.starts_with("#version") {
+        "glsl"
+    } else if trimmed.starts_with("Shader \"") {
+        "shaderlab"
+    } else if content.contains("@vertex") || content.contains("@fragment") || content.contains("@compute") {
+        "wgsl"
+    } else if content.contains("SV_Position") || content.contains("SV_Target") {
+        "hlsl"
+    } else if trimmed.starts_with("<?xml") || trimmed.starts_with('<') {
+        "xml"
+    } else if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(content).is_ok()
+    {
+        "json"
+    } else {
+        "plaintext"
+    };
+    language.to_string()
+}
+
+/// Render a minimap-style preview of the first lines of a text file.
+/// Each character is drawn as a small block colored by a rough token class.
+pub fn render_code_preview(content: &str) -> DynamicImage {
+    let background = Rgba([30, 30, 36, 255]);
+    let mut img = RgbaImage::from_pixel(PREVIEW_SIZE, PREVIEW_SIZE, background);
+
+    let max_lines = ((PREVIEW_SIZE - 2 * PREVIEW_MARGIN) / LINE_HEIGHT) as usize;
+    let max_cols = ((PREVIEW_SIZE - 2 * PREVIEW_MARGIN) / CHAR_WIDTH) as usize;
+
+    for (row, line) in content.lines().take(max_lines).enumerate() {
+        let trimmed = line.trim_start();
+        let is_comment = trimmed.starts_with("//")
+            || trimmed.starts_with('#')
+            || trimmed.starts_with(';')
+            || trimmed.starts_with("/*")
+            || trimmed.starts_with('*');
+        let mut in_string: Option<char> = None;
+
+        let mut col = 0usize;
+        for ch in line.chars() {
+            if col >= max_cols {
+                break;
+            }
+            if ch == '\t' {
+                col += 4;
+                continue;
+            }
+
+            let color = if is_comment {
+                Rgba([106, 153, 85, 255])
+            } else if let Some(quote) = in_string {
+                if ch == quote {
+                    in_string = None;
+                }
+                Rgba([206, 145, 120, 255])
+            } else if ch == '"' || ch == '\'' {
+                in_string = Some(ch);
+                Rgba([206, 145, 120, 255])
+            } else if ch.is_ascii_digit() {
+                Rgba([181, 206, 168, 255])
+            } else if ch.is_alphabetic() || ch == '_' {
+                Rgba([156, 220, 254, 255])
+            } else {
+                Rgba([212, 212, 212, 255])
+            };
+
+            if !ch.is_whitespace() {
+                let x0 = PREVIEW_MARGIN + col as u32 * CHAR_WIDTH;
+                let y0 = PREVIEW_MARGIN + row as u32 * LINE_HEIGHT;
+                for dy in 0..LINE_HEIGHT - 1 {
+                    for dx in 0..CHAR_WIDTH {
+                        img.put_pixel(x0 + dx, y0 + dy, color);
+                    }
+                }
+            }
+            col += 1;
+        }
+    }
+
+    DynamicImage::ImageRgba8(img)
+}
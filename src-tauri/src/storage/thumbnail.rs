@@ -1,4 +1,5 @@
 use image::imageops::FilterType;
+use image::DynamicImage;
 use std::path::Path;
 
 const THUMBNAIL_SIZE: u32 = 256;
@@ -11,6 +12,16 @@ pub fn generate_thumbnail(
     asset_id: &str,
 ) -> Result<String, image::ImageError> {
     let img = image::open(source)?;
+    generate_thumbnail_from_image(&img, library_root, asset_id)
+}
+
+/// Generate a thumbnail from an already decoded image.
+/// Returns the relative path from the library root.
+pub fn generate_thumbnail_from_image(
+    img: &DynamicImage,
+    library_root: &Path,
+    asset_id: &str,
+) -> Result<String, image::ImageError> {
     let thumb = img.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Lanczos3);
 
     let thumb_dir = library_root.join(".thumbnails");
@@ -1,4 +1,4 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useMemo } from "react";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { MainLayout } from "./components/layout/MainLayout";
@@ -37,9 +37,25 @@ function AppContent() {
   const [showLibraryMgmt, setShowLibraryMgmt] = useState(false);
   const [selectedAssetId, setSelectedAssetId] = useState<string | null>(null);
   const [searchResults, setSearchResults] = useState<Asset[] | null>(null);
+  // Keyword search the results came from and the cursor of their next page
+  const [keywordQuery, setKeywordQuery] = useState<string | null>(null);
+  const [searchCursor, setSearchCursor] = useState<string | null>(null);
 
   const { data: libraries } = useLibraries();
-  const { data: assetsData } = useAssets();
+  const {
+    data: assetPages,
+    fetchNextPage,
+    hasNextPage,
+    isFetchingNextPage,
+  } = useAssets();
+  const assetsData = useMemo(
+    () =>
+      assetPages && {
+        assets: assetPages.pages.flatMap((p) => p.assets),
+        total: assetPages.pages[0]?.total ?? 0,
+      },
+    [assetPages]
+  );
   const keywordSearch = useKeywordSearch();
   const semanticSearch = useSemanticSearch();
 
@@ -113,6 +129,8 @@ function AppContent() {
         {
           onSuccess: (results) => {
             setSearchResults(results.map((r) => r.asset));
+            setKeywordQuery(null);
+            setSearchCursor(null);
           },
         }
       );
@@ -127,12 +145,45 @@ function AppContent() {
         {
           onSuccess: (results) => {
             setSearchResults(results.assets);
+            setKeywordQuery(query);
+            setSearchCursor(results.next_cursor);
+          },
+        }
+      );
+    }
+  };
+
+  const clearSearch = () => {
+    setSearchResults(null);
+    setKeywordQuery(null);
+    setSearchCursor(null);
+  };
+
+  const loadMore = () => {
+    if (searchResults) {
+      if (!currentLibrary || !keywordQuery || !searchCursor || keywordSearch.isPending) return;
+      keywordSearch.mutate(
+        {
+          libraryId: currentLibrary.id,
+          query: keywordQuery,
+          page: 1,
+          pageSize: 100,
+          cursor: searchCursor,
+        },
+        {
+          onSuccess: (results) => {
+            setSearchResults((prev) => [...(prev ?? []), ...results.assets]);
+            setSearchCursor(results.next_cursor);
           },
         }
       );
+    } else if (hasNextPage && !isFetchingNextPage) {
+      fetchNextPage();
     }
   };
 
+  const canLoadMore = searchResults ? !!searchCursor : !!hasNextPage;
+
   const handleAssetClick = (asset: Asset) => {
     setSelectedAssetId(asset.id);
   };
@@ -170,21 +221,47 @@ function AppContent() {
     return (
       <div className="flex flex-1 overflow-hidden p-6">
         <DropZone onOpenSettings={() => { window.location.hash = "#/settings"; }}>
-          <AssetGrid assets={displayAssets} onAssetClick={handleAssetClick} />
+          <AssetGrid
+            assets={displayAssets}
+            onAssetClick={handleAssetClick}
+            onEndReached={canLoadMore ? loadMore : undefined}
+          />
           {assetsData && !searchResults && (
-            <div className="px-4 py-2 border-t border-border text-xs text-text-secondary">
-              {assetsData.total} assets total
+            <div className="px-4 py-2 border-t border-border text-xs text-text-secondary flex items-center justify-between">
+              <span>
+                {assetsData.assets.length} of {assetsData.total} assets
+              </span>
+              {canLoadMore && (
+                <button
+                  onClick={loadMore}
+                  disabled={isFetchingNextPage}
+                  className="text-primary hover:underline disabled:opacity-50"
+                >
+                  Load more
+                </button>
+              )}
             </div>
           )}
           {searchResults && (
             <div className="px-4 py-2 border-t border-border text-xs text-text-secondary flex items-center justify-between">
               <span>{searchResults.length} results</span>
-              <button
-                onClick={() => setSearchResults(null)}
-                className="text-primary hover:underline"
-              >
-                Clear search
-              </button>
+              <div className="flex items-center gap-3">
+                {canLoadMore && (
+                  <button
+                    onClick={loadMore}
+                    disabled={keywordSearch.isPending}
+                    className="text-primary hover:underline disabled:opacity-50"
+                  >
+                    Load more
+                  </button>
+                )}
+                <button
+                  onClick={clearSearch}
+                  className="text-primary hover:underline"
+                >
+                  Clear search
+                </button>
+              </div>
             </div>
           )}
         </DropZone>
@@ -15,7 +15,7 @@ export function AssetCard({ asset, onClick }: AssetCardProps) {
   const [thumbSrc, setThumbSrc] = useState<string | null>(null);
 
   useEffect(() => {
-    if (asset.file_type === "image" && asset.thumbnail_path) {
+    if (asset.thumbnail_path) {
       getThumbnailData(asset.id).then((dataUrl) => {
         if (dataUrl) setThumbSrc(dataUrl);
       });
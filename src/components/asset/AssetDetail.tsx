@@ -189,8 +189,9 @@ export function AssetDetail({ assetId, onClose }: AssetDetailProps) {
                 key={tag.id}
                 className="inline-flex items-center gap-1 px-2 py-0.5 text-xs rounded-full border border-border"
                 style={{ borderColor: tag.color, color: tag.color }}
+                title={tag.path}
               >
-                {tag.name}
+                {tag.path}
                 <button
                   onClick={() => handleRemoveTag(tag.id)}
                   className="hover:opacity-70"
@@ -1,4 +1,4 @@
-import { useState, useRef, useEffect, MouseEvent } from "react";
+import { useState, useRef, useEffect, MouseEvent, UIEvent } from "react";
 import { AssetCard } from "./AssetCard";
 import type { Asset } from "../../types/asset";
 import { useAppStore } from "../../stores/appStore";
@@ -6,8 +6,13 @@ import { useAppStore } from "../../stores/appStore";
 interface AssetGridProps {
   assets: Asset[];
   onAssetClick: (asset: Asset) => void;
+  /** Called when the user scrolls near the end, to load more assets */
+  onEndReached?: () => void;
 }
 
+// Distance from the bottom (px) at which the next page is requested
+const END_REACHED_THRESHOLD = 400;
+
 interface SelectionBox {
   startX: number;
   startY: number;
@@ -15,7 +20,7 @@ interface SelectionBox {
   endY: number;
 }
 
-export function AssetGrid({ assets, onAssetClick }: AssetGridProps) {
+export function AssetGrid({ assets, onAssetClick, onEndReached }: AssetGridProps) {
   const viewMode = useAppStore((s) => s.viewMode);
   const { setSelectedAssetIds } = useAppStore();
 
@@ -94,6 +99,13 @@ export function AssetGrid({ assets, onAssetClick }: AssetGridProps) {
     }
   }, [isSelecting]);
 
+  const handleScroll = (e: UIEvent<HTMLDivElement>) => {
+    const el = e.currentTarget;
+    if (onEndReached && el.scrollHeight - el.scrollTop - el.clientHeight < END_REACHED_THRESHOLD) {
+      onEndReached();
+    }
+  };
+
   const setCardRef = (assetId: string, el: HTMLDivElement | null) => {
     if (el) {
       cardRefs.current.set(assetId, el);
@@ -115,7 +127,7 @@ export function AssetGrid({ assets, onAssetClick }: AssetGridProps) {
 
   if (viewMode === "list") {
     return (
-      <div className="flex-1 overflow-y-auto">
+      <div className="flex-1 overflow-y-auto" onScroll={handleScroll}>
         <table className="w-full text-sm">
           <thead>
             <tr className="text-left text-text-secondary border-b border-border">
@@ -164,6 +176,7 @@ export function AssetGrid({ assets, onAssetClick }: AssetGridProps) {
       className="flex-1 overflow-y-auto relative select-none"
       onMouseDown={handleMouseDown}
       onMouseMove={handleMouseMove}
+      onScroll={handleScroll}
     >
       <div className="grid grid-cols-[repeat(auto-fill,minmax(180px,1fr))] gap-3">
         {assets.map((asset) => (
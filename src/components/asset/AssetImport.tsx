@@ -6,7 +6,7 @@ import { useAppStore } from "../../stores/appStore";
 import { aiTagAsset, getAiConfig } from "../../services/tauriBridge";
 import { useTranslation } from "react-i18next";
 import { AiConfigHintDialog } from "../common/AiConfigHintDialog";
-import type { AiConfig } from "../../types/asset";
+import { SUPPORTED_EXTENSIONS, type AiConfig } from "../../types/asset";
 
 interface AssetImportProps {
   open: boolean;
@@ -17,12 +17,6 @@ interface AssetImportProps {
 const STORAGE_KEY_DONT_SHOW_AI_NOT_CONFIGURED = "yingge_dont_show_ai_not_configured";
 const STORAGE_KEY_DONT_SHOW_AI_CONNECTION_FAILED = "yingge_dont_show_ai_connection_failed";
 
-const SUPPORTED_EXTENSIONS = [
-  "png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "tiff",
-  "mp3", "wav", "ogg", "flac", "aac", "m4a",
-  "mp4", "avi", "mov", "webm",
-];
-
 // Check if AI config is valid (has required fields)
 function isAiConfigValid(config: AiConfig | null): boolean {
   if (!config) return false;
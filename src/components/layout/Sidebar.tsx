@@ -1,15 +1,17 @@
 import { useState, useEffect } from "react";
 import { FolderOpen, Plus, ChevronDown, ChevronRight } from "lucide-react";
 import { useTranslation } from "react-i18next";
-import { open } from "@tauri-apps/plugin-dialog";
+import { confirm, open } from "@tauri-apps/plugin-dialog";
 import { useAppStore } from "../../stores/appStore";
 import { useFolders, useImportAssets } from "../../hooks/useAssets";
 import { useLibraries, useCreateLibrary } from "../../hooks/useLibrary";
-import type { Library as LibraryType } from "../../types/asset";
+import { SUPPORTED_EXTENSIONS, type FolderInfo, type Library as LibraryType } from "../../types/asset";
 import { ContextMenu, type ContextMenuItem } from "../common/ContextMenu";
 import { useMutation, useQueryClient, useQuery } from "@tanstack/react-query";
 import * as api from "../../services/tauriBridge";
 
+const FOLDER_DRAG_TYPE = "application/x-yingge-folder";
+
 export function Sidebar() {
   const { t } = useTranslation();
   const queryClient = useQueryClient();
@@ -84,11 +86,20 @@ export function Sidebar() {
     },
   });
 
+  // Keep the selection on a folder that was renamed or moved (or on one of its subfolders)
+  const followMovedFolder = (oldPath: string, folder: FolderInfo) => {
+    if (currentFolder === oldPath || currentFolder.startsWith(oldPath + "/")) {
+      setCurrentFolder(folder.path + currentFolder.slice(oldPath.length));
+    }
+    queryClient.invalidateQueries({ queryKey: ["folders"] });
+    queryClient.invalidateQueries({ queryKey: ["assets"] });
+  };
+
   const renameFolderMutation = useMutation({
     mutationFn: ({ oldPath, newName }: { oldPath: string; newName: string }) =>
       api.renameFolder(currentLibrary!.id, oldPath, newName),
-    onSuccess: () => {
-      queryClient.invalidateQueries({ queryKey: ["folders"] });
+    onSuccess: (folder, { oldPath }) => {
+      followMovedFolder(oldPath, folder);
       setRenamingFolder(null);
       setRenameValue("");
     },
@@ -98,6 +109,65 @@ export function Sidebar() {
     },
   });
 
+  const moveFolderMutation = useMutation({
+    mutationFn: ({ path, newParentPath }: { path: string; newParentPath: string }) =>
+      api.moveFolder(currentLibrary!.id, path, newParentPath),
+    onSuccess: (folder, { path }) => followMovedFolder(path, folder),
+    onError: (error) => {
+      console.error("Failed to move folder:", error);
+      alert(`移动文件夹失败: ${error}`);
+    },
+  });
+
+  const handleDeleteFolder = async (folderPath: string) => {
+    if (!currentLibrary) return;
+    try {
+      const summary = await api.getFolderDeleteSummary(currentLibrary.id, folderPath);
+      const confirmed = await confirm(
+        t("folder.deleteMessage", {
+          assets: summary.asset_count,
+          folders: summary.folder_count,
+        }),
+        {
+          title: t("delete.title"),
+          kind: "warning",
+          okLabel: t("common.delete"),
+          cancelLabel: t("common.cancel"),
+        }
+      );
+      if (!confirmed) return;
+
+      await api.deleteFolder(currentLibrary.id, folderPath, true);
+      if (currentFolder === folderPath || currentFolder.startsWith(folderPath + "/")) {
+        setCurrentFolder("/");
+      }
+      queryClient.invalidateQueries({ queryKey: ["folders"] });
+      queryClient.invalidateQueries({ queryKey: ["assets"] });
+    } catch (e) {
+      console.error("Failed to delete folder:", e);
+      alert(`删除文件夹失败: ${e}`);
+    }
+  };
+
+  const handleFolderDragOver = (e: React.DragEvent) => {
+    if (e.dataTransfer.types.includes(FOLDER_DRAG_TYPE)) {
+      e.preventDefault();
+      e.dataTransfer.dropEffect = "move";
+    }
+  };
+
+  const handleFolderDrop = (e: React.DragEvent, targetPath: string) => {
+    const path = e.dataTransfer.getData(FOLDER_DRAG_TYPE);
+    if (!path) return;
+    e.preventDefault();
+    e.stopPropagation();
+    const currentParent = path.slice(0, path.lastIndexOf("/")) || "/";
+    if (targetPath === path || targetPath.startsWith(path + "/") || targetPath === currentParent) {
+      return;
+    }
+    moveFolderMutation.mutate({ path, newParentPath: targetPath });
+  };
+
   if (!sidebarOpen) return null;
 
   const handleCreateLibrary = () => {
@@ -156,12 +226,6 @@ export function Sidebar() {
         label: t("folder.import", "Import"),
         onClick: async () => {
           setContextMenu(null);
-          const SUPPORTED_EXTENSIONS = [
-            "png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "tiff",
-            "mp3", "wav", "ogg", "flac", "aac", "m4a",
-            "mp4", "avi", "mov", "webm",
-          ];
-
           const selected = await open({
             multiple: true,
             directory: false,
@@ -208,6 +272,13 @@ export function Sidebar() {
           setRenameValue(folder?.name || "");
         },
       });
+      items.push({
+        label: t("folder.delete", "Delete Folder"),
+        onClick: () => {
+          setContextMenu(null);
+          handleDeleteFolder(folderPath);
+        },
+      });
     }
 
     return items;
@@ -226,15 +297,10 @@ export function Sidebar() {
   };
 
   const isFolderVisible = (folderPath: string): boolean => {
-    if (!folderPath.includes("/")) {
-      // Top-level folder, always visible if root is expanded
-      return rootExpanded;
-    }
-
-    // Check if all parent folders are expanded
-    const parts = folderPath.split("/");
+    // Check if all parent folders are expanded (paths are canonical: "/a/b")
+    const parts = folderPath.split("/").filter((p) => p);
     for (let i = 1; i < parts.length; i++) {
-      const parentPath = parts.slice(0, i).join("/");
+      const parentPath = "/" + parts.slice(0, i).join("/");
       if (!expandedFolders.has(parentPath)) {
         return false;
       }
@@ -329,6 +395,8 @@ export function Sidebar() {
                 window.location.hash = '#/';
               }
             }}
+            onDragOver={handleFolderDragOver}
+            onDrop={(e) => handleFolderDrop(e, "/")}
             className={`w-full flex items-center justify-between gap-1 px-2 py-1.5 text-sm rounded transition-colors cursor-pointer ${
               currentFolder === "/"
                 ? "bg-primary/20 text-primary"
@@ -416,6 +484,13 @@ export function Sidebar() {
                         e.stopPropagation();
                         handleContextMenu(e, folder.path);
                       }}
+                      draggable
+                      onDragStart={(e) => {
+                        e.dataTransfer.setData(FOLDER_DRAG_TYPE, folder.path);
+                        e.dataTransfer.effectAllowed = "move";
+                      }}
+                      onDragOver={handleFolderDragOver}
+                      onDrop={(e) => handleFolderDrop(e, folder.path)}
                       className={`w-full flex items-center justify-between gap-1 px-2 py-1.5 text-sm rounded transition-colors cursor-pointer ${
                         currentFolder === folder.path
                           ? "bg-primary/20 text-primary"
@@ -440,10 +515,14 @@ export function Sidebar() {
                         ) : (
                           <span className="w-3.5 flex-shrink-0" />
                         )}
-                        <FolderOpen size={14} className="flex-shrink-0" />
+                        <FolderOpen
+                          size={14}
+                          className="flex-shrink-0"
+                          style={folder.color ? { color: folder.color } : undefined}
+                        />
                         {folder.name}
                       </span>
-                      <span className="text-xs opacity-60">{folder.asset_count}</span>
+                      <span className="text-xs opacity-60">{folder.total_asset_count}</span>
                     </div>
                   )}
                 </div>
@@ -1,6 +1,6 @@
 import { useState } from "react";
-import { Plus, Trash2, Edit2, Save, X } from "lucide-react";
-import { useTags, useCreateTag, useDeleteTag } from "../../hooks/useTags";
+import { Plus, Trash2, Edit2, Save, X, CornerDownRight, Move } from "lucide-react";
+import { useTags, useCreateTag, useDeleteTag, useMoveTag } from "../../hooks/useTags";
 import { useAppStore } from "../../stores/appStore";
 import { renameTag as renameTagApi } from "../../services/tauriBridge";
 import { useQueryClient } from "@tanstack/react-query";
@@ -10,12 +10,17 @@ export function TagManager() {
   const { data: tags } = useTags();
   const createTag = useCreateTag();
   const deleteTag = useDeleteTag();
+  const moveTag = useMoveTag();
   const queryClient = useQueryClient();
 
   const [newTagName, setNewTagName] = useState("");
   const [newTagColor, setNewTagColor] = useState("#6366f1");
   const [editingId, setEditingId] = useState<string | null>(null);
   const [editName, setEditName] = useState("");
+  const [newTagParentId, setNewTagParentId] = useState<string | null>(null);
+  const [movingId, setMovingId] = useState<string | null>(null);
+
+  const newTagParent = tags?.find((t) => t.id === newTagParentId);
 
   const handleCreate = () => {
     if (newTagName && currentLibrary) {
@@ -23,11 +28,17 @@ export function TagManager() {
         libraryId: currentLibrary.id,
         name: newTagName,
         color: newTagColor,
+        parentId: newTagParentId ?? undefined,
       });
       setNewTagName("");
     }
   };
 
+  const handleMove = (id: string, parentId: string) => {
+    moveTag.mutate({ id, parentId: parentId || null });
+    setMovingId(null);
+  };
+
   const handleRename = async (id: string) => {
     await renameTagApi(id, editName);
     queryClient.invalidateQueries({ queryKey: ["tags"] });
@@ -39,10 +50,22 @@ export function TagManager() {
       <h1 className="text-xl font-semibold mb-6">Tag Manager</h1>
 
       {/* Create new tag */}
+      {newTagParent && (
+        <div className="flex items-center gap-1.5 mb-2 text-xs text-text-secondary">
+          <CornerDownRight size={12} />
+          Child of <span className="text-text-primary">{newTagParent.path}</span>
+          <button
+            onClick={() => setNewTagParentId(null)}
+            className="p-0.5 rounded hover:bg-bg-tertiary"
+          >
+            <X size={12} />
+          </button>
+        </div>
+      )}
       <div className="flex gap-2 mb-6">
         <input
           type="text"
-          placeholder="New tag name"
+          placeholder={newTagParent ? "New child tag name" : "New tag name"}
           value={newTagName}
           onChange={(e) => setNewTagName(e.target.value)}
           onKeyDown={(e) => e.key === "Enter" && handleCreate()}
@@ -74,7 +97,11 @@ export function TagManager() {
               key={tag.id}
               className="flex items-center justify-between px-3 py-2 rounded hover:bg-bg-secondary transition-colors"
             >
-              <div className="flex items-center gap-3">
+              <div
+                className="flex items-center gap-3"
+                style={{ paddingLeft: `${(tag.path.split("/").length - 1) * 20}px` }}
+                title={tag.path}
+              >
                 <span
                   className="w-3 h-3 rounded-full"
                   style={{ backgroundColor: tag.color }}
@@ -110,9 +137,46 @@ export function TagManager() {
                 )}
               </div>
               <div className="flex items-center gap-3">
-                <span className="text-xs text-text-secondary">
-                  {tag.asset_count} assets
+                {movingId === tag.id && (
+                  <select
+                    autoFocus
+                    defaultValue={tag.parent_id ?? ""}
+                    onChange={(e) => handleMove(tag.id, e.target.value)}
+                    onBlur={() => setMovingId(null)}
+                    className="px-2 py-0.5 text-xs bg-bg rounded border border-border focus:border-primary focus:outline-none"
+                  >
+                    <option value="">Top level</option>
+                    {(tags ?? [])
+                      .filter((t) => t.id !== tag.id && !t.path.startsWith(`${tag.path}/`))
+                      .map((t) => (
+                        <option key={t.id} value={t.id}>
+                          {t.path}
+                        </option>
+                      ))}
+                  </select>
+                )}
+                <span
+                  className="text-xs text-text-secondary"
+                  title={`${tag.asset_count} tagged directly, ${tag.total_count} including child tags`}
+                >
+                  {tag.total_count === tag.asset_count
+                    ? `${tag.asset_count} assets`
+                    : `${tag.asset_count} / ${tag.total_count} assets`}
                 </span>
+                <button
+                  onClick={() => setNewTagParentId(tag.id)}
+                  title="Add child tag"
+                  className="p-1 rounded hover:bg-bg-tertiary text-text-secondary"
+                >
+                  <CornerDownRight size={14} />
+                </button>
+                <button
+                  onClick={() => setMovingId(movingId === tag.id ? null : tag.id)}
+                  title="Move"
+                  className="p-1 rounded hover:bg-bg-tertiary text-text-secondary"
+                >
+                  <Move size={14} />
+                </button>
                 <button
                   onClick={() => {
                     setEditingId(tag.id);
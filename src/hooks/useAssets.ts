@@ -1,22 +1,28 @@
-import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { useQuery, useInfiniteQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import * as api from "../services/tauriBridge";
 import { useAppStore } from "../stores/appStore";
+import type { AssetFilter } from "../types/asset";
 
-export function useAssets(page = 1, pageSize = 50) {
+/** Pages through the current folder by cursor; `fetchNextPage` loads the next page */
+export function useAssets(pageSize = 50, filter?: AssetFilter) {
   const libraryId = useAppStore((s) => s.currentLibrary?.id);
   const folderPath = useAppStore((s) => s.currentFolder);
 
-  return useQuery({
-    queryKey: ["assets", libraryId, folderPath, page, pageSize],
-    queryFn: () =>
+  return useInfiniteQuery({
+    queryKey: ["assets", libraryId, folderPath, pageSize, filter],
+    queryFn: ({ pageParam }) =>
       api.getAssets({
         libraryId: libraryId!,
         folderPath,
-        page,
+        filter,
+        page: 1,
         pageSize,
         sortBy: "date",
         sortOrder: "desc",
+        cursor: pageParam,
       }),
+    initialPageParam: undefined as string | undefined,
+    getNextPageParam: (lastPage) => lastPage.next_cursor ?? undefined,
     enabled: !!libraryId,
   });
 }
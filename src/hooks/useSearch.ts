@@ -1,5 +1,6 @@
 import { useMutation } from "@tanstack/react-query";
 import * as api from "../services/tauriBridge";
+import type { AssetFilter } from "../types/asset";
 
 export function useKeywordSearch() {
   return useMutation({
@@ -7,9 +8,14 @@ export function useKeywordSearch() {
       libraryId: string;
       query: string;
       tagIds?: string[];
+      includeTagDescendants?: boolean;
       fileType?: string;
+      filter?: AssetFilter;
       page: number;
       pageSize: number;
+      sortBy?: string;
+      sortOrder?: string;
+      cursor?: string;
     }) => api.searchKeyword(params),
   });
 }
@@ -30,14 +36,17 @@ export function useSemanticSearch() {
 
 export function useTagSearch() {
   return useMutation({
-    mutationFn: ({
-      libraryId,
-      tagIds,
-      matchAll,
-    }: {
+    mutationFn: (params: {
       libraryId: string;
       tagIds: string[];
       matchAll: boolean;
-    }) => api.searchByTags(libraryId, tagIds, matchAll),
+      filter?: AssetFilter;
+      includeDescendants?: boolean;
+      page: number;
+      pageSize: number;
+      sortBy?: string;
+      sortOrder?: string;
+      cursor?: string;
+    }) => api.searchByTags(params),
   });
 }
@@ -21,18 +21,33 @@ export function useCreateTag() {
       name,
       color,
       category,
+      parentId,
     }: {
       libraryId: string;
       name: string;
       color?: string;
       category?: string;
-    }) => api.createTag(libraryId, name, color, category),
+      parentId?: string;
+    }) => api.createTag(libraryId, name, color, category, parentId),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["tags"] });
     },
   });
 }
 
+export function useMoveTag() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, parentId }: { id: string; parentId: string | null }) =>
+      api.moveTag(id, parentId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["tags"] });
+      queryClient.invalidateQueries({ queryKey: ["asset-detail"] });
+    },
+  });
+}
+
 export function useDeleteTag() {
   const queryClient = useQueryClient();
 
@@ -4,9 +4,38 @@ import type {
   Asset,
   PaginatedAssets,
   AssetDetail,
+  AssetFilter,
+  BatchRenameOptions,
+  BatchRenameResult,
+  ExportSelection,
+  ExportOptions,
+  ExportResult,
+  BulkOperation,
+  BulkQueryResult,
+  RenamePreview,
+  NameSuggestion,
+  CollectionInfo,
+  AssetComment,
+  LicenseUpdate,
+  ColorMatch,
+  UsageProject,
+  UsageScanResult,
+  LicenseUsage,
+  CommentAnchor,
+  CommentSearchResult,
+  SmartCollectionQuery,
+  ColorLabel,
+  AssetFieldValue,
+  CustomField,
+  CustomFieldType,
+  AssetVersion,
+  AssetLineage,
+  DerivedAsset,
   FolderInfo,
+  FolderTreeSummary,
   Tag,
   TagWithCount,
+  TagAlias,
   AiConfig,
   AiConfigInput,
   AiTagResult,
@@ -24,6 +53,10 @@ export const listLibraries = () => invoke<Library[]>("list_libraries");
 export const getLibrary = (id: string) =>
   invoke<Library>("get_library", { id });
 
+/** Apply AI suggested names right away instead of queueing them for review */
+export const setLibraryAutoApplyAiNames = (id: string, enabled: boolean) =>
+  invoke<Library>("set_library_auto_apply_ai_names", { id, enabled });
+
 export const deleteLibrary = (id: string) =>
   invoke<void>("delete_library", { id });
 
@@ -32,46 +65,98 @@ export const deleteLibraryWithFiles = (id: string) =>
 
 // --- Asset ---
 
+/** With license, every imported asset gets the same license and attribution */
 export const importAssets = (
   libraryId: string,
   filePaths: string[],
-  folderPath: string
+  folderPath: string,
+  license?: LicenseUpdate
 ) =>
-  invoke<Asset[]>("import_assets", { libraryId, filePaths, folderPath });
+  invoke<Asset[]>("import_assets", { libraryId, filePaths, folderPath, license });
 
 export const getAssets = (params: {
   libraryId: string;
   folderPath?: string;
   fileType?: string;
+  filter?: AssetFilter;
   page: number;
   pageSize: number;
+  /** "name", "size", "date", "rating" or "field:<field id>" */
   sortBy: string;
   sortOrder: string;
+  /** next_cursor of the previous page; page is ignored when set */
+  cursor?: string;
 }) => invoke<PaginatedAssets>("get_assets", params);
 
 export const getAssetDetail = (id: string) =>
   invoke<AssetDetail>("get_asset_detail", { id });
 
+export const refreshImageMetadata = (libraryId: string, force: boolean) =>
+  invoke<number>("refresh_image_metadata", { libraryId, force });
+
+export const refreshColorPalettes = (libraryId: string, force: boolean) =>
+  invoke<number>("refresh_color_palettes", { libraryId, force });
+
 export const renameAsset = (id: string, newName: string) =>
   invoke<void>("rename_asset", { id, newName });
 
 export const updateDescription = (id: string, description: string) =>
   invoke<void>("update_description", { id, description });
 
+export const bulkUpdateDescription = (assetIds: string[], description: string) =>
+  invoke<void>("bulk_update_description", { assetIds, description });
+
+/** Rating from 0 (unrated) to 5 stars */
+export const setAssetRating = (assetIds: string[], rating: number) =>
+  invoke<void>("set_asset_rating", { assetIds, rating });
+
+/** Pass null to clear the label */
+export const setAssetColorLabel = (assetIds: string[], colorLabel: ColorLabel | null) =>
+  invoke<void>("set_asset_color_label", { assetIds, colorLabel });
+
+export const setAssetFavorite = (assetIds: string[], favorite: boolean) =>
+  invoke<void>("set_asset_favorite", { assetIds, favorite });
+
 export const deleteAssets = (ids: string[]) =>
   invoke<void>("delete_assets", { ids });
 
 export const moveAssets = (ids: string[], targetFolder: string) =>
   invoke<void>("move_assets", { ids, targetFolder });
 
+/** With dryRun nothing changes and the result previews old and new names */
+export const batchRename = (assetIds: string[], options: BatchRenameOptions, dryRun: boolean) =>
+  invoke<BatchRenameResult>("batch_rename", { assetIds, options, dryRun });
+
+/** Copies assets under their real names; failures are reported per file */
+export const exportAssets = (selection: ExportSelection, options: ExportOptions) =>
+  invoke<ExportResult>("export_assets", { selection, options });
+
 export const getFolders = (libraryId: string) =>
   invoke<FolderInfo[]>("get_folders", { libraryId });
 
 export const createFolder = (libraryId: string, folderName: string, parentPath: string) =>
-  invoke<void>("create_folder", { libraryId, folderName, parentPath });
+  invoke<FolderInfo>("create_folder", { libraryId, folderName, parentPath });
+
+/** Omitted fields are kept; an empty color or icon clears it */
+export const updateFolder = (params: {
+  id: string;
+  color?: string;
+  icon?: string;
+  description?: string;
+  defaultTagIds?: string[];
+}) => invoke<FolderInfo>("update_folder", params);
 
 export const renameFolder = (libraryId: string, oldPath: string, newName: string) =>
-  invoke<void>("rename_folder", { libraryId, oldPath, newName });
+  invoke<FolderInfo>("rename_folder", { libraryId, oldPath, newName });
+
+export const getFolderDeleteSummary = (libraryId: string, path: string) =>
+  invoke<FolderTreeSummary>("get_folder_delete_summary", { libraryId, path });
+
+export const deleteFolder = (libraryId: string, path: string, useTrash: boolean) =>
+  invoke<FolderTreeSummary>("delete_folder", { libraryId, path, useTrash });
+
+export const moveFolder = (libraryId: string, path: string, newParentPath: string) =>
+  invoke<FolderInfo>("move_folder", { libraryId, path, newParentPath });
 
 export const getAssetFilePath = (id: string) =>
   invoke<string>("get_asset_file_path", { id });
@@ -82,14 +167,174 @@ export const getThumbnailPath = (id: string) =>
 export const getThumbnailData = (id: string) =>
   invoke<string | null>("get_thumbnail_data", { id });
 
+// --- Versions ---
+
+export const replaceAssetFile = (id: string, filePath: string, note?: string) =>
+  invoke<Asset>("replace_asset_file", { id, filePath, note });
+
+export const listAssetVersions = (assetId: string) =>
+  invoke<AssetVersion[]>("list_asset_versions", { assetId });
+
+export const getVersionThumbnailData = (versionId: string) =>
+  invoke<string | null>("get_version_thumbnail_data", { versionId });
+
+export const getVersionFilePath = (versionId: string) =>
+  invoke<string>("get_version_file_path", { versionId });
+
+export const revertAssetVersion = (versionId: string, note?: string) =>
+  invoke<Asset>("revert_asset_version", { versionId, note });
+
+// --- Collections ---
+
+export const getCollections = (libraryId: string) =>
+  invoke<CollectionInfo[]>("get_collections", { libraryId });
+
+/** With a query the collection is smart, otherwise manual */
+export const createCollection = (params: {
+  libraryId: string;
+  name: string;
+  description?: string;
+  query?: SmartCollectionQuery;
+}) => invoke<CollectionInfo>("create_collection", params);
+
+export const updateCollection = (params: {
+  id: string;
+  name?: string;
+  description?: string;
+  query?: SmartCollectionQuery;
+}) => invoke<CollectionInfo>("update_collection", params);
+
+export const deleteCollection = (id: string) =>
+  invoke<void>("delete_collection", { id });
+
+export const addToCollection = (collectionId: string, assetIds: string[]) =>
+  invoke<CollectionInfo>("add_to_collection", { collectionId, assetIds });
+
+export const removeFromCollection = (collectionId: string, assetIds: string[]) =>
+  invoke<CollectionInfo>("remove_from_collection", { collectionId, assetIds });
+
+/** Moves the given assets to the start of a manual collection, in order */
+export const reorderCollection = (collectionId: string, assetIds: string[]) =>
+  invoke<void>("reorder_collection", { collectionId, assetIds });
+
+export const getCollectionAssets = (params: {
+  collectionId: string;
+  page: number;
+  pageSize: number;
+  /** Like getAssets, plus "position" for manual collections */
+  sortBy: string;
+  sortOrder: string;
+  cursor?: string;
+}) => invoke<PaginatedAssets>("get_collection_assets", params);
+
+// --- Licenses ---
+
+/** Unset fields are kept; empty strings clear them */
+export const setAssetLicense = (assetIds: string[], update: LicenseUpdate) =>
+  invoke<void>("set_asset_license", { assetIds, update });
+
+export const getLicenseUsage = (libraryId: string) =>
+  invoke<LicenseUsage[]>("get_license_usage", { libraryId });
+
+/** Returns the credits file, also written to outputPath if given */
+export const generateCredits = (params: {
+  assetIds: string[];
+  format: "markdown" | "text" | "json";
+  attributionOnly: boolean;
+  outputPath?: string;
+}) => invoke<string>("generate_credits", params);
+
+// --- Usage ---
+
+/** Matches files by content hash and, with perceptual, similar images */
+export const scanProjectUsage = (params: {
+  libraryId: string;
+  projectPath: string;
+  perceptual?: boolean;
+  maxHashDistance?: number;
+}) => invoke<UsageScanResult>("scan_project_usage", params);
+
+export const getUsageProjects = (libraryId: string) =>
+  invoke<UsageProject[]>("get_usage_projects", { libraryId });
+
+export const clearProjectUsage = (libraryId: string, projectPath: string) =>
+  invoke<void>("clear_project_usage", { libraryId, projectPath });
+
+// --- Comments ---
+
+/** With parentId the comment is a reply in that comment's thread */
+export const addComment = (params: {
+  assetId: string;
+  body: string;
+  parentId?: string;
+  anchor?: CommentAnchor;
+  author?: string;
+}) => invoke<AssetComment>("add_comment", params);
+
+export const getAssetComments = (assetId: string) =>
+  invoke<AssetComment[]>("get_asset_comments", { assetId });
+
+export const updateComment = (id: string, body: string) =>
+  invoke<AssetComment>("update_comment", { id, body });
+
+/** Only the first comment of a thread can be resolved */
+export const setCommentResolved = (id: string, resolved: boolean) =>
+  invoke<AssetComment>("set_comment_resolved", { id, resolved });
+
+export const deleteComment = (id: string) =>
+  invoke<void>("delete_comment", { id });
+
+export const searchComments = (params: {
+  libraryId: string;
+  query: string;
+  includeResolved?: boolean;
+  limit?: number;
+}) => invoke<CommentSearchResult[]>("search_comments", params);
+
+// --- Custom fields ---
+
+export const getCustomFields = (libraryId: string) =>
+  invoke<CustomField[]>("get_custom_fields", { libraryId });
+
+export const createCustomField = (params: {
+  libraryId: string;
+  name: string;
+  fieldType: CustomFieldType;
+  options?: string[];
+}) => invoke<CustomField>("create_custom_field", params);
+
+export const updateCustomField = (params: {
+  id: string;
+  name?: string;
+  options?: string[];
+  position?: number;
+}) => invoke<CustomField>("update_custom_field", params);
+
+export const deleteCustomField = (id: string) =>
+  invoke<void>("delete_custom_field", { id });
+
+/** Pass null to clear the value */
+export const setAssetFieldValue = (
+  assetId: string,
+  fieldId: string,
+  value: string | number | boolean | null
+) => invoke<AssetFieldValue[]>("set_asset_field_value", { assetId, fieldId, value });
+
+export const bulkSetFieldValue = (
+  assetIds: string[],
+  fieldId: string,
+  value: string | number | boolean | null
+) => invoke<void>("bulk_set_field_value", { assetIds, fieldId, value });
+
 // --- Tag ---
 
 export const createTag = (
   libraryId: string,
   name: string,
   color?: string,
-  category?: string
-) => invoke<Tag>("create_tag", { libraryId, name, color, category });
+  category?: string,
+  parentId?: string
+) => invoke<Tag>("create_tag", { libraryId, name, color, category, parentId });
 
 export const listTags = (libraryId: string, category?: string) =>
   invoke<TagWithCount[]>("list_tags", { libraryId, category });
@@ -97,14 +342,51 @@ export const listTags = (libraryId: string, category?: string) =>
 export const renameTag = (id: string, newName: string) =>
   invoke<void>("rename_tag", { id, newName });
 
+/** Moves the tag and its subtree; a null parentId moves it to the top level */
+export const moveTag = (id: string, parentId: string | null) =>
+  invoke<Tag>("move_tag", { id, parentId });
+
+/** Also deletes all tags below it */
 export const deleteTag = (id: string) => invoke<void>("delete_tag", { id });
 
+/** Merges the sources into the target; keepAliases (default true) keeps their paths as aliases */
+export const mergeTags = (sourceIds: string[], targetId: string, keepAliases?: boolean) =>
+  invoke<Tag>("merge_tags", { sourceIds, targetId, keepAliases });
+
+export const addTagAlias = (tagId: string, alias: string) =>
+  invoke<TagAlias>("add_tag_alias", { tagId, alias });
+
+export const removeTagAlias = (libraryId: string, alias: string) =>
+  invoke<void>("remove_tag_alias", { libraryId, alias });
+
+export const getTagAliases = (libraryId: string) =>
+  invoke<TagAlias[]>("get_tag_aliases", { libraryId });
+
 export const assignTags = (assetId: string, tagIds: string[]) =>
   invoke<void>("assign_tags", { assetId, tagIds });
 
 export const removeTags = (assetId: string, tagIds: string[]) =>
   invoke<void>("remove_tags", { assetId, tagIds });
 
+/** Resolves to the number of new tag assignments */
+export const bulkAssignTags = (assetIds: string[], tagIds: string[]) =>
+  invoke<number>("bulk_assign_tags", { assetIds, tagIds });
+
+/** Resolves to the number of removed tag assignments */
+export const bulkRemoveTags = (assetIds: string[], tagIds: string[]) =>
+  invoke<number>("bulk_remove_tags", { assetIds, tagIds });
+
+// --- Bulk operations on queries ---
+
+/** Applies the operation to every asset matching the query; dryRun only counts the assets it would apply to */
+export const bulkQueryOperation = (
+  libraryId: string,
+  query: SmartCollectionQuery,
+  operation: BulkOperation,
+  dryRun: boolean
+) =>
+  invoke<BulkQueryResult>("bulk_query_operation", { libraryId, query, operation, dryRun });
+
 export const getAssetTags = (assetId: string) =>
   invoke<Tag[]>("get_asset_tags", { assetId });
 
@@ -114,21 +396,57 @@ export const searchKeyword = (params: {
   libraryId: string;
   query: string;
   tagIds?: string[];
+  /** Also match tags below tagIds */
+  includeTagDescendants?: boolean;
   fileType?: string;
+  filter?: AssetFilter;
   page: number;
   pageSize: number;
+  /** Defaults to newest first */
+  sortBy?: string;
+  sortOrder?: string;
+  cursor?: string;
 }) => invoke<PaginatedAssets>("search_keyword", params);
 
-export const searchByTags = (
-  libraryId: string,
-  tagIds: string[],
-  matchAll: boolean
-) => invoke<Asset[]>("search_by_tags", { libraryId, tagIds, matchAll });
+export const searchByTags = (params: {
+  libraryId: string;
+  tagIds: string[];
+  matchAll: boolean;
+  filter?: AssetFilter;
+  /** Lets a parent tag match assets tagged with tags below it */
+  includeDescendants?: boolean;
+  page: number;
+  pageSize: number;
+  /** Defaults to newest first */
+  sortBy?: string;
+  sortOrder?: string;
+  cursor?: string;
+}) => invoke<PaginatedAssets>("search_by_tags", params);
+
+/** color is "#rrggbb"; maxDistance is a CIELAB ΔE, minCoverage a share of the image (0-1) */
+export const searchByColor = (params: {
+  libraryId: string;
+  color: string;
+  maxDistance?: number;
+  minCoverage?: number;
+  limit?: number;
+}) => invoke<ColorMatch[]>("search_by_color", params);
 
 // --- AI ---
 
-export const aiTagAsset = (assetId: string) =>
-  invoke<AiTagResult>("ai_tag_asset", { assetId });
+/** applyName overrides the library's auto-apply setting for the suggested name */
+export const aiTagAsset = (assetId: string, applyName?: boolean) =>
+  invoke<AiTagResult>("ai_tag_asset", { assetId, applyName });
+
+export const getNameSuggestions = (libraryId: string) =>
+  invoke<NameSuggestion[]>("get_name_suggestions", { libraryId });
+
+/** Suggestions that cannot be applied report an error and stay pending */
+export const acceptNameSuggestions = (assetIds: string[]) =>
+  invoke<RenamePreview[]>("accept_name_suggestions", { assetIds });
+
+export const rejectNameSuggestions = (assetIds: string[]) =>
+  invoke<void>("reject_name_suggestions", { assetIds });
 
 export const aiSemanticSearch = (
   libraryId: string,
@@ -160,6 +478,15 @@ export const mergeSpritesheet = (params: {
   descriptorFormat: string;
 }) => invoke<SpritesheetResult>("merge_spritesheet", params);
 
+export const exportAsepriteTag = (params: {
+  assetId: string;
+  tagName?: string;
+  columns: number;
+  padding: number;
+  outputName: string;
+  descriptorFormat: string;
+}) => invoke<SpritesheetResult>("export_aseprite_tag", params);
+
 export const splitImage = (assetId: string, rows: number, cols: number) =>
   invoke<Asset[]>("split_image", { assetId, rows, cols });
 
@@ -179,6 +506,16 @@ export const compressImage = (params: {
   suffix: string;
 }) => invoke<CompressResult>("compress_image", params);
 
+export const getAssetSources = (assetId: string) =>
+  invoke<AssetLineage | null>("get_asset_sources", { assetId });
+
+export const getAssetDerivatives = (assetId: string) =>
+  invoke<DerivedAsset[]>("get_asset_derivatives", { assetId });
+
+/** Regenerate all outputs of the run that produced an asset; previous files become versions */
+export const rerunProcessing = (assetId: string) =>
+  invoke<Asset[]>("rerun_processing", { assetId });
+
 // --- Menu ---
 
 export const updateMenuLanguage = (language: string) =>
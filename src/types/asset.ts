@@ -1,10 +1,22 @@
+/** File extensions offered by the import file pickers */
+export const SUPPORTED_EXTENSIONS = [
+  "png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "tiff",
+  "aseprite", "ase",
+  "tmx", "tmj", "tsx", "tsj",
+  "mp3", "wav", "ogg", "flac", "aac", "m4a",
+  "mp4", "avi", "mov", "webm",
+  "json", "xml", "yaml", "yml", "toml", "txt", "md", "csv", "ini", "cfg",
+  "glsl", "vert", "frag", "comp", "geom", "tesc", "tese",
+  "hlsl", "hlsli", "fx", "fxh", "wgsl", "shader", "cginc",
+];
+
 export interface Asset {
   id: string;
   library_id: string;
   file_name: string;
   original_name: string;
   relative_path: string;
-  file_type: "image" | "audio" | "video" | "other";
+  file_type: "image" | "audio" | "video" | "tilemap" | "text" | "other";
   mime_type: string;
   file_size: number;
   file_hash: string;
@@ -15,27 +27,477 @@ export interface Asset {
   ai_description: string;
   thumbnail_path: string | null;
   folder_path: string;
+  /** 0 (unrated) to 5 stars */
+  rating: number;
+  color_label: ColorLabel | null;
+  is_favorite: boolean;
   created_at: string;
   updated_at: string;
   imported_at: string;
 }
 
+export type ColorLabel = "red" | "orange" | "yellow" | "green" | "blue" | "purple" | "gray";
+
 export interface PaginatedAssets {
   assets: Asset[];
   total: number;
   page: number;
   page_size: number;
+  /** Pass as `cursor` to fetch the next page; null on the last page */
+  next_cursor: string | null;
+}
+
+export interface FormatMetadata {
+  format: string;
+  data: Record<string, unknown>;
+}
+
+export interface AssetLink {
+  id: string;
+  asset_id: string;
+  target_asset_id: string | null;
+  link_type: string;
+  reference_path: string;
+  reference_hash: string;
+  created_at: string;
+}
+
+export interface ImageMetadata {
+  asset_id: string;
+  color_type: string;
+  bit_depth: number;
+  has_alpha: boolean;
+  alpha_used: boolean;
+  frame_count: number;
+  has_icc_profile: boolean;
+  icc_profile_size: number | null;
+  dpi_x: number | null;
+  dpi_y: number | null;
+  text_entries: Record<string, string>;
+  updated_at: string;
+}
+
+export type CustomFieldType = "text" | "number" | "boolean" | "enum" | "date";
+
+export interface CustomField {
+  id: string;
+  library_id: string;
+  name: string;
+  field_type: CustomFieldType;
+  /** Allowed values of enum fields */
+  options: string[];
+  position: number;
+  created_at: string;
+}
+
+export interface AssetFieldValue {
+  field_id: string;
+  name: string;
+  field_type: CustomFieldType;
+  /** Dates are "YYYY-MM-DD" strings */
+  value: string | number | boolean | null;
+}
+
+export interface FieldCondition {
+  field_id: string;
+  op: "eq" | "ne" | "lt" | "lte" | "gt" | "gte" | "contains" | "set" | "unset";
+  value?: string | number | boolean | null;
+}
+
+export interface AssetFilter {
+  has_alpha?: boolean;
+  alpha_used?: boolean;
+  animated?: boolean;
+  has_icc_profile?: boolean;
+  color_type?: string;
+  language?: string;
+  /** All conditions must match */
+  fields?: FieldCondition[];
+  min_rating?: number;
+  max_rating?: number;
+  color_label?: ColorLabel;
+  /** Only favorites (or only non-favorites) */
+  favorite?: boolean;
+  /** License identifier, e.g. "CC-BY-4.0" */
+  license?: string;
+  has_license?: boolean;
+  requires_attribution?: boolean;
+  /** Only assets found (or not found) in a scanned game project */
+  used?: boolean;
+  folder_path?: string;
+  /** Also match assets in subfolders of folder_path */
+  include_subfolders?: boolean;
+  /** Any of these, e.g. "image/png" */
+  mime_types?: string[];
+  /** File size in bytes */
+  min_size?: number;
+  max_size?: number;
+  min_width?: number;
+  max_width?: number;
+  min_height?: number;
+  max_height?: number;
+  /** Width divided by height */
+  min_aspect?: number;
+  max_aspect?: number;
+  /** "YYYY-MM-DD" or "YYYY-MM-DD HH:MM:SS" (UTC); "after" is inclusive, "before" exclusive */
+  imported_after?: string;
+  imported_before?: string;
+  updated_after?: string;
+  updated_before?: string;
+  tagged?: boolean;
+  has_ai_description?: boolean;
+  has_embedding?: boolean;
+}
+
+export interface AssetUsage {
+  id: string;
+  library_id: string;
+  asset_id: string;
+  project_path: string;
+  /** Relative to the project */
+  file_path: string;
+  match_kind: "exact" | "perceptual";
+  scanned_at: string;
+}
+
+export interface UsageProject {
+  project_path: string;
+  asset_count: number;
+  file_count: number;
+  scanned_at: string;
+}
+
+export interface UsageScanResult {
+  project_path: string;
+  files_scanned: number;
+  used_assets: number;
+  usages: AssetUsage[];
+}
+
+/** Dominant color of an image, in CIELAB with its hex value */
+export interface PaletteColor {
+  hex: string;
+  l: number;
+  a: number;
+  b: number;
+  /** Share of the visible pixels, 0-1 */
+  coverage: number;
+}
+
+export interface ColorMatch {
+  asset: Asset;
+  /** CIELAB ΔE of the closest palette color */
+  distance: number;
+  /** Share of the image covered by matching colors */
+  coverage: number;
+}
+
+export interface AssetLicense {
+  asset_id: string;
+  /** e.g. "CC-BY-4.0", "CC0-1.0", "purchased" or "in-house" */
+  license: string;
+  author: string;
+  source_url: string;
+  /** Credit line used instead of one built from author and license */
+  attribution: string;
+  requires_attribution: boolean;
+  updated_at: string;
+}
+
+export interface LicenseUpdate {
+  license?: string;
+  author?: string;
+  source_url?: string;
+  attribution?: string;
+  /** Otherwise derived from the license */
+  requires_attribution?: boolean;
+}
+
+export interface LicenseUsage {
+  license: string;
+  requires_attribution: boolean;
+  asset_count: number;
+}
+
+export interface TextContentInfo {
+  asset_id: string;
+  language: string;
+  byte_size: number;
+  line_count: number;
+  truncated: boolean;
+  updated_at: string;
+}
+
+export interface AssetVersion {
+  id: string;
+  asset_id: string;
+  version_number: number;
+  relative_path: string;
+  original_name: string;
+  file_type: string;
+  mime_type: string;
+  file_size: number;
+  file_hash: string;
+  width: number | null;
+  height: number | null;
+  duration_ms: number | null;
+  thumbnail_path: string | null;
+  note: string;
+  created_at: string;
+}
+
+export interface AssetDerivation {
+  id: string;
+  asset_id: string;
+  operation:
+    | "remove_background"
+    | "split_image"
+    | "merge_spritesheet"
+    | "export_aseprite_tag"
+    | "compress_image";
+  /** Operation parameters, including the operation name */
+  params: Record<string, unknown>;
+  batch_id: string;
+  output_index: number;
+  created_at: string;
+}
+
+export interface DerivationSource {
+  position: number;
+  source_asset_id: string | null;
+  source_name: string;
+  /** Null once the source has been deleted */
+  asset: Asset | null;
+}
+
+export interface AssetLineage {
+  derivation: AssetDerivation;
+  sources: DerivationSource[];
+}
+
+export interface DerivedAsset extends Asset {
+  operation: AssetDerivation["operation"];
+  params: Record<string, unknown>;
+}
+
+export interface SmartCollectionQuery {
+  /** Keyword query, matched like keyword search */
+  text?: string;
+  tag_ids?: string[];
+  /** Require all tags instead of any */
+  match_all_tags?: boolean;
+  file_type?: string;
+  folder_path?: string;
+  include_subfolders?: boolean;
+  min_width?: number;
+  max_width?: number;
+  min_height?: number;
+  max_height?: number;
+  filter?: AssetFilter;
+}
+
+export interface Collection {
+  id: string;
+  library_id: string;
+  name: string;
+  description: string;
+  kind: "manual" | "smart";
+  /** Query of smart collections */
+  query: SmartCollectionQuery | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CollectionInfo extends Collection {
+  asset_count: number;
+}
+
+export interface BatchRenameOptions {
+  /** e.g. "{folder}_{index:03}" or "{tag:character|snake}_{width}x{height}" */
+  template?: string;
+  /** Value of {index} for the first asset */
+  start_index?: number;
+  case?: "lower" | "upper" | "title" | "snake" | "kebab" | "camel" | "pascal";
+  find?: string;
+  replace?: string;
+  /** Treat find as a regular expression */
+  regex?: boolean;
+  /** Also rename the files on disk */
+  rename_files?: boolean;
+}
+
+export interface RenamePreview {
+  asset_id: string;
+  old_name: string;
+  new_name: string;
+  /** Why the asset cannot be renamed, e.g. a name collision */
+  error: string | null;
+}
+
+export interface BatchRenameResult {
+  items: RenamePreview[];
+  applied: boolean;
+}
+
+export type ExportSelection =
+  | { kind: "assets"; asset_ids: string[] }
+  | { kind: "folder"; library_id: string; folder_path: string; include_subfolders?: boolean }
+  | { kind: "collection"; collection_id: string }
+  | { kind: "search"; library_id: string; query: string }
+  | { kind: "query"; library_id: string; query: SmartCollectionQuery };
+
+export interface ExportOptions {
+  destination: string;
+  /** Recreate the library's folder structure below the destination */
+  keep_folders?: boolean;
+  on_conflict?: "skip" | "overwrite" | "rename";
+  image_format?: "png" | "jpeg" | "webp";
+  max_width?: number;
+  max_height?: number;
+  /** JPEG quality, 1-100 */
+  quality?: number;
+  strip_metadata?: boolean;
+  /** Write export_manifest.json into the destination (default true) */
+  write_manifest?: boolean;
+}
+
+export interface ExportedFile {
+  asset_id: string;
+  file_name: string;
+  folder_path: string;
+  tags: string[];
+  status: "exported" | "skipped" | "failed";
+  /** Path relative to the destination */
+  path: string | null;
+  file_size: number | null;
+  width: number | null;
+  height: number | null;
+  converted: boolean;
+  error: string | null;
+}
+
+export interface ExportResult {
+  destination: string;
+  files: ExportedFile[];
+  exported: number;
+  skipped: number;
+  failed: number;
+  manifest_path: string | null;
+}
+
+/** Operation applied to every asset matching a query */
+export type BulkOperation =
+  | { kind: "tag"; tag_ids: string[] }
+  | { kind: "untag"; tag_ids: string[] }
+  | { kind: "move"; target_folder: string }
+  | { kind: "delete" }
+  | { kind: "ai_tag"; apply_name?: boolean | null }
+  | {
+      kind: "compress";
+      max_width: number | null;
+      max_height: number | null;
+      quality: number;
+      output_format: "jpeg" | "png";
+      suffix?: string;
+    }
+  | { kind: "export"; options: ExportOptions };
+
+export interface BulkFailure {
+  asset_id: string;
+  error: string;
+}
+
+export interface BulkQueryResult {
+  matched: number;
+  /** false for dry runs */
+  applied: boolean;
+  failures: BulkFailure[];
+  export: ExportResult | null;
+}
+
+/** Review comment; replies have a parent_id */
+export interface AssetComment {
+  id: string;
+  asset_id: string;
+  parent_id: string | null;
+  author: string;
+  body: string;
+  /** Point or rectangle on an image, in pixels */
+  anchor_x: number | null;
+  anchor_y: number | null;
+  anchor_width: number | null;
+  anchor_height: number | null;
+  /** Time in audio or video */
+  anchor_time_ms: number | null;
+  resolved: boolean;
+  resolved_at: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CommentAnchor {
+  x?: number;
+  y?: number;
+  width?: number;
+  height?: number;
+  time_ms?: number;
+}
+
+export interface CommentSearchResult {
+  asset: Asset;
+  comments: AssetComment[];
+  license: AssetLicense | null;
+  /** Dominant colors of images, most common first */
+  palette: PaletteColor[];
+  /** Where the asset is used in scanned game projects */
+  usages: AssetUsage[];
 }
 
 export interface AssetDetail {
   asset: Asset;
   tags: Tag[];
+  format_metadata: FormatMetadata | null;
+  links: AssetLink[];
+  image_metadata: ImageMetadata | null;
+  text_content: TextContentInfo | null;
+  /** Previous files, newest first */
+  versions: AssetVersion[];
+  /** Values of custom fields set on this asset */
+  fields: AssetFieldValue[];
+  /** Manual collections containing this asset */
+  collections: Collection[];
+  /** Review comments and replies, oldest first */
+  comments: AssetComment[];
+  /** How the asset was produced, if it is a processing output */
+  derived_from: AssetLineage | null;
+  derivatives: DerivedAsset[];
 }
 
 export interface FolderInfo {
-  path: string;
+  id: string;
+  library_id: string;
+  parent_id: string | null;
   name: string;
+  /** Canonical path: "/a/b" */
+  path: string;
+  color: string | null;
+  icon: string | null;
+  description: string;
+  default_tag_ids: string[];
+  created_at: string;
+  updated_at: string;
+  /** Assets directly in this folder */
   asset_count: number;
+  /** Assets in this folder and all subfolders */
+  total_asset_count: number;
+}
+
+export interface FolderTreeSummary {
+  path: string;
+  /** The folder itself and all subfolders */
+  folder_count: number;
+  asset_count: number;
+  total_size: number;
 }
 
 export interface Tag {
@@ -46,10 +508,24 @@ export interface Tag {
   category: string;
   is_ai: boolean;
   created_at: string;
+  parent_id: string | null;
+  /** e.g. "character/enemy/boss" */
+  path: string;
 }
 
 export interface TagWithCount extends Tag {
   asset_count: number;
+  /** Including assets tagged with tags below this one */
+  total_count: number;
+}
+
+/** Another name that resolves to a tag, e.g. "trees" or "树" for "tree" */
+export interface TagAlias {
+  library_id: string;
+  alias: string;
+  tag_id: string;
+  created_at: string;
+  tag_path: string;
 }
 
 export interface ScoredAsset {
@@ -61,6 +537,16 @@ export interface AiTagResult {
   tags: Tag[];
   description: string;
   suggested_name: string | null;
+  /** New file name if the suggested name was applied */
+  applied_name: string | null;
+}
+
+export interface NameSuggestion {
+  asset_id: string;
+  file_name: string;
+  folder_path: string;
+  suggested_name: string;
+  created_at: string;
 }
 
 export interface AiConfig {
@@ -88,6 +574,8 @@ export interface Library {
   root_path: string;
   created_at: string;
   updated_at: string;
+  /** Apply AI suggested names right away instead of queueing them for review */
+  auto_apply_ai_names: boolean;
 }
 
 export interface SpritesheetResult {